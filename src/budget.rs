@@ -0,0 +1,119 @@
+use crate::error::{ErrorKind, RuntimeError};
+use std::time::{Duration, Instant};
+
+/// Hard limits for running untrusted code. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Loop iterations plus function calls.
+    pub max_steps: Option<u64>,
+    /// Wall-clock time, measured from when the limits are set.
+    pub timeout: Option<Duration>,
+    /// Total size of all lists, maps, strings and big integers created: one
+    /// unit per list element or map entry, and per byte of string or integer
+    /// data.
+    pub max_memory: Option<usize>,
+    /// Bytes written to stdout and stderr.
+    pub max_output: Option<usize>,
+}
+
+/// How often `Budget::step` looks at the clock.
+const CLOCK_INTERVAL: u64 = 256;
+
+/// Tracks what a script has used against its `Limits`.
+#[derive(Debug, Clone)]
+pub struct Budget {
+    limits: Limits,
+    steps: u64,
+    memory: usize,
+    output: usize,
+    deadline: Option<Instant>,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self::new(Limits::default())
+    }
+}
+
+impl Budget {
+    pub fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            steps: 0,
+            memory: 0,
+            output: 0,
+            deadline: limits.timeout.map(|timeout| Instant::now() + timeout),
+        }
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn memory(&self) -> usize {
+        self.memory
+    }
+
+    pub fn output(&self) -> usize {
+        self.output
+    }
+
+    /// Counts one loop iteration or call, also checking the time limit.
+    pub fn step(&mut self) -> Result<(), RuntimeError> {
+        self.steps += 1;
+        if let Some(max) = self.limits.max_steps
+            && self.steps > max
+        {
+            return Err(exceeded(
+                ErrorKind::StepLimit,
+                format!("step limit of {} exceeded", max),
+            ));
+        }
+        if self.steps.is_multiple_of(CLOCK_INTERVAL) {
+            self.check_time()?;
+        }
+        Ok(())
+    }
+
+    pub fn check_time(&self) -> Result<(), RuntimeError> {
+        match (self.deadline, self.limits.timeout) {
+            (Some(deadline), Some(timeout)) if Instant::now() >= deadline => Err(exceeded(
+                ErrorKind::TimeLimit,
+                format!("time limit of {}ms exceeded", timeout.as_millis()),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Counts `size` more units of memory, as defined by `Limits::max_memory`.
+    pub fn allocate(&mut self, size: usize) -> Result<(), RuntimeError> {
+        self.memory = self.memory.saturating_add(size);
+        match self.limits.max_memory {
+            Some(max) if self.memory > max => Err(exceeded(
+                ErrorKind::MemoryLimit,
+                format!("memory limit of {} exceeded", max),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Counts `bytes` more bytes of output, before they are written.
+    pub fn write(&mut self, bytes: usize) -> Result<(), RuntimeError> {
+        self.output = self.output.saturating_add(bytes);
+        match self.limits.max_output {
+            Some(max) if self.output > max => Err(exceeded(
+                ErrorKind::OutputLimit,
+                format!("output limit of {} bytes exceeded", max),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn exceeded(kind: ErrorKind, message: String) -> RuntimeError {
+    RuntimeError::new(kind, message)
+}
@@ -0,0 +1,173 @@
+use crate::node::{Node, SpannedNode};
+use crate::nodes::expressions::assignment::Target;
+use crate::nodes::{
+    Assignment, Block, Break, ClassDef, CompoundAssignment, Continue, ForNode, FunctionDef, If,
+    Lambda, Literal, MethodDef, Program, WhileNode, Yield,
+};
+
+/// A problem found by the static checks that run after parsing.
+#[derive(Debug, Clone)]
+pub struct CheckError {
+    pub message: String,
+    pub line: usize,
+    /// Defaults to the start of the line.
+    pub column: Option<usize>,
+}
+
+/// Runs the static checks on a freshly parsed tree.
+pub fn check(node: &dyn Node) -> Result<(), CheckError> {
+    check_jumps(node, &mut Vec::new())?;
+    check_literals(node)?;
+    check_yields(node, false, true)?;
+    check_targets(node)
+}
+
+/// Reports assignments to expressions that cannot be assigned to, such as
+/// `f() = 1`, and declarations of anything but a plain variable.
+fn check_targets(node: &dyn Node) -> Result<(), CheckError> {
+    let any = node.as_any();
+    let invalid_line = if let Some(assignment) = any.downcast_ref::<Assignment>() {
+        matches!(assignment.target, Target::Invalid).then_some(assignment.line)
+    } else {
+        any.downcast_ref::<CompoundAssignment>()
+            .filter(|assignment| matches!(assignment.target, Target::Invalid))
+            .map(|assignment| assignment.line)
+    };
+    if let Some(line) = invalid_line {
+        return Err(CheckError {
+            message: "Invalid assignment target".to_string(),
+            line,
+            column: None,
+        });
+    }
+    node.children().into_iter().try_for_each(check_targets)
+}
+
+/// Reports literals whose text could not be converted to a value, such as
+/// integers that do not fit in an `Int`.
+fn check_literals(node: &dyn Node) -> Result<(), CheckError> {
+    if let Some(literal) = node.as_any().downcast_ref::<Literal>()
+        && let Some(message) = &literal.error
+    {
+        return Err(CheckError {
+            message: message.clone(),
+            line: literal.line,
+            column: Some(literal.column),
+        });
+    }
+    node.children().into_iter().try_for_each(check_literals)
+}
+
+/// Verifies that every `yield` is in a function, nested only in statements a
+/// generator can suspend and resume: blocks, `if`, `while` and `for`.
+fn check_yields(node: &dyn Node, in_function: bool, resumable: bool) -> Result<(), CheckError> {
+    let any = node.as_any();
+
+    if let Some(node) = any.downcast_ref::<Yield>() {
+        let message = if !in_function {
+            "Yield outside of a function"
+        } else if !resumable {
+            "Yield is only supported directly inside blocks, if, while and for"
+        } else {
+            return Ok(());
+        };
+        return Err(CheckError {
+            message: message.to_string(),
+            line: node.line,
+            column: None,
+        });
+    }
+
+    // A class's children are its method bodies.
+    let (in_function, resumable) = if any.is::<FunctionDef>()
+        || any.is::<Lambda>()
+        || any.is::<MethodDef>()
+        || any.is::<ClassDef>()
+    {
+        (true, true)
+    } else {
+        let suspendable = any.is::<SpannedNode>()
+            || any.is::<Program>()
+            || any.is::<Block>()
+            || any.is::<If>()
+            || any.is::<WhileNode>()
+            || any.is::<ForNode>();
+        (in_function, resumable && suspendable)
+    };
+    node.children()
+        .into_iter()
+        .try_for_each(|child| check_yields(child, in_function, resumable))
+}
+
+/// Verifies that every `break`/`continue` sits inside a loop of the same
+/// function, and that labelled jumps name an enclosing loop.
+fn check_jumps(node: &dyn Node, loops: &mut Vec<Option<String>>) -> Result<(), CheckError> {
+    let any = node.as_any();
+
+    if let Some(jump) = any.downcast_ref::<Break>() {
+        return check_jump("Break", &jump.label, jump.line, loops);
+    }
+    if let Some(jump) = any.downcast_ref::<Continue>() {
+        return check_jump("Continue", &jump.label, jump.line, loops);
+    }
+
+    // Function bodies start without any enclosing loop.
+    if any.is::<FunctionDef>()
+        || any.is::<Lambda>()
+        || any.is::<MethodDef>()
+        || any.is::<ClassDef>()
+    {
+        let mut function_loops = Vec::new();
+        for child in node.children() {
+            check_jumps(child, &mut function_loops)?;
+        }
+        return Ok(());
+    }
+
+    let label = if let Some(while_node) = any.downcast_ref::<WhileNode>() {
+        Some(while_node.label.clone())
+    } else {
+        any.downcast_ref::<ForNode>()
+            .map(|for_node| for_node.label.clone())
+    };
+
+    if let Some(label) = label {
+        loops.push(label);
+        let result = node
+            .children()
+            .into_iter()
+            .try_for_each(|child| check_jumps(child, loops));
+        loops.pop();
+        return result;
+    }
+
+    for child in node.children() {
+        check_jumps(child, loops)?;
+    }
+    Ok(())
+}
+
+fn check_jump(
+    kind: &str,
+    label: &Option<String>,
+    line: usize,
+    loops: &[Option<String>],
+) -> Result<(), CheckError> {
+    if loops.is_empty() {
+        return Err(CheckError {
+            message: format!("{} used outside of a loop", kind),
+            line,
+            column: None,
+        });
+    }
+    if let Some(label) = label
+        && !loops.iter().any(|l| l.as_deref() == Some(label))
+    {
+        return Err(CheckError {
+            message: format!("{} refers to unknown loop label '{}'", kind, label),
+            line,
+            column: None,
+        });
+    }
+    Ok(())
+}
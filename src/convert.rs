@@ -0,0 +1,221 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::node::Value;
+use crate::numeric;
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Conversion from a Rust value into a script `Value`.
+pub trait IntoValue {
+    fn into_value(self) -> Value;
+}
+
+/// Conversion from a script `Value` into a Rust value.
+pub trait FromValue: Sized {
+    fn from_value(value: Value) -> Result<Self, RuntimeError>;
+}
+
+fn mismatch(expected: &str, value: &Value) -> RuntimeError {
+    RuntimeError::new(
+        ErrorKind::TypeError,
+        format!("Expected {}, got {}", expected, value.type_name()),
+    )
+}
+
+fn out_of_range(n: &dyn std::fmt::Display, target: &str) -> RuntimeError {
+    RuntimeError::new(
+        ErrorKind::Overflow,
+        format!("Int {} does not fit in {}", n, target),
+    )
+}
+
+impl IntoValue for Value {
+    fn into_value(self) -> Value {
+        self
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        Ok(value)
+    }
+}
+
+impl IntoValue for () {
+    fn into_value(self) -> Value {
+        Value::Void
+    }
+}
+
+impl FromValue for () {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        match value {
+            Value::Void => Ok(()),
+            other => Err(mismatch("Void", &other)),
+        }
+    }
+}
+
+impl IntoValue for i64 {
+    fn into_value(self) -> Value {
+        Value::Int(self)
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        match value {
+            Value::Int(n) => Ok(n),
+            Value::BigInt(n) => Err(out_of_range(&n, "i64")),
+            other => Err(mismatch("Int", &other)),
+        }
+    }
+}
+
+impl IntoValue for BigInt {
+    fn into_value(self) -> Value {
+        numeric::int_value(self)
+    }
+}
+
+impl FromValue for BigInt {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        numeric::to_big(&value).ok_or_else(|| mismatch("Int", &value))
+    }
+}
+
+impl IntoValue for Decimal {
+    fn into_value(self) -> Value {
+        Value::Decimal(self)
+    }
+}
+
+/// Accepts integers as well, which convert exactly.
+impl FromValue for Decimal {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        numeric::to_decimal(&value).ok_or_else(|| mismatch("Decimal", &value))
+    }
+}
+
+impl IntoValue for i32 {
+    fn into_value(self) -> Value {
+        Value::Int(self.into())
+    }
+}
+
+impl FromValue for i32 {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        match value {
+            Value::Int(n) => i32::try_from(n).map_err(|_| out_of_range(&n, "i32")),
+            Value::BigInt(n) => Err(out_of_range(&n, "i32")),
+            other => Err(mismatch("Int", &other)),
+        }
+    }
+}
+
+impl IntoValue for f64 {
+    fn into_value(self) -> Value {
+        Value::Float(self)
+    }
+}
+
+/// Accepts integers as well, since scripts freely mix the two.
+impl FromValue for f64 {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        match value {
+            Value::Float(n) => Ok(n),
+            Value::Int(n) => Ok(n as f64),
+            other => Err(mismatch("Float", &other)),
+        }
+    }
+}
+
+impl IntoValue for bool {
+    fn into_value(self) -> Value {
+        Value::Bool(self)
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        match value {
+            Value::Bool(b) => Ok(b),
+            other => Err(mismatch("Bool", &other)),
+        }
+    }
+}
+
+impl IntoValue for String {
+    fn into_value(self) -> Value {
+        Value::String(Rc::new(RefCell::new(self)))
+    }
+}
+
+impl IntoValue for &str {
+    fn into_value(self) -> Value {
+        self.to_string().into_value()
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        match value {
+            Value::String(s) => Ok(s.borrow().clone()),
+            other => Err(mismatch("String", &other)),
+        }
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self) -> Value {
+        let items = self.into_iter().map(IntoValue::into_value).collect();
+        Value::List(Rc::new(RefCell::new(items)))
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        match value {
+            Value::List(l) => l.borrow().iter().cloned().map(T::from_value).collect(),
+            other => Err(mismatch("List", &other)),
+        }
+    }
+}
+
+impl<T: IntoValue> IntoValue for HashMap<String, T> {
+    fn into_value(self) -> Value {
+        let entries = self.into_iter().map(|(k, v)| (k, v.into_value())).collect();
+        Value::Map(Rc::new(RefCell::new(entries)))
+    }
+}
+
+impl<T: FromValue> FromValue for HashMap<String, T> {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        match value {
+            Value::Map(m) => m
+                .borrow()
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), T::from_value(v.clone())?)))
+                .collect(),
+            other => Err(mismatch("Map", &other)),
+        }
+    }
+}
+
+/// `None` maps to `Void` and back.
+impl<T: IntoValue> IntoValue for Option<T> {
+    fn into_value(self) -> Value {
+        self.map_or(Value::Void, IntoValue::into_value)
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        match value {
+            Value::Void => Ok(None),
+            other => T::from_value(other).map(Some),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use crate::node::Value;
+
+pub type Env = Rc<RefCell<Environment>>;
+
+/// One lexical scope in the environment chain.
+///
+/// Blocks and loop iterations get block scopes; calls get a function scope
+/// whose parent is the environment the function was defined in. The global
+/// scope is the root and also counts as a function scope.
+#[derive(Default)]
+pub struct Environment {
+    values: HashMap<String, Value>,
+    parent: Option<Env>,
+    function_scope: bool,
+    // Names that plain assignments in this function resolve beyond it.
+    nonlocals: HashSet<String>,
+}
+
+impl Environment {
+    pub fn new_global() -> Env {
+        Self::new_function(None)
+    }
+
+    pub fn new_function(parent: Option<Env>) -> Env {
+        Rc::new(RefCell::new(Environment {
+            parent,
+            function_scope: true,
+            ..Default::default()
+        }))
+    }
+
+    pub fn new_block(parent: &Env) -> Env {
+        Rc::new(RefCell::new(Environment {
+            parent: Some(parent.clone()),
+            ..Default::default()
+        }))
+    }
+
+    /// Looks a name up through the whole chain.
+    pub fn get(env: &Env, name: &str) -> Option<Value> {
+        let mut current = env.clone();
+        loop {
+            let next = {
+                let scope = current.borrow();
+                if let Some(value) = scope.values.get(name) {
+                    return Some(value.clone());
+                }
+                scope.parent.clone()?
+            };
+            current = next;
+        }
+    }
+
+    /// Whether `env` is the global scope or a block inside it, rather than
+    /// part of a function call.
+    pub fn is_top_level(env: &Env) -> bool {
+        let mut current = env.clone();
+        loop {
+            let next = {
+                let scope = current.borrow();
+                if scope.function_scope {
+                    return scope.parent.is_none();
+                }
+                match &scope.parent {
+                    Some(parent) => parent.clone(),
+                    None => return true,
+                }
+            };
+            current = next;
+        }
+    }
+
+    /// Creates (or shadows) a binding in this scope.
+    pub fn define(&mut self, name: &str, value: Value) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    /// Plain assignment: updates the nearest existing binding inside the
+    /// current function, otherwise creates one in the function scope. Names
+    /// declared `nonlocal` keep searching the enclosing scopes instead.
+    pub fn assign(env: &Env, name: &str, value: Value) -> Result<(), String> {
+        let mut current = env.clone();
+        let mut nonlocal = false;
+        loop {
+            let next = {
+                let mut scope = current.borrow_mut();
+                if let Some(slot) = scope.values.get_mut(name) {
+                    *slot = value;
+                    return Ok(());
+                }
+                if scope.function_scope && !nonlocal {
+                    if scope.nonlocals.contains(name) {
+                        nonlocal = true;
+                    } else {
+                        scope.values.insert(name.to_string(), value);
+                        return Ok(());
+                    }
+                }
+                scope.parent.clone()
+            };
+            match next {
+                Some(next) => current = next,
+                None => return Err(format!("No binding for nonlocal variable '{}'", name)),
+            }
+        }
+    }
+
+    /// Marks `name` as referring to an outer binding for the innermost
+    /// function enclosing `env`.
+    pub fn declare_nonlocal(env: &Env, name: &str) {
+        let mut current = env.clone();
+        loop {
+            let next = {
+                let mut scope = current.borrow_mut();
+                if scope.function_scope {
+                    scope.nonlocals.insert(name.to_string());
+                    return;
+                }
+                scope.parent.clone()
+            };
+            match next {
+                Some(next) => current = next,
+                None => return,
+            }
+        }
+    }
+}
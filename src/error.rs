@@ -1,3 +1,5 @@
+use crate::node::Value;
+
 #[derive(Debug, Clone)]
 pub struct ParseError {
     pub message: String,
@@ -16,19 +18,208 @@ impl std::fmt::Display for ParseError {
     }
 }
 
+/// What kind of failure a `RuntimeError` reports. Each kind has a stable
+/// code (see `ErrorKind::code`) for tests, `catch` handlers and tooling to
+/// match on instead of the message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// An error without a more specific kind.
+    Other,
+    /// An operation was applied to a value of the wrong type.
+    TypeError,
+    /// A variable, function, class or `this` is not defined.
+    NameError,
+    /// A list or string index is out of bounds.
+    IndexError,
+    /// An object has no field or method of the given name. A missing map key
+    /// is not an error: `get` and `m[key]` give `Void` for it.
+    KeyError,
+    /// Division or modulo by zero.
+    ZeroDivision,
+    /// A function, method or class was called with the wrong number of
+    /// arguments.
+    ArityError,
+    /// An argument has the right type but an unusable value, such as text
+    /// that is not a number.
+    ValueError,
+    /// Reading a file or writing output failed.
+    IOError,
+    /// A number does not fit the type it is converted to, or a decimal
+    /// result is out of range.
+    Overflow,
+    /// Calls nested deeper than `Context::max_depth`.
+    RecursionLimit,
+    /// More loop iterations and calls than `Limits::max_steps`.
+    StepLimit,
+    /// Ran longer than `Limits::timeout`.
+    TimeLimit,
+    /// Allocated more than `Limits::max_memory`.
+    MemoryLimit,
+    /// Printed more than `Limits::max_output`.
+    OutputLimit,
+    /// Raised by a `throw` statement.
+    Thrown,
+}
+
+impl ErrorKind {
+    /// Whether `catch` can handle errors of this kind. Exhausted execution
+    /// budgets always end the script.
+    pub fn is_catchable(self) -> bool {
+        !matches!(
+            self,
+            ErrorKind::StepLimit
+                | ErrorKind::TimeLimit
+                | ErrorKind::MemoryLimit
+                | ErrorKind::OutputLimit
+        )
+    }
+
+    /// The kind's stable code. Codes are never reused or renumbered.
+    pub fn code(self) -> &'static str {
+        match self {
+            ErrorKind::Other => "E0001",
+            ErrorKind::TypeError => "E0002",
+            ErrorKind::NameError => "E0003",
+            ErrorKind::IndexError => "E0004",
+            ErrorKind::KeyError => "E0005",
+            ErrorKind::ZeroDivision => "E0006",
+            ErrorKind::ArityError => "E0007",
+            ErrorKind::ValueError => "E0008",
+            ErrorKind::IOError => "E0009",
+            ErrorKind::Overflow => "E0010",
+            ErrorKind::RecursionLimit => "E0011",
+            ErrorKind::StepLimit => "E0012",
+            ErrorKind::TimeLimit => "E0013",
+            ErrorKind::MemoryLimit => "E0014",
+            ErrorKind::OutputLimit => "E0015",
+            ErrorKind::Thrown => "E0016",
+        }
+    }
+}
+
+/// The source range of a node, from its first character up to (but not
+/// including) the character after it. Lines and columns start at 1; they are
+/// `u32` to keep `RuntimeError` small.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: u32,
+    pub column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// A call the error propagated through: the called function and the line
+/// of the call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub function: String,
+    pub line: usize,
+}
+
+impl std::fmt::Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "at {}:{}", self.function, self.line)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RuntimeError {
+    pub kind: ErrorKind,
     pub message: String,
-    pub stack_trace: Vec<String>, // List of "at <function>:<line>"
+    /// The innermost node that failed, if it came from parsed source.
+    pub span: Option<Span>,
+    /// Calls the error propagated through, innermost first.
+    pub stack_trace: Vec<Frame>,
+    /// Frames beyond `MAX_STACK_TRACE` that were dropped.
+    pub omitted_frames: usize,
+    /// The value raised by a `throw`, if the error came from one.
+    pub thrown: Option<Value>,
+}
+
+/// Frames kept in a stack trace; deeper ones are only counted in
+/// `RuntimeError::omitted_frames`.
+pub const MAX_STACK_TRACE: usize = 20;
+
+impl RuntimeError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        RuntimeError {
+            kind,
+            message: message.into(),
+            span: None,
+            stack_trace: vec![],
+            omitted_frames: 0,
+            thrown: None,
+        }
+    }
+
+    /// Adds the frame of a call the error is propagating through.
+    pub fn push_frame(&mut self, function: &str, line: usize) {
+        if self.stack_trace.len() < MAX_STACK_TRACE {
+            self.stack_trace.push(Frame {
+                function: function.to_string(),
+                line,
+            });
+        } else {
+            self.omitted_frames += 1;
+        }
+    }
 }
 
 impl std::fmt::Display for RuntimeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Runtime Error: {}\nStack Trace:\n{}",
-            self.message,
-            self.stack_trace.join("\n")
-        )
+        write!(f, "Runtime Error [{}]", self.kind.code())?;
+        if let Some(span) = &self.span {
+            write!(f, " at {}", span)?;
+        }
+        write!(f, ": {}\nStack Trace:\n", self.message)?;
+        let mut lines: Vec<String> = self.stack_trace.iter().map(Frame::to_string).collect();
+        if self.omitted_frames > 0 {
+            lines.push(format!("... {} more frames", self.omitted_frames));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Error returned by the `Interpreter` facade.
+#[derive(Debug, Clone)]
+pub enum InterpreterError {
+    Parse(ParseError),
+    Runtime(RuntimeError),
+    /// A source file could not be read.
+    Io {
+        path: String,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for InterpreterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InterpreterError::Parse(e) => write!(f, "{}", e),
+            InterpreterError::Runtime(e) => write!(f, "{}", e),
+            InterpreterError::Io { path, message } => {
+                write!(f, "Failed to read '{}': {}", path, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InterpreterError {}
+
+impl From<ParseError> for InterpreterError {
+    fn from(e: ParseError) -> Self {
+        InterpreterError::Parse(e)
+    }
+}
+
+impl From<RuntimeError> for InterpreterError {
+    fn from(e: RuntimeError) -> Self {
+        InterpreterError::Runtime(e)
     }
 }
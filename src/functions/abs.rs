@@ -1,20 +1,13 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
+use crate::numeric;
 
-pub fn abs_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(RuntimeError {
-            message: format!("abs expects 1 argument, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
-
+pub fn abs_fn(_call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match &args[0] {
-        Value::Int(n) => Ok(Value::Int(n.abs())),
         Value::Float(n) => Ok(Value::Float(n.abs())),
-        _ => Err(RuntimeError {
-            message: "abs expects a number (int or float)".to_string(),
-            stack_trace: vec![],
+        other => numeric::abs(other).ok_or_else(|| {
+            RuntimeError::new(ErrorKind::TypeError, "abs expects a number (int or float)")
         }),
     }
 }
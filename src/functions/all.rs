@@ -0,0 +1,21 @@
+use crate::error::RuntimeError;
+use crate::functions::callback::{callable_arg, list_arg};
+use crate::functions::registry::CallCtx;
+use crate::node::Value;
+
+/// `all(list)` or `all(list, predicate)`; stops at the first falsy result.
+pub fn all_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let items = list_arg("all", &args[0])?;
+    let predicate = args.get(1).map(|p| callable_arg("all", p)).transpose()?;
+
+    for item in items {
+        let value = match &predicate {
+            Some(predicate) => call.call_function(predicate, vec![item])?,
+            None => item,
+        };
+        if !value.is_truthy() {
+            return Ok(Value::Bool(false));
+        }
+    }
+    Ok(Value::Bool(true))
+}
@@ -0,0 +1,21 @@
+use crate::error::RuntimeError;
+use crate::functions::callback::{callable_arg, list_arg};
+use crate::functions::registry::CallCtx;
+use crate::node::Value;
+
+/// `any(list)` or `any(list, predicate)`; stops at the first truthy result.
+pub fn any_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let items = list_arg("any", &args[0])?;
+    let predicate = args.get(1).map(|p| callable_arg("any", p)).transpose()?;
+
+    for item in items {
+        let value = match &predicate {
+            Some(predicate) => call.call_function(predicate, vec![item])?,
+            None => item,
+        };
+        if value.is_truthy() {
+            return Ok(Value::Bool(true));
+        }
+    }
+    Ok(Value::Bool(false))
+}
@@ -1,15 +1,11 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
 
-pub fn append_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 2 {
-        return Err(RuntimeError {
-            message: format!("append expects 2 arguments, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
+pub fn append_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match &args[0] {
         Value::List(list) => {
+            call.allocate(1)?;
             list.borrow_mut().push(args[1].clone());
             Ok(Value::Void)
         }
@@ -17,18 +13,19 @@ pub fn append_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
             let mut string = s.borrow_mut();
             match &args[1] {
                 Value::String(other) => {
+                    call.allocate(other.borrow().len())?;
                     string.push_str(&other.borrow());
                     Ok(Value::Void)
                 }
-                _ => Err(RuntimeError {
-                    message: "Second argument to append for String must be a string".to_string(),
-                    stack_trace: vec![],
-                }),
+                _ => Err(RuntimeError::new(
+                    ErrorKind::TypeError,
+                    "Second argument to append for String must be a string",
+                )),
             }
         }
-        _ => Err(RuntimeError {
-            message: "First argument to append must be a list or string".to_string(),
-            stack_trace: vec![],
-        }),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "First argument to append must be a list or string",
+        )),
     }
 }
@@ -0,0 +1,27 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::node::{Callable, Value};
+use std::rc::Rc;
+
+// Argument helpers shared by the built-ins that call back into user code.
+
+/// Copies the elements out of a list argument, so callbacks are free to
+/// modify the list while it is being processed.
+pub(crate) fn list_arg(name: &str, value: &Value) -> Result<Vec<Value>, RuntimeError> {
+    match value {
+        Value::List(l) => Ok(l.borrow().clone()),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            format!("{} expects a list", name),
+        )),
+    }
+}
+
+pub(crate) fn callable_arg(name: &str, value: &Value) -> Result<Rc<Callable>, RuntimeError> {
+    match value {
+        Value::Function(f) => Ok(f.clone()),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            format!("{} expects a function", name),
+        )),
+    }
+}
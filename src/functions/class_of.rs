@@ -0,0 +1,43 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
+use crate::node::{Context, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub fn class_of_fn(_call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    match &args[0] {
+        Value::Object(obj) => Ok(Value::String(Rc::new(RefCell::new(
+            obj.borrow().class_name.clone(),
+        )))),
+        other => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            format!("class_of expects an object, got {}", other.type_name()),
+        )),
+    }
+}
+
+/// The class a reflection built-in inspects: the class of an object, or a
+/// class given by name.
+pub(crate) fn class_arg(ctx: &Context, name: &str, value: &Value) -> Result<String, RuntimeError> {
+    let class_name = match value {
+        Value::Object(obj) => obj.borrow().class_name.clone(),
+        Value::String(s) => s.borrow().clone(),
+        other => {
+            return Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                format!(
+                    "{} expects an object or a class name, got {}",
+                    name,
+                    other.type_name()
+                ),
+            ));
+        }
+    };
+    if ctx.class(&class_name).is_none() {
+        return Err(RuntimeError::new(
+            ErrorKind::NameError,
+            format!("Undefined class '{}'", class_name),
+        ));
+    }
+    Ok(class_name)
+}
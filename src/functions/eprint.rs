@@ -0,0 +1,11 @@
+use crate::error::RuntimeError;
+use crate::functions::print::format_line;
+use crate::functions::registry::CallCtx;
+use crate::node::Value;
+use crate::output::Stream;
+
+pub fn eprint_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let line = format_line(call.context(), args)?;
+    call.context().write_output(Stream::Stderr, &line)?;
+    Ok(Value::Void)
+}
@@ -0,0 +1,26 @@
+use crate::error::RuntimeError;
+use crate::functions::class_of::class_arg;
+use crate::functions::registry::CallCtx;
+use crate::node::{Context, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub fn fields_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let class_name = class_arg(call.context(), "fields", &args[0])?;
+    let fields: Vec<Value> = field_names(call.context(), &class_name)
+        .into_iter()
+        .map(|name| Value::String(Rc::new(RefCell::new(name))))
+        .collect();
+    call.allocate(fields.len())?;
+    Ok(Value::List(Rc::new(RefCell::new(fields))))
+}
+
+/// The fields objects of the class have, inherited ones first, in
+/// declaration order.
+pub(crate) fn field_names(ctx: &Context, class_name: &str) -> Vec<String> {
+    ctx.class_chain(class_name)
+        .into_iter()
+        .rev()
+        .flat_map(|class| class.fields.clone())
+        .collect()
+}
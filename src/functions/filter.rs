@@ -0,0 +1,32 @@
+use crate::error::RuntimeError;
+use crate::functions::callback::{callable_arg, list_arg};
+use crate::functions::registry::CallCtx;
+use crate::iteration::Iter;
+use crate::node::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub fn filter_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    // Iterators stay lazy: the function runs as items are taken.
+    if let Value::Iterator(_) = &args[0] {
+        let predicate = callable_arg("filter", &args[1])?;
+        let items = Iter::new(call.context(), args[0].clone())?;
+        return Ok(Value::Iterator(Rc::new(RefCell::new(Iter::filter(
+            items, predicate,
+        )))));
+    }
+    let items = list_arg("filter", &args[0])?;
+    let predicate = callable_arg("filter", &args[1])?;
+
+    let mut result = Vec::new();
+    for item in items {
+        if call
+            .call_function(&predicate, vec![item.clone()])?
+            .is_truthy()
+        {
+            result.push(item);
+        }
+    }
+    call.allocate(result.len())?;
+    Ok(Value::List(Rc::new(RefCell::new(result))))
+}
@@ -0,0 +1,20 @@
+use crate::error::RuntimeError;
+use crate::functions::callback::{callable_arg, list_arg};
+use crate::functions::registry::CallCtx;
+use crate::node::Value;
+
+/// Returns the first element matching the predicate, or void if none does.
+pub fn find_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let items = list_arg("find", &args[0])?;
+    let predicate = callable_arg("find", &args[1])?;
+
+    for item in items {
+        if call
+            .call_function(&predicate, vec![item.clone()])?
+            .is_truthy()
+        {
+            return Ok(item);
+        }
+    }
+    Ok(Value::Void)
+}
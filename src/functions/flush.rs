@@ -0,0 +1,8 @@
+use crate::error::RuntimeError;
+use crate::functions::registry::CallCtx;
+use crate::node::Value;
+
+pub fn flush_fn(call: &mut CallCtx, _args: &[Value]) -> Result<Value, RuntimeError> {
+    call.context().flush_output()?;
+    Ok(Value::Void)
+}
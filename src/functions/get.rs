@@ -1,32 +1,27 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
 use std::cell::RefCell;
 use std::rc::Rc;
 
-pub fn get_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 2 {
-        return Err(RuntimeError {
-            message: format!("get expects 2 arguments, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
+pub fn get_fn(_call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match &args[0] {
         Value::List(l) => {
             let list = l.borrow();
             let index = match &args[1] {
                 Value::Int(i) => *i as usize,
                 _ => {
-                    return Err(RuntimeError {
-                        message: "Second argument to get for List must be an integer".to_string(),
-                        stack_trace: vec![],
-                    });
+                    return Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        "Second argument to get for List must be an integer",
+                    ));
                 }
             };
             if index >= list.len() {
-                return Err(RuntimeError {
-                    message: format!("Index {} out of bounds (len {})", index, list.len()),
-                    stack_trace: vec![],
-                });
+                return Err(RuntimeError::new(
+                    ErrorKind::IndexError,
+                    format!("Index {} out of bounds (len {})", index, list.len()),
+                ));
             }
             Ok(list[index].clone())
         }
@@ -35,15 +30,15 @@ pub fn get_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
             let key = match &args[1] {
                 Value::String(s) => s,
                 _ => {
-                    return Err(RuntimeError {
-                        message: "Second argument to get for Map must be a string".to_string(),
-                        stack_trace: vec![],
-                    });
+                    return Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        "Second argument to get for Map must be a string",
+                    ));
                 }
             };
             match map.get(key.borrow().as_str()) {
                 Some(val) => Ok(val.clone()),
-                None => Ok(Value::Void), // Or error? Void seems safer for now
+                None => Ok(Value::Void),
             }
         }
         Value::String(s) => {
@@ -51,24 +46,24 @@ pub fn get_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
             let index = match &args[1] {
                 Value::Int(i) => *i as usize,
                 _ => {
-                    return Err(RuntimeError {
-                        message: "Second argument to get for String must be an integer".to_string(),
-                        stack_trace: vec![],
-                    });
+                    return Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        "Second argument to get for String must be an integer",
+                    ));
                 }
             };
             if let Some(ch) = string.chars().nth(index) {
                 Ok(Value::String(Rc::new(RefCell::new(ch.to_string()))))
             } else {
-                Err(RuntimeError {
-                    message: format!("Index {} out of bounds (len {})", index, string.len()),
-                    stack_trace: vec![],
-                })
+                Err(RuntimeError::new(
+                    ErrorKind::IndexError,
+                    format!("Index {} out of bounds (len {})", index, string.len()),
+                ))
             }
         }
-        _ => Err(RuntimeError {
-            message: "First argument to get must be a list, map, or string".to_string(),
-            stack_trace: vec![],
-        }),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "First argument to get must be a list, map, or string",
+        )),
     }
 }
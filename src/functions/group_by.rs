@@ -0,0 +1,28 @@
+use crate::error::RuntimeError;
+use crate::functions::callback::{callable_arg, list_arg};
+use crate::functions::print::format_value;
+use crate::functions::registry::CallCtx;
+use crate::node::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Groups the elements into a map from the printed key to the list of
+/// elements with that key, keeping their original order.
+pub fn group_by_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let items = list_arg("group_by", &args[0])?;
+    let key_fn = callable_arg("group_by", &args[1])?;
+    call.allocate(items.len())?;
+
+    let mut groups: HashMap<String, Vec<Value>> = HashMap::new();
+    for item in items {
+        let key = format_value(&call.call_function(&key_fn, vec![item.clone()])?);
+        groups.entry(key).or_default().push(item);
+    }
+
+    let groups = groups
+        .into_iter()
+        .map(|(key, items)| (key, Value::List(Rc::new(RefCell::new(items)))))
+        .collect();
+    Ok(Value::Map(Rc::new(RefCell::new(groups))))
+}
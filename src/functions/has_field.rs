@@ -0,0 +1,21 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::class_of::class_arg;
+use crate::functions::fields::field_names;
+use crate::functions::registry::CallCtx;
+use crate::node::Value;
+
+pub fn has_field_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let class_name = class_arg(call.context(), "has_field", &args[0])?;
+    match &args[1] {
+        Value::String(field) => {
+            let field = field.borrow();
+            Ok(Value::Bool(
+                field_names(call.context(), &class_name).contains(&field),
+            ))
+        }
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "Second argument to has_field must be a string",
+        )),
+    }
+}
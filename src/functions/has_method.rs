@@ -0,0 +1,21 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::class_of::class_arg;
+use crate::functions::methods::method_names;
+use crate::functions::registry::CallCtx;
+use crate::node::Value;
+
+pub fn has_method_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let class_name = class_arg(call.context(), "has_method", &args[0])?;
+    match &args[1] {
+        Value::String(method) => {
+            let method = method.borrow();
+            Ok(Value::Bool(
+                method_names(call.context(), &class_name).contains(&method),
+            ))
+        }
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "Second argument to has_method must be a string",
+        )),
+    }
+}
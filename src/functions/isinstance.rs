@@ -0,0 +1,19 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
+use crate::node::Value;
+
+pub fn isinstance_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let Value::String(class_name) = &args[1] else {
+        return Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "Second argument to isinstance must be a class name",
+        ));
+    };
+    match &args[0] {
+        Value::Object(obj) => Ok(Value::Bool(
+            call.context()
+                .is_instance(&obj.borrow().class_name, &class_name.borrow()),
+        )),
+        _ => Ok(Value::Bool(false)),
+    }
+}
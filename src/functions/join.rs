@@ -1,16 +1,10 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
 use std::cell::RefCell;
 use std::rc::Rc;
 
-pub fn join_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 2 {
-        return Err(RuntimeError {
-            message: format!("join expects 2 arguments, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
-
+pub fn join_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match (&args[0], &args[1]) {
         (Value::List(l), Value::String(delim)) => {
             let list = l.borrow();
@@ -23,22 +17,22 @@ pub fn join_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
                     Value::Int(n) => Ok(n.to_string()),
                     Value::Float(n) => Ok(n.to_string()),
                     Value::Bool(b) => Ok(b.to_string()),
-                    _ => Err(RuntimeError {
-                        message: "join: list elements must be strings or convertible to strings"
-                            .to_string(),
-                        stack_trace: vec![],
-                    }),
+                    _ => Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        "join: list elements must be strings or convertible to strings",
+                    )),
                 })
                 .collect();
 
             let strings = strings?;
             let result = strings.join(&delimiter);
 
+            call.allocate(result.len())?;
             Ok(Value::String(Rc::new(RefCell::new(result))))
         }
-        _ => Err(RuntimeError {
-            message: "join expects a list and a string delimiter".to_string(),
-            stack_trace: vec![],
-        }),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "join expects a list and a string delimiter",
+        )),
     }
 }
@@ -1,15 +1,10 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
 use std::cell::RefCell;
 use std::rc::Rc;
 
-pub fn keys_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(RuntimeError {
-            message: format!("keys expects 1 argument, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
+pub fn keys_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match &args[0] {
         Value::Map(m) => {
             let map = m.borrow();
@@ -17,11 +12,12 @@ pub fn keys_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
                 .keys()
                 .map(|k| Value::String(Rc::new(RefCell::new(k.clone()))))
                 .collect();
+            call.allocate(keys.len())?;
             Ok(Value::List(Rc::new(RefCell::new(keys))))
         }
-        _ => Err(RuntimeError {
-            message: "First argument to keys must be a map".to_string(),
-            stack_trace: vec![],
-        }),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "First argument to keys must be a map",
+        )),
     }
 }
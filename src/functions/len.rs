@@ -1,21 +1,25 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
+use crate::special;
 
-pub fn len_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(RuntimeError {
-            message: format!("len expects 1 argument, got {}", args.len()),
-            stack_trace: vec![],
-        });
+pub fn len_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    if let Some(result) = special::call(call.context(), &args[0], special::LEN, &[]) {
+        return match result? {
+            Value::Int(n) => Ok(Value::Int(n)),
+            other => Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                format!("__len__ must return an Int, got {}", other.type_name()),
+            )),
+        };
     }
-
     match &args[0] {
-        Value::String(s) => Ok(Value::Int(s.borrow().len() as i32)),
-        Value::List(l) => Ok(Value::Int(l.borrow().len() as i32)),
-        Value::Map(m) => Ok(Value::Int(m.borrow().len() as i32)),
-        _ => Err(RuntimeError {
-            message: "len expects a string, list, or map".to_string(),
-            stack_trace: vec![],
-        }),
+        Value::String(s) => Ok(Value::Int(s.borrow().len() as i64)),
+        Value::List(l) => Ok(Value::Int(l.borrow().len() as i64)),
+        Value::Map(m) => Ok(Value::Int(m.borrow().len() as i64)),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "len expects a string, list, map, or object with __len__",
+        )),
     }
 }
@@ -0,0 +1,27 @@
+use crate::error::RuntimeError;
+use crate::functions::callback::{callable_arg, list_arg};
+use crate::functions::registry::CallCtx;
+use crate::iteration::Iter;
+use crate::node::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub fn map_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    // Iterators stay lazy: the function runs as items are taken.
+    if let Value::Iterator(_) = &args[0] {
+        let func = callable_arg("map", &args[1])?;
+        let items = Iter::new(call.context(), args[0].clone())?;
+        return Ok(Value::Iterator(Rc::new(RefCell::new(Iter::map(
+            items, func,
+        )))));
+    }
+    let items = list_arg("map", &args[0])?;
+    let func = callable_arg("map", &args[1])?;
+
+    let mut result = Vec::with_capacity(items.len());
+    for item in items {
+        result.push(call.call_function(&func, vec![item])?);
+    }
+    call.allocate(result.len())?;
+    Ok(Value::List(Rc::new(RefCell::new(result))))
+}
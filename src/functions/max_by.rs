@@ -0,0 +1,11 @@
+use crate::error::RuntimeError;
+use crate::functions::registry::CallCtx;
+use crate::functions::sort::extreme_by;
+use crate::node::Value;
+use std::cmp::Ordering;
+
+/// Returns the element with the largest key (the first one on ties), or void
+/// for an empty list.
+pub fn max_by_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    extreme_by(call, "max_by", &args[0], &args[1], Ordering::Greater)
+}
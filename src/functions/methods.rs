@@ -0,0 +1,36 @@
+use crate::error::RuntimeError;
+use crate::functions::class_of::class_arg;
+use crate::functions::registry::CallCtx;
+use crate::node::{Context, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub fn methods_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let class_name = class_arg(call.context(), "methods", &args[0])?;
+    let methods: Vec<Value> = method_names(call.context(), &class_name)
+        .into_iter()
+        .map(|name| Value::String(Rc::new(RefCell::new(name))))
+        .collect();
+    call.allocate(methods.len())?;
+    Ok(Value::List(Rc::new(RefCell::new(methods))))
+}
+
+/// The instance and static methods of the class, including inherited ones,
+/// sorted.
+pub(crate) fn method_names(ctx: &Context, class_name: &str) -> Vec<String> {
+    let mut names: Vec<String> = ctx
+        .class_chain(class_name)
+        .into_iter()
+        .flat_map(|class| {
+            class
+                .methods
+                .keys()
+                .chain(class.static_methods.keys())
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
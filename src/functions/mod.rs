@@ -1,19 +1,40 @@
 pub mod abs;
+pub mod all;
+pub mod any;
 pub mod append;
+pub mod callback;
 pub mod chr;
+pub mod class_of;
+pub mod eprint;
+pub mod fields;
+pub mod filter;
+pub mod find;
+pub mod flush;
 pub mod get;
+pub mod group_by;
+pub mod has_field;
+pub mod has_method;
+pub mod isinstance;
 pub mod join;
 pub mod keys;
 pub mod len;
+pub mod map;
+pub mod max_by;
+pub mod methods;
+pub mod min_by;
 pub mod ord;
 pub mod print;
 pub mod range;
 pub mod read_file;
+pub mod reduce;
+pub mod registry;
 pub mod reverse;
 pub mod set;
 pub mod slice;
 pub mod sort;
+pub mod sort_by;
 pub mod split;
 pub mod sum;
+pub mod take;
 pub mod to_float;
 pub mod to_int;
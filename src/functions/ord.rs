@@ -1,34 +1,28 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
 
-pub fn ord_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(RuntimeError {
-            message: format!("ord expects 1 argument, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
-
+pub fn ord_fn(_call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match &args[0] {
         Value::String(s) => {
             let str_val = s.borrow();
             let chars: Vec<char> = str_val.chars().collect();
 
             if chars.len() != 1 {
-                return Err(RuntimeError {
-                    message: format!(
+                return Err(RuntimeError::new(
+                    ErrorKind::ValueError,
+                    format!(
                         "ord expects a single character, got string of length {}",
                         chars.len()
                     ),
-                    stack_trace: vec![],
-                });
+                ));
             }
 
-            Ok(Value::Int(chars[0] as i32))
+            Ok(Value::Int(chars[0] as i64))
         }
-        _ => Err(RuntimeError {
-            message: "ord expects a string".to_string(),
-            stack_trace: vec![],
-        }),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "ord expects a string",
+        )),
     }
 }
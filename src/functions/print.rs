@@ -1,60 +1,131 @@
-use crate::error::RuntimeError;
-use crate::node::Value;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
+use crate::node::{Context, Object, Value};
+use crate::output::Stream;
+use crate::special;
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::Infallible;
 use std::rc::Rc;
 
-thread_local! {
-    pub static TEST_LOGS: RefCell<Option<Rc<RefCell<Vec<String>>>>> = RefCell::new(None);
-}
-
-fn write_output(s: &str) {
-    TEST_LOGS.with(|logs| {
-        if let Some(logs) = &*logs.borrow() {
-            logs.borrow_mut().push(s.to_string());
-        } else {
-            print!("{}", s);
-        }
-    });
+pub fn print_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let line = format_line(call.context(), args)?;
+    call.context().write_output(Stream::Stdout, &line)?;
+    Ok(Value::Void)
 }
 
-pub fn print_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
+/// The arguments separated by spaces, followed by a newline.
+pub(crate) fn format_line(ctx: &mut Context, args: &[Value]) -> Result<String, RuntimeError> {
     let mut output = String::new();
     for (i, arg) in args.iter().enumerate() {
         if i > 0 {
             output.push(' ');
         }
-        output.push_str(&format_value(arg));
+        output.push_str(&display_value(ctx, arg)?);
     }
     output.push('\n');
-    write_output(&output);
-    Ok(Value::Void)
+    Ok(output)
+}
+
+pub fn format_value(val: &Value) -> String {
+    let Ok(text) = format_with(val, &mut |_| Ok::<_, Infallible>(None), &mut Vec::new());
+    text
 }
 
-fn format_value(val: &Value) -> String {
-    match val {
+/// Like `format_value`, but objects whose class defines `__str__` show its
+/// result, also inside lists and maps.
+pub fn display_value(ctx: &mut Context, val: &Value) -> Result<String, RuntimeError> {
+    let mut custom = |obj: &Rc<RefCell<Object>>| {
+        let object = Value::Object(obj.clone());
+        match special::call(ctx, &object, special::STR, &[]) {
+            None => Ok(None),
+            Some(result) => match result? {
+                Value::String(s) => Ok(Some(s.borrow().clone())),
+                other => Err(RuntimeError::new(
+                    ErrorKind::TypeError,
+                    format!("__str__ must return a String, got {}", other.type_name()),
+                )),
+            },
+        }
+    };
+    format_with(val, &mut custom, &mut Vec::new())
+}
+
+/// Formats `val`. Objects show the text `custom` returns for them, or else
+/// their class and fields, e.g. `Point(x: 1, y: 2)`. `open` holds the
+/// objects being formatted, so an object containing itself shows as
+/// `Point(...)`.
+fn format_with<E, F>(
+    val: &Value,
+    custom: &mut F,
+    open: &mut Vec<*const RefCell<Object>>,
+) -> Result<String, E>
+where
+    F: FnMut(&Rc<RefCell<Object>>) -> Result<Option<String>, E>,
+{
+    Ok(match val {
         Value::Int(v) => format!("{}", v),
+        Value::BigInt(v) => format!("{}", v),
         Value::Float(v) => format!("{}", v),
+        Value::Decimal(v) => format!("{}", v),
         Value::String(v) => v.borrow().clone(),
         Value::Bool(v) => format!("{}", v),
         Value::List(l) => {
-            let list = l.borrow();
-            let elements: Vec<String> = list.iter().map(|v| format_value(v)).collect();
+            let list = l.borrow().clone();
+            let elements = list
+                .iter()
+                .map(|element| format_with(element, custom, open))
+                .collect::<Result<Vec<_>, _>>()?;
             format!("[{}]", elements.join(", "))
         }
         Value::Map(m) => {
-            let map = m.borrow();
-            let mut keys: Vec<&String> = map.keys().collect();
-            keys.sort();
-            let elements: Vec<String> = keys
-                .iter()
-                .map(|k| format!("{}: {}", k, format_value(&map[*k])))
-                .collect();
-            format!("{{{}}}", elements.join(", "))
+            let map = m.borrow().clone();
+            format!("{{{}}}", format_entries(&map, custom, open)?)
         }
+        Value::Function(func) => func.to_string(),
+        Value::Iterator(iter) => match iter.try_borrow() {
+            Ok(iter) => iter.to_string(),
+            // Printed by the generator that is running.
+            Err(_) => "<iterator>".to_string(),
+        },
         Value::Void => "(void)".to_string(),
         Value::Object(obj) => {
-            let obj = obj.borrow();
-            format!("<Object {}>", obj.class_name)
+            if let Some(text) = custom(obj)? {
+                return Ok(text);
+            }
+            let class_name = obj.borrow().class_name.clone();
+            if open.contains(&Rc::as_ptr(obj)) {
+                return Ok(format!("{}(...)", class_name));
+            }
+            open.push(Rc::as_ptr(obj));
+            let fields = obj.borrow().fields.clone();
+            let entries = format_entries(&fields, custom, open);
+            open.pop();
+            format!("{}({})", class_name, entries?)
         }
-    }
+    })
+}
+
+/// `key: value` pairs sorted by key, separated by commas.
+fn format_entries<E, F>(
+    entries: &HashMap<String, Value>,
+    custom: &mut F,
+    open: &mut Vec<*const RefCell<Object>>,
+) -> Result<String, E>
+where
+    F: FnMut(&Rc<RefCell<Object>>) -> Result<Option<String>, E>,
+{
+    let mut keys: Vec<&String> = entries.keys().collect();
+    keys.sort();
+    let elements = keys
+        .iter()
+        .map(|k| {
+            Ok(format!(
+                "{}: {}",
+                k,
+                format_with(&entries[*k], custom, open)?
+            ))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(elements.join(", "))
 }
@@ -1,42 +1,105 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
 use std::cell::RefCell;
 use std::rc::Rc;
 
-pub fn range_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() || args.len() > 2 {
-        return Err(RuntimeError {
-            message: format!("range expects 1 or 2 arguments, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
+/// `range(end)`, `range(start, end)` or `range(start, end, step)`. Yields
+/// integers when every argument is an integer, floats otherwise.
+pub fn range_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let bounds = RangeBounds::from_args(args)?;
+    // The result is a list, charged one unit per element.
+    call.allocate(bounds.len())?;
+    let result = (0..bounds.len()).map(|i| bounds.get(i)).collect();
+    Ok(Value::List(Rc::new(RefCell::new(result))))
+}
+
+/// The numbers a call to `range` stands for, computed one at a time so a
+/// `for` loop over `range(...)` does not build the list.
+#[derive(Debug, Clone, Copy)]
+pub enum RangeBounds {
+    Int { start: i64, step: i64, len: usize },
+    Float { start: f64, step: f64, len: usize },
+}
 
-    let (start, end) = if args.len() == 1 {
-        match &args[0] {
-            Value::Int(n) => (0, *n),
+impl RangeBounds {
+    pub fn from_args(args: &[Value]) -> Result<Self, RuntimeError> {
+        let (start, end, step) = match args {
+            [end] => (&Value::Int(0), end, &Value::Int(1)),
+            [start, end] => (start, end, &Value::Int(1)),
+            [start, end, step] => (start, end, step),
+            _ => unreachable!("arity is checked by the registry"),
+        };
+
+        match (start, end, step) {
+            (Value::Int(start), Value::Int(end), Value::Int(step)) => {
+                if *step == 0 {
+                    return Err(zero_step());
+                }
+                let (diff, step_size) = (*end as i128 - *start as i128, (*step as i128).abs());
+                let count = match diff.signum() == step.signum() as i128 {
+                    true => (diff.abs() + step_size - 1) / step_size,
+                    false => 0,
+                };
+                Ok(RangeBounds::Int {
+                    start: *start,
+                    step: *step,
+                    len: count.try_into().unwrap_or(usize::MAX),
+                })
+            }
             _ => {
-                return Err(RuntimeError {
-                    message: "range expects integer arguments".to_string(),
-                    stack_trace: vec![],
-                });
+                let (start, end, step) = (to_f64(start)?, to_f64(end)?, to_f64(step)?);
+                if step == 0.0 {
+                    return Err(zero_step());
+                }
+                let count = ((end - start) / step).ceil().max(0.0);
+                Ok(RangeBounds::Float {
+                    start,
+                    step,
+                    len: if count.is_finite() {
+                        count as usize
+                    } else {
+                        usize::MAX
+                    },
+                })
             }
         }
-    } else {
-        match (&args[0], &args[1]) {
-            (Value::Int(s), Value::Int(e)) => (*s, *e),
-            _ => {
-                return Err(RuntimeError {
-                    message: "range expects integer arguments".to_string(),
-                    stack_trace: vec![],
-                });
+    }
+
+    pub fn len(&self) -> usize {
+        match *self {
+            RangeBounds::Int { len, .. } | RangeBounds::Float { len, .. } => len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The `i`th number, for `i < len()`.
+    pub fn get(&self, i: usize) -> Value {
+        match *self {
+            RangeBounds::Int { start, step, .. } => {
+                Value::Int((start as i128 + i as i128 * step as i128) as i64)
             }
+            // Computing each element from the start avoids accumulating
+            // rounding errors.
+            RangeBounds::Float { start, step, .. } => Value::Float(start + i as f64 * step),
         }
-    };
+    }
+}
 
-    let mut result = Vec::new();
-    for i in start..end {
-        result.push(Value::Int(i));
+fn to_f64(value: &Value) -> Result<f64, RuntimeError> {
+    match value {
+        Value::Int(n) => Ok(*n as f64),
+        Value::Float(f) => Ok(*f),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "range expects integer or float arguments",
+        )),
     }
+}
 
-    Ok(Value::List(Rc::new(RefCell::new(result))))
+fn zero_step() -> RuntimeError {
+    RuntimeError::new(ErrorKind::ValueError, "range step must not be zero")
 }
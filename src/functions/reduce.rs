@@ -0,0 +1,25 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::callback::{callable_arg, list_arg};
+use crate::functions::registry::CallCtx;
+use crate::node::Value;
+
+/// `reduce(list, f, initial)` folds the list with `f(acc, item)`. Without an
+/// initial value the first element is used.
+pub fn reduce_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let mut items = list_arg("reduce", &args[0])?.into_iter();
+    let func = callable_arg("reduce", &args[1])?;
+
+    let mut acc = match args.get(2) {
+        Some(initial) => initial.clone(),
+        None => items.next().ok_or_else(|| {
+            RuntimeError::new(
+                ErrorKind::ValueError,
+                "reduce of an empty list with no initial value",
+            )
+        })?,
+    };
+    for item in items {
+        acc = call.call_function(&func, vec![acc, item])?;
+    }
+    Ok(acc)
+}
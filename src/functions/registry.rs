@@ -0,0 +1,463 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions;
+use crate::node::{Callable, Context, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::rc::Rc;
+
+/// Signature of a built-in. Closures may capture host state such as a
+/// database handle or a counter.
+pub type NativeFn = Rc<dyn Fn(&mut CallCtx, &[Value]) -> Result<Value, RuntimeError>>;
+
+/// Number of arguments a built-in accepts. Checked before the built-in runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exact(usize),
+    /// Inclusive range.
+    Range(usize, usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    pub fn accepts(&self, count: usize) -> bool {
+        match *self {
+            Arity::Exact(n) => count == n,
+            Arity::Range(min, max) => (min..=max).contains(&count),
+            Arity::AtLeast(min) => count >= min,
+        }
+    }
+}
+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Arity::Exact(1) => write!(f, "1 argument"),
+            Arity::Exact(n) => write!(f, "{} arguments", n),
+            Arity::Range(min, max) if max == min + 1 => write!(f, "{} or {} arguments", min, max),
+            Arity::Range(min, max) => write!(f, "{} to {} arguments", min, max),
+            Arity::AtLeast(1) => write!(f, "at least 1 argument"),
+            Arity::AtLeast(n) => write!(f, "at least {} arguments", n),
+        }
+    }
+}
+
+/// Handle passed to built-ins while they run, giving access to the
+/// interpreter that called them.
+pub struct CallCtx<'a> {
+    ctx: &'a mut Context,
+}
+
+impl CallCtx<'_> {
+    /// Calls a function value, e.g. a callback passed in by the script.
+    pub fn call(&mut self, callee: &Value, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        match callee {
+            Value::Function(callee) => self.ctx.call_function(callee, args),
+            _ => Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                "Cannot call a value that is not a function",
+            )),
+        }
+    }
+
+    pub fn call_function(
+        &mut self,
+        callee: &Callable,
+        args: Vec<Value>,
+    ) -> Result<Value, RuntimeError> {
+        self.ctx.call_function(callee, args)
+    }
+
+    /// Counts `size` elements or bytes of new collection data against the
+    /// script's budget.
+    pub fn allocate(&mut self, size: usize) -> Result<(), RuntimeError> {
+        self.ctx.allocate(size)
+    }
+
+    /// The calling context, for built-ins that need more than calls.
+    pub fn context(&mut self) -> &mut Context {
+        self.ctx
+    }
+}
+
+/// A registered built-in function.
+#[derive(Clone)]
+pub struct BuiltIn {
+    pub name: String,
+    pub module: String,
+    pub arity: Arity,
+    pub doc: String,
+    pub func: NativeFn,
+}
+
+impl BuiltIn {
+    /// Checks the argument count and runs the built-in.
+    pub fn call(&self, ctx: &mut Context, args: &[Value]) -> Result<Value, RuntimeError> {
+        if !self.arity.accepts(args.len()) {
+            return Err(RuntimeError::new(
+                ErrorKind::ArityError,
+                format!("{} expects {}, got {}", self.name, self.arity, args.len()),
+            ));
+        }
+        (self.func)(&mut CallCtx { ctx }, args)
+    }
+}
+
+/// The built-in functions visible to scripts, grouped into named modules
+/// that can be switched off as a whole.
+#[derive(Clone, Default)]
+pub struct BuiltInRegistry {
+    builtins: HashMap<String, BuiltIn>,
+    disabled_modules: HashSet<String>,
+}
+
+impl BuiltInRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a built-in, replacing any existing one with the same name.
+    pub fn register<F>(
+        &mut self,
+        module: &str,
+        name: &str,
+        arity: Arity,
+        doc: &str,
+        func: F,
+    ) -> &mut Self
+    where
+        F: Fn(&mut CallCtx, &[Value]) -> Result<Value, RuntimeError> + 'static,
+    {
+        self.insert(BuiltIn {
+            name: name.to_string(),
+            module: module.to_string(),
+            arity,
+            doc: doc.to_string(),
+            func: Rc::new(func),
+        })
+    }
+
+    pub fn insert(&mut self, builtin: BuiltIn) -> &mut Self {
+        self.builtins.insert(builtin.name.clone(), builtin);
+        self
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<BuiltIn> {
+        self.builtins.remove(name)
+    }
+
+    /// Looks up a built-in, hiding those in disabled modules.
+    pub fn get(&self, name: &str) -> Option<&BuiltIn> {
+        self.builtins
+            .get(name)
+            .filter(|b| !self.disabled_modules.contains(&b.module))
+    }
+
+    pub fn disable_module(&mut self, module: &str) -> &mut Self {
+        self.disabled_modules.insert(module.to_string());
+        self
+    }
+
+    pub fn enable_module(&mut self, module: &str) -> &mut Self {
+        self.disabled_modules.remove(module);
+        self
+    }
+
+    pub fn is_module_enabled(&self, module: &str) -> bool {
+        !self.disabled_modules.contains(module)
+    }
+
+    /// Names of all modules with at least one built-in, sorted.
+    pub fn modules(&self) -> Vec<&str> {
+        let mut modules: Vec<&str> = self.builtins.values().map(|b| b.module.as_str()).collect();
+        modules.sort();
+        modules.dedup();
+        modules
+    }
+
+    /// The enabled built-ins, sorted by name.
+    pub fn list(&self) -> Vec<&BuiltIn> {
+        let mut builtins: Vec<&BuiltIn> = self
+            .builtins
+            .values()
+            .filter(|b| self.is_module_enabled(&b.module))
+            .collect();
+        builtins.sort_by(|a, b| a.name.cmp(&b.name));
+        builtins
+    }
+
+    /// The standard library every `Context` starts with.
+    pub fn standard() -> Self {
+        use Arity::*;
+
+        let mut registry = Self::new();
+        registry
+            .register(
+                "io",
+                "print",
+                AtLeast(0),
+                "Prints the arguments separated by spaces.",
+                functions::print::print_fn,
+            )
+            .register(
+                "io",
+                "eprint",
+                AtLeast(0),
+                "Prints the arguments separated by spaces to stderr.",
+                functions::eprint::eprint_fn,
+            )
+            .register(
+                "io",
+                "flush",
+                Exact(0),
+                "Writes out any buffered output.",
+                functions::flush::flush_fn,
+            )
+            .register(
+                "io",
+                "read_file",
+                Exact(1),
+                "Reads a whole file into a string.",
+                functions::read_file::read_file_fn,
+            )
+            .register(
+                "collections",
+                "get",
+                Exact(2),
+                "Element of a list or string at an index, or map value for a key (`Void` if missing).",
+                functions::get::get_fn,
+            )
+            .register(
+                "collections",
+                "set",
+                Exact(3),
+                "Sets a list element or map entry in place.",
+                functions::set::set_fn,
+            )
+            .register(
+                "collections",
+                "keys",
+                Exact(1),
+                "List of the keys of a map.",
+                functions::keys::keys_fn,
+            )
+            .register(
+                "collections",
+                "append",
+                Exact(2),
+                "Appends an element to a list, or a string to a string, in place.",
+                functions::append::append_fn,
+            )
+            .register(
+                "collections",
+                "len",
+                Exact(1),
+                "Length of a list, map or string.",
+                functions::len::len_fn,
+            )
+            .register(
+                "collections",
+                "slice",
+                Exact(3),
+                "Part of a list or string between two indices.",
+                functions::slice::slice_fn,
+            )
+            .register(
+                "collections",
+                "sort",
+                Range(1, 2),
+                "Sorts a list in place, optionally by a key function.",
+                functions::sort::sort_fn,
+            )
+            .register(
+                "collections",
+                "reverse",
+                Exact(1),
+                "Reverses a list in place.",
+                functions::reverse::reverse_fn,
+            )
+            .register(
+                "collections",
+                "range",
+                Range(1, 3),
+                "List of numbers from start (default 0) up to end, by step (default 1).",
+                functions::range::range_fn,
+            )
+            .register(
+                "math",
+                "abs",
+                Exact(1),
+                "Absolute value of a number.",
+                functions::abs::abs_fn,
+            )
+            .register(
+                "math",
+                "sum",
+                Exact(1),
+                "Sum of a list of numbers.",
+                functions::sum::sum_fn,
+            )
+            .register(
+                "strings",
+                "split",
+                Exact(2),
+                "Splits a string on a delimiter.",
+                functions::split::split_fn,
+            )
+            .register(
+                "strings",
+                "join",
+                Exact(2),
+                "Joins a list into a string with a delimiter.",
+                functions::join::join_fn,
+            )
+            .register(
+                "strings",
+                "ord",
+                Exact(1),
+                "Code point of a one-character string.",
+                functions::ord::ord_fn,
+            )
+            .register(
+                "strings",
+                "chr",
+                Exact(1),
+                "One-character string for a code point.",
+                functions::chr::chr_fn,
+            )
+            .register(
+                "conversions",
+                "to_int",
+                Exact(1),
+                "Converts a value to an integer.",
+                functions::to_int::to_int_fn,
+            )
+            .register(
+                "conversions",
+                "to_float",
+                Exact(1),
+                "Converts a value to a float.",
+                functions::to_float::to_float_fn,
+            )
+            .register(
+                "functional",
+                "map",
+                Exact(2),
+                "New list with the function applied to each element, or a lazy iterator for an iterator.",
+                functions::map::map_fn,
+            )
+            .register(
+                "functional",
+                "filter",
+                Exact(2),
+                "New list of the elements the predicate accepts, or a lazy iterator for an iterator.",
+                functions::filter::filter_fn,
+            )
+            .register(
+                "functional",
+                "take",
+                Exact(2),
+                "List of the first n items of a list, string, map or iterator.",
+                functions::take::take_fn,
+            )
+            .register(
+                "functional",
+                "reduce",
+                Range(2, 3),
+                "Folds a list with f(acc, item), starting from the initial value or first element.",
+                functions::reduce::reduce_fn,
+            )
+            .register(
+                "functional",
+                "any",
+                Range(1, 2),
+                "Whether any element (or predicate result) is truthy.",
+                functions::any::any_fn,
+            )
+            .register(
+                "functional",
+                "all",
+                Range(1, 2),
+                "Whether every element (or predicate result) is truthy.",
+                functions::all::all_fn,
+            )
+            .register(
+                "functional",
+                "find",
+                Exact(2),
+                "First element the predicate accepts, or void.",
+                functions::find::find_fn,
+            )
+            .register(
+                "functional",
+                "group_by",
+                Exact(2),
+                "Map from key to the elements with that key.",
+                functions::group_by::group_by_fn,
+            )
+            .register(
+                "functional",
+                "sort_by",
+                Exact(2),
+                "New list sorted by a key function.",
+                functions::sort_by::sort_by_fn,
+            )
+            .register(
+                "functional",
+                "min_by",
+                Exact(2),
+                "Element with the smallest key, or void.",
+                functions::min_by::min_by_fn,
+            )
+            .register(
+                "functional",
+                "max_by",
+                Exact(2),
+                "Element with the largest key, or void.",
+                functions::max_by::max_by_fn,
+            )
+            .register(
+                "reflection",
+                "class_of",
+                Exact(1),
+                "Name of the class of an object.",
+                functions::class_of::class_of_fn,
+            )
+            .register(
+                "reflection",
+                "fields",
+                Exact(1),
+                "Fields of an object or class, inherited ones first.",
+                functions::fields::fields_fn,
+            )
+            .register(
+                "reflection",
+                "methods",
+                Exact(1),
+                "Sorted method names of an object or class, including static and inherited ones.",
+                functions::methods::methods_fn,
+            )
+            .register(
+                "reflection",
+                "has_field",
+                Exact(2),
+                "Whether an object or class has the named field.",
+                functions::has_field::has_field_fn,
+            )
+            .register(
+                "reflection",
+                "has_method",
+                Exact(2),
+                "Whether an object or class has the named method.",
+                functions::has_method::has_method_fn,
+            )
+            .register(
+                "reflection",
+                "isinstance",
+                Exact(2),
+                "Whether a value is an object of the named class or a subclass.",
+                functions::isinstance::isinstance_fn,
+            );
+        registry
+    }
+}
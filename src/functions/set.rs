@@ -1,23 +1,18 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
 
-pub fn set_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 3 {
-        return Err(RuntimeError {
-            message: format!("set expects 3 arguments, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
+pub fn set_fn(_call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match &args[0] {
         Value::Map(m) => {
             let mut map = m.borrow_mut();
             let key = match &args[1] {
                 Value::String(s) => s.borrow().clone(),
                 _ => {
-                    return Err(RuntimeError {
-                        message: "Second argument to set for Map must be a string".to_string(),
-                        stack_trace: vec![],
-                    });
+                    return Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        "Second argument to set for Map must be a string",
+                    ));
                 }
             };
             map.insert(key, args[2].clone());
@@ -28,17 +23,17 @@ pub fn set_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
             let index = match &args[1] {
                 Value::Int(i) => *i as usize,
                 _ => {
-                    return Err(RuntimeError {
-                        message: "Second argument to set for List must be an integer".to_string(),
-                        stack_trace: vec![],
-                    });
+                    return Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        "Second argument to set for List must be an integer",
+                    ));
                 }
             };
             if index >= list.len() {
-                return Err(RuntimeError {
-                    message: format!("Index {} out of bounds (len {})", index, list.len()),
-                    stack_trace: vec![],
-                });
+                return Err(RuntimeError::new(
+                    ErrorKind::IndexError,
+                    format!("Index {} out of bounds (len {})", index, list.len()),
+                ));
             }
             list[index] = args[2].clone();
             Ok(Value::Void)
@@ -48,19 +43,19 @@ pub fn set_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
             let index = match &args[1] {
                 Value::Int(i) => *i as usize,
                 _ => {
-                    return Err(RuntimeError {
-                        message: "Second argument to set for String must be an integer".to_string(),
-                        stack_trace: vec![],
-                    });
+                    return Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        "Second argument to set for String must be an integer",
+                    ));
                 }
             };
             let char_val = match &args[2] {
                 Value::String(c) => c.borrow().clone(),
                 _ => {
-                    return Err(RuntimeError {
-                        message: "Third argument to set for String must be a string".to_string(),
-                        stack_trace: vec![],
-                    });
+                    return Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        "Third argument to set for String must be a string",
+                    ));
                 }
             };
 
@@ -69,10 +64,10 @@ pub fn set_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
             // Simplest way: collect chars, replace, collect string.
             let mut chars: Vec<char> = string.chars().collect();
             if index >= chars.len() {
-                return Err(RuntimeError {
-                    message: format!("Index {} out of bounds (len {})", index, chars.len()),
-                    stack_trace: vec![],
-                });
+                return Err(RuntimeError::new(
+                    ErrorKind::IndexError,
+                    format!("Index {} out of bounds (len {})", index, chars.len()),
+                ));
             }
 
             // We expect char_val to be a single char string, or we take the first char?
@@ -82,15 +77,15 @@ pub fn set_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
                 *string = chars.into_iter().collect();
                 Ok(Value::Void)
             } else {
-                Err(RuntimeError {
-                    message: "Value to set in string cannot be empty".to_string(),
-                    stack_trace: vec![],
-                })
+                Err(RuntimeError::new(
+                    ErrorKind::ValueError,
+                    "Value to set in string cannot be empty",
+                ))
             }
         }
-        _ => Err(RuntimeError {
-            message: "First argument to set must be a map, list, or string".to_string(),
-            stack_trace: vec![],
-        }),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "First argument to set must be a map, list, or string",
+        )),
     }
 }
@@ -1,76 +1,85 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
 use std::cell::RefCell;
 use std::rc::Rc;
 
-pub fn slice_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 3 {
-        return Err(RuntimeError {
-            message: format!("slice expects 3 arguments, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
-
+pub fn slice_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     let (start_idx, end_idx) = match (&args[1], &args[2]) {
         (Value::Int(s), Value::Int(e)) => (*s, *e),
         _ => {
-            return Err(RuntimeError {
-                message: "slice expects integer indices for start and end".to_string(),
-                stack_trace: vec![],
-            });
+            return Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                "slice expects integer indices for start and end",
+            ));
         }
     };
 
-    match &args[0] {
+    let result = slice_value(&args[0], Some(start_idx), Some(end_idx))?;
+    call.allocate(slice_size(&result))?;
+    Ok(result)
+}
+
+/// Slices a string or list. Negative indices count from the end; a missing
+/// start or end means the start or end of the value. Shared with the slice
+/// syntax `value[start:end]`.
+pub fn slice_value(
+    value: &Value,
+    start_idx: Option<i64>,
+    end_idx: Option<i64>,
+) -> Result<Value, RuntimeError> {
+    match value {
         Value::String(s) => {
             let string = s.borrow();
             let chars: Vec<char> = string.chars().collect();
-            let len = chars.len() as i32;
-
-            let start = normalize_index(start_idx, len);
-            let end = normalize_index(end_idx, len);
-
-            if start < 0 || end < 0 || start > len || end > len || start > end {
-                return Err(RuntimeError {
-                    message: format!(
-                        "slice indices out of bounds: start={}, end={}, len={}",
-                        start, end, len
-                    ),
-                    stack_trace: vec![],
-                });
-            }
-
-            let result: String = chars[start as usize..end as usize].iter().collect();
+            let (start, end) = bounds(start_idx, end_idx, chars.len())?;
+            let result: String = chars[start..end].iter().collect();
             Ok(Value::String(Rc::new(RefCell::new(result))))
         }
         Value::List(l) => {
             let list = l.borrow();
-            let len = list.len() as i32;
+            let (start, end) = bounds(start_idx, end_idx, list.len())?;
+            let result: Vec<Value> = list[start..end].to_vec();
+            Ok(Value::List(Rc::new(RefCell::new(result))))
+        }
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "slice expects a string or list as first argument",
+        )),
+    }
+}
 
-            let start = normalize_index(start_idx, len);
-            let end = normalize_index(end_idx, len);
+/// Memory units of a slice: bytes for a string, elements for a list.
+pub fn slice_size(value: &Value) -> usize {
+    match value {
+        Value::String(s) => s.borrow().len(),
+        Value::List(l) => l.borrow().len(),
+        _ => 0,
+    }
+}
 
-            if start < 0 || end < 0 || start > len || end > len || start > end {
-                return Err(RuntimeError {
-                    message: format!(
-                        "slice indices out of bounds: start={}, end={}, len={}",
-                        start, end, len
-                    ),
-                    stack_trace: vec![],
-                });
-            }
+fn bounds(
+    start_idx: Option<i64>,
+    end_idx: Option<i64>,
+    len: usize,
+) -> Result<(usize, usize), RuntimeError> {
+    let len = len as i64;
+    let start = normalize_index(start_idx.unwrap_or(0), len);
+    let end = normalize_index(end_idx.unwrap_or(len), len);
 
-            let result: Vec<Value> = list[start as usize..end as usize].to_vec();
-            Ok(Value::List(Rc::new(RefCell::new(result))))
-        }
-        _ => Err(RuntimeError {
-            message: "slice expects a string or list as first argument".to_string(),
-            stack_trace: vec![],
-        }),
+    if start < 0 || end < 0 || start > len || end > len || start > end {
+        return Err(RuntimeError::new(
+            ErrorKind::IndexError,
+            format!(
+                "slice indices out of bounds: start={}, end={}, len={}",
+                start, end, len
+            ),
+        ));
     }
+    Ok((start as usize, end as usize))
 }
 
-fn normalize_index(idx: i32, len: i32) -> i32 {
+fn normalize_index(idx: i64, len: i64) -> i64 {
     if idx < 0 {
         (len + idx).max(0)
     } else {
@@ -1,17 +1,23 @@
-use crate::error::RuntimeError;
-use crate::node::Value;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::callback::{callable_arg, list_arg};
+use crate::functions::registry::CallCtx;
+use crate::node::{Callable, Value};
+use crate::numeric;
 use std::cmp::Ordering;
 
-pub fn sort_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(RuntimeError {
-            message: format!("sort expects 1 argument, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
-
+/// `sort(list)` or `sort(list, key)`: sorts the list in place, optionally
+/// comparing the results of a key function.
+pub fn sort_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match &args[0] {
         Value::List(l) => {
+            if let Some(key_fn) = args.get(1) {
+                let key_fn = callable_arg("sort", key_fn)?;
+                let items = l.borrow().clone();
+                let sorted = sort_by_key(call, items, &key_fn)?;
+                *l.borrow_mut() = sorted;
+                return Ok(Value::Void);
+            }
+
             let mut list = l.borrow_mut();
 
             // Check if all elements are comparable
@@ -24,50 +30,73 @@ pub fn sort_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
 
             Ok(Value::Void)
         }
-        _ => Err(RuntimeError {
-            message: "sort expects a list".to_string(),
-            stack_trace: vec![],
-        }),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "sort expects a list",
+        )),
     }
 }
 
-fn compare_values(a: &Value, b: &Value) -> Result<Ordering, RuntimeError> {
-    match (a, b) {
-        (Value::Int(x), Value::Int(y)) => Ok(x.cmp(y)),
-        (Value::Float(x), Value::Float(y)) => {
-            if x < y {
-                Ok(Ordering::Less)
-            } else if x > y {
-                Ok(Ordering::Greater)
-            } else {
-                Ok(Ordering::Equal)
-            }
-        }
-        (Value::Int(x), Value::Float(y)) => {
-            let x_f = *x as f64;
-            if x_f < *y {
-                Ok(Ordering::Less)
-            } else if x_f > *y {
-                Ok(Ordering::Greater)
-            } else {
-                Ok(Ordering::Equal)
-            }
-        }
-        (Value::Float(x), Value::Int(y)) => {
-            let y_f = *y as f64;
-            if x < &y_f {
-                Ok(Ordering::Less)
-            } else if x > &y_f {
-                Ok(Ordering::Greater)
-            } else {
-                Ok(Ordering::Equal)
-            }
+/// Stable sort of `items` by the keys `key_fn` returns for them.
+pub(crate) fn sort_by_key(
+    call: &mut CallCtx,
+    items: Vec<Value>,
+    key_fn: &Callable,
+) -> Result<Vec<Value>, RuntimeError> {
+    let mut keyed = Vec::with_capacity(items.len());
+    for item in items {
+        keyed.push((call.call_function(key_fn, vec![item.clone()])?, item));
+    }
+
+    let mut error = None;
+    keyed.sort_by(|(a, _), (b, _)| {
+        compare_values(a, b).unwrap_or_else(|e| {
+            error.get_or_insert(e);
+            Ordering::Equal
+        })
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(keyed.into_iter().map(|(_, item)| item).collect()),
+    }
+}
+
+/// Finds the first element whose key compares as `wanted` against all
+/// others (`Less` for the minimum, `Greater` for the maximum).
+pub(crate) fn extreme_by(
+    call: &mut CallCtx,
+    name: &str,
+    list: &Value,
+    key_fn: &Value,
+    wanted: Ordering,
+) -> Result<Value, RuntimeError> {
+    let items = list_arg(name, list)?;
+    let key_fn = callable_arg(name, key_fn)?;
+
+    let mut best: Option<(Value, Value)> = None;
+    for item in items {
+        let key = call.call_function(&key_fn, vec![item.clone()])?;
+        let replace = match &best {
+            None => true,
+            Some((best_key, _)) => compare_values(&key, best_key)? == wanted,
+        };
+        if replace {
+            best = Some((key, item));
         }
+    }
+    Ok(best.map(|(_, item)| item).unwrap_or(Value::Void))
+}
+
+pub(crate) fn compare_values(a: &Value, b: &Value) -> Result<Ordering, RuntimeError> {
+    if let Some(ordering) = numeric::compare(a, b) {
+        return Ok(ordering);
+    }
+    match (a, b) {
         (Value::String(x), Value::String(y)) => Ok(x.borrow().cmp(&y.borrow())),
         (Value::Bool(x), Value::Bool(y)) => Ok(x.cmp(y)),
-        _ => Err(RuntimeError {
-            message: "sort: unable to compare mixed types".to_string(),
-            stack_trace: vec![],
-        }),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "sort: unable to compare mixed types",
+        )),
     }
 }
@@ -0,0 +1,17 @@
+use crate::error::RuntimeError;
+use crate::functions::callback::{callable_arg, list_arg};
+use crate::functions::registry::CallCtx;
+use crate::functions::sort::sort_by_key;
+use crate::node::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Returns a new list sorted by the key function; the input is left as is.
+pub fn sort_by_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let items = list_arg("sort_by", &args[0])?;
+    let key_fn = callable_arg("sort_by", &args[1])?;
+
+    let sorted = sort_by_key(call, items, &key_fn)?;
+    call.allocate(sorted.len())?;
+    Ok(Value::List(Rc::new(RefCell::new(sorted))))
+}
@@ -1,16 +1,10 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
 use std::cell::RefCell;
 use std::rc::Rc;
 
-pub fn split_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 2 {
-        return Err(RuntimeError {
-            message: format!("split expects 2 arguments, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
-
+pub fn split_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match (&args[0], &args[1]) {
         (Value::String(s), Value::String(delim)) => {
             let string = s.borrow();
@@ -21,11 +15,12 @@ pub fn split_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
                 .map(|part| Value::String(Rc::new(RefCell::new(part.to_string()))))
                 .collect();
 
+            call.allocate(parts.len())?;
             Ok(Value::List(Rc::new(RefCell::new(parts))))
         }
-        _ => Err(RuntimeError {
-            message: "split expects two strings (string, delimiter)".to_string(),
-            stack_trace: vec![],
-        }),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "split expects two strings (string, delimiter)",
+        )),
     }
 }
@@ -1,56 +1,25 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
+use crate::numeric::{self, ArithOp};
 
-pub fn sum_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(RuntimeError {
-            message: format!("sum expects 1 argument, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
-
+pub fn sum_fn(_call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match &args[0] {
         Value::List(l) => {
             let list = l.borrow();
-            let mut int_sum = 0i32;
-            let mut float_sum = 0.0f64;
-            let mut has_float = false;
+            let not_numbers =
+                || RuntimeError::new(ErrorKind::TypeError, "sum expects a list of numbers");
 
+            // Stays exact until the first float.
+            let mut total = Value::Int(0);
             for item in list.iter() {
-                match item {
-                    Value::Int(n) => {
-                        if has_float {
-                            float_sum += *n as f64;
-                        } else {
-                            int_sum += n;
-                        }
-                    }
-                    Value::Float(n) => {
-                        if !has_float {
-                            has_float = true;
-                            float_sum = int_sum as f64 + n;
-                        } else {
-                            float_sum += n;
-                        }
-                    }
-                    _ => {
-                        return Err(RuntimeError {
-                            message: "sum expects a list of numbers".to_string(),
-                            stack_trace: vec![],
-                        });
-                    }
-                }
-            }
-
-            if has_float {
-                Ok(Value::Float(float_sum))
-            } else {
-                Ok(Value::Int(int_sum))
+                total = numeric::arith(ArithOp::Add, &total, item).ok_or_else(not_numbers)??;
             }
+            Ok(total)
         }
-        _ => Err(RuntimeError {
-            message: "sum expects a list".to_string(),
-            stack_trace: vec![],
-        }),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "sum expects a list",
+        )),
     }
 }
@@ -0,0 +1,37 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
+use crate::iteration::Iter;
+use crate::node::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// `take(iterable, n)`: list of the first `n` items. Only those items are
+/// produced, so it works on infinite generators.
+pub fn take_fn(call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
+    let count = match &args[1] {
+        Value::Int(n) if *n >= 0 => *n as usize,
+        Value::Int(_) => {
+            return Err(RuntimeError::new(
+                ErrorKind::ValueError,
+                "take expects a non-negative count",
+            ));
+        }
+        other => {
+            return Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                format!("take expects an Int count, got {}", other.type_name()),
+            ));
+        }
+    };
+
+    let mut items = Iter::new(call.context(), args[0].clone())?;
+    let mut result = Vec::new();
+    while result.len() < count {
+        match items.next(call.context())? {
+            Some(item) => result.push(item),
+            None => break,
+        }
+    }
+    call.allocate(result.len())?;
+    Ok(Value::List(Rc::new(RefCell::new(result))))
+}
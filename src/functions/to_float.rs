@@ -1,31 +1,27 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
+use crate::numeric;
 
-pub fn to_float_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(RuntimeError {
-            message: format!("to_float expects 1 argument, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
-
+pub fn to_float_fn(_call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match &args[0] {
         Value::String(s) => {
             let s = s.borrow();
             let trimmed = s.trim();
             match trimmed.parse::<f64>() {
                 Ok(f) => Ok(Value::Float(f)),
-                Err(_) => Err(RuntimeError {
-                    message: format!("to_float: invalid number format '{}'", trimmed),
-                    stack_trace: vec![],
-                }),
+                Err(_) => Err(RuntimeError::new(
+                    ErrorKind::ValueError,
+                    format!("to_float: invalid number format '{}'", trimmed),
+                )),
             }
         }
-        Value::Int(i) => Ok(Value::Float(*i as f64)),
-        Value::Float(f) => Ok(Value::Float(*f)),
-        _ => Err(RuntimeError {
-            message: "to_float expects a string, int, or float".to_string(),
-            stack_trace: vec![],
-        }),
+        Value::Int(_) | Value::BigInt(_) | Value::Float(_) | Value::Decimal(_) => {
+            Ok(Value::Float(numeric::to_f64(&args[0]).unwrap_or(f64::NAN)))
+        }
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "to_float expects a string, int, or float",
+        )),
     }
 }
@@ -1,31 +1,33 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::registry::CallCtx;
 use crate::node::Value;
+use crate::numeric;
+use num_bigint::BigInt;
 
-pub fn to_int_fn(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(RuntimeError {
-            message: format!("to_int expects 1 argument, got {}", args.len()),
-            stack_trace: vec![],
-        });
-    }
-
+pub fn to_int_fn(_call: &mut CallCtx, args: &[Value]) -> Result<Value, RuntimeError> {
     match &args[0] {
         Value::String(s) => {
             let s = s.borrow();
             let trimmed = s.trim();
-            match trimmed.parse::<i32>() {
-                Ok(i) => Ok(Value::Int(i)),
-                Err(_) => Err(RuntimeError {
-                    message: format!("to_int: invalid number format '{}'", trimmed),
-                    stack_trace: vec![],
-                }),
+            match trimmed.parse::<BigInt>() {
+                Ok(i) => Ok(numeric::int_value(i)),
+                Err(_) => Err(RuntimeError::new(
+                    ErrorKind::ValueError,
+                    format!("to_int: invalid number format '{}'", trimmed),
+                )),
             }
         }
-        Value::Int(i) => Ok(Value::Int(*i)),
-        Value::Float(f) => Ok(Value::Int(*f as i32)),
-        _ => Err(RuntimeError {
-            message: "to_int expects a string, int, or float".to_string(),
-            stack_trace: vec![],
+        Value::Int(_) | Value::BigInt(_) => Ok(args[0].clone()),
+        Value::Float(f) => numeric::truncate_f64(*f).ok_or_else(|| {
+            RuntimeError::new(
+                ErrorKind::Overflow,
+                format!("to_int: {} has no integer value", f),
+            )
         }),
+        Value::Decimal(d) => Ok(numeric::truncate_decimal(*d)),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            "to_int expects a string, int, or float",
+        )),
     }
 }
@@ -0,0 +1,273 @@
+//! Generators: functions whose body contains `yield`. Calling one returns an
+//! iterator that runs the body up to the next `yield` each time an item is
+//! taken, so infinite sequences and long pipelines only do the work asked of
+//! them.
+//!
+//! `Node::run` cannot stop halfway through a statement, so the generator
+//! executes the statements that may contain a `yield` (blocks, `if`, `while`
+//! and `for`) itself and runs everything else normally. When a `yield`
+//! suspends the body, each enclosing statement records where it was in a
+//! `Slot`; the next resume walks back down the same path.
+
+use crate::environment::{Env, Environment};
+use crate::error::{ErrorKind, RuntimeError};
+use crate::iteration::Iter;
+use crate::node::{Context, Node, STACK_GROWTH, STACK_RED_ZONE, SpannedNode, Value};
+use crate::nodes::{
+    Block, ClassDef, ForNode, FunctionDef, If, Lambda, MethodDef, WhileNode, Yield,
+};
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+/// Whether `node` contains a `yield` belonging to the function it is in.
+/// Nested functions, lambdas and classes have their own bodies.
+pub fn contains_yield(node: &dyn Node) -> bool {
+    let any = node.as_any();
+    if any.is::<Yield>() {
+        return true;
+    }
+    if any.is::<FunctionDef>()
+        || any.is::<Lambda>()
+        || any.is::<ClassDef>()
+        || any.is::<MethodDef>()
+    {
+        return false;
+    }
+    node.children().into_iter().any(contains_yield)
+}
+
+/// A suspended call of a generator function.
+pub struct Generator {
+    name: String,
+    body: Rc<dyn Node>,
+    /// The call's context, kept between resumes.
+    ctx: Context,
+    frame: Frame,
+    done: bool,
+}
+
+impl Generator {
+    /// A generator that has not started yet. `ctx` has the arguments bound.
+    pub fn new(name: String, body: Rc<dyn Node>, ctx: Context) -> Generator {
+        Generator {
+            name,
+            body,
+            ctx,
+            frame: Frame::default(),
+            done: false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Runs the body up to the next `yield` and returns its value, or `None`
+    /// once the body has finished or returned.
+    pub fn resume(&mut self, caller: &Context) -> Result<Option<Value>, RuntimeError> {
+        if self.done {
+            return Ok(None);
+        }
+        if caller.depth >= caller.max_depth {
+            return Err(RuntimeError::new(
+                ErrorKind::RecursionLimit,
+                "maximum recursion depth exceeded",
+            ));
+        }
+        self.ctx.depth = caller.depth + 1;
+
+        let body = self.body.clone();
+        let (frame, ctx) = (&mut self.frame, &mut self.ctx);
+        let result = stacker::maybe_grow(STACK_RED_ZONE, STACK_GROWTH, || {
+            frame.exec(body.as_ref(), ctx)
+        });
+        match result {
+            Ok(Flow::Yield(value)) => Ok(Some(value)),
+            Ok(Flow::Next) => {
+                self.finish();
+                Ok(None)
+            }
+            Err(err) => {
+                self.finish();
+                Err(err)
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        self.done = true;
+        self.frame.resume.clear();
+        self.ctx.control_flow = None;
+    }
+}
+
+/// How a statement run by the generator ended.
+enum Flow {
+    /// Finished, or raised a control flow signal left in the context.
+    Next,
+    /// Suspended at a `yield` with its value.
+    Yield(Value),
+}
+
+/// Where a statement enclosing the last `yield` was suspended.
+enum Slot {
+    /// At the statement with this index, in the block's scope.
+    Block { index: usize, env: Env },
+    /// In the `then` or the `else` branch.
+    If { then: bool },
+    /// In the body.
+    While,
+    /// In the body, with the iteration's scope.
+    For { iter: Iter, env: Env },
+}
+
+#[derive(Default)]
+struct Frame {
+    /// Slots of the statements around the last `yield`, outermost first.
+    resume: VecDeque<Slot>,
+    /// `contains_yield` of the nodes seen so far, keyed by address.
+    yields: HashMap<usize, bool>,
+}
+
+impl Frame {
+    fn exec(&mut self, node: &dyn Node, ctx: &mut Context) -> Result<Flow, RuntimeError> {
+        let any = node.as_any();
+        if let Some(spanned) = any.downcast_ref::<SpannedNode>() {
+            return self.exec(spanned.inner.as_ref(), ctx).map_err(|mut err| {
+                err.span.get_or_insert(spanned.span);
+                err
+            });
+        }
+        if self.resume.is_empty() && !self.contains_yield(node) {
+            node.run(ctx)?;
+            return Ok(Flow::Next);
+        }
+
+        if let Some(node) = any.downcast_ref::<Yield>() {
+            Ok(Flow::Yield(node.value.run(ctx)?))
+        } else if let Some(node) = any.downcast_ref::<Block>() {
+            self.exec_block(node, ctx)
+        } else if let Some(node) = any.downcast_ref::<If>() {
+            self.exec_if(node, ctx)
+        } else if let Some(node) = any.downcast_ref::<WhileNode>() {
+            self.exec_while(node, ctx)
+        } else if let Some(node) = any.downcast_ref::<ForNode>() {
+            self.exec_for(node, ctx)
+        } else {
+            // `check` rejects programs that reach this.
+            Err(RuntimeError::new(
+                ErrorKind::Other,
+                "Yield is only supported directly inside blocks, if, while and for",
+            ))
+        }
+    }
+
+    fn contains_yield(&mut self, node: &dyn Node) -> bool {
+        let key = node as *const dyn Node as *const () as usize;
+        *self
+            .yields
+            .entry(key)
+            .or_insert_with(|| contains_yield(node))
+    }
+
+    fn exec_block(&mut self, block: &Block, ctx: &mut Context) -> Result<Flow, RuntimeError> {
+        let (mut index, env) = match self.resume.pop_front() {
+            // Resume inside the statement that yielded, or after it if the
+            // `yield` was the statement itself.
+            Some(Slot::Block { index, env }) if self.resume.is_empty() => (index + 1, env),
+            Some(Slot::Block { index, env }) => (index, env),
+            _ => (0, Environment::new_block(&ctx.env)),
+        };
+        let outer = std::mem::replace(&mut ctx.env, env);
+        let mut flow = Ok(Flow::Next);
+        while let Some(statement) = block.statements.get(index) {
+            flow = self.exec(statement.as_ref(), ctx);
+            if !matches!(flow, Ok(Flow::Next)) || ctx.control_flow.is_some() {
+                break;
+            }
+            index += 1;
+        }
+        let env = std::mem::replace(&mut ctx.env, outer);
+        if let Ok(Flow::Yield(_)) = flow {
+            self.resume.push_front(Slot::Block { index, env });
+        }
+        flow
+    }
+
+    fn exec_if(&mut self, node: &If, ctx: &mut Context) -> Result<Flow, RuntimeError> {
+        let then = match self.resume.pop_front() {
+            Some(Slot::If { then }) => then,
+            _ => node.condition.run(ctx)?.is_truthy(),
+        };
+        let branch = match then {
+            true => Some(&node.then_block),
+            false => node.else_block.as_ref(),
+        };
+        let Some(branch) = branch else {
+            return Ok(Flow::Next);
+        };
+        let flow = self.exec(branch.as_ref(), ctx)?;
+        if let Flow::Yield(_) = flow {
+            self.resume.push_front(Slot::If { then });
+        }
+        Ok(flow)
+    }
+
+    fn exec_while(&mut self, node: &WhileNode, ctx: &mut Context) -> Result<Flow, RuntimeError> {
+        let mut resuming = matches!(self.resume.pop_front(), Some(Slot::While));
+        loop {
+            if !resuming {
+                ctx.step()?;
+                if !node.condition.run(ctx)?.is_truthy() {
+                    break;
+                }
+            }
+            resuming = false;
+
+            let flow = self.exec(node.body.as_ref(), ctx)?;
+            if let Flow::Yield(_) = flow {
+                self.resume.push_front(Slot::While);
+                return Ok(flow);
+            }
+            if !ctx.loop_should_continue(node.label.as_deref()) {
+                break;
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    fn exec_for(&mut self, node: &ForNode, ctx: &mut Context) -> Result<Flow, RuntimeError> {
+        let (mut iter, mut env) = match self.resume.pop_front() {
+            Some(Slot::For { iter, env }) => (iter, Some(env)),
+            _ => (node.iterate(ctx)?, None),
+        };
+        loop {
+            let env = match env.take() {
+                Some(env) => env,
+                None => {
+                    let Some((index, element)) = node.next_binding(&mut iter, ctx)? else {
+                        break;
+                    };
+                    ctx.step()?;
+                    let env = Environment::new_block(&ctx.env);
+                    let outer = std::mem::replace(&mut ctx.env, env);
+                    node.bind(ctx, index, element);
+                    std::mem::replace(&mut ctx.env, outer)
+                }
+            };
+
+            let outer = std::mem::replace(&mut ctx.env, env);
+            let flow = self.exec(node.body.as_ref(), ctx);
+            let env = std::mem::replace(&mut ctx.env, outer);
+            let flow = flow?;
+            if let Flow::Yield(_) = flow {
+                self.resume.push_front(Slot::For { iter, env });
+                return Ok(flow);
+            }
+            if !ctx.loop_should_continue(node.label.as_deref()) {
+                break;
+            }
+        }
+        Ok(Flow::Next)
+    }
+}
@@ -6,22 +6,35 @@ pub enum Rule {
     Program,
     Stmt,
     Assignment,
+    Declaration,
+    CompoundAssignment,
+    Nonlocal,
     Return,
+    Yield,
+    Throw,
+    TryCatch,
     Comparison,
     LogicalOr,
     LogicalAnd,
     Term,
     Factor,
     Unary,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    Shift,
+    Power,
     IfElse,
     IfThen,
     Int,
     Float,
+    Decimal,
     String,
     True,
     False,
     FunctionDef,
     FunctionCall,
+    Lambda,
     ParamList,
     ArgList,
     ListLiteral,
@@ -31,21 +44,41 @@ pub enum Rule {
     MapEntry,
     ForLoop,
     WhileLoop,
+    Break,
+    Continue,
     Block,
     Identifier,
     Expr,
     Atom,
     If,
     UnaryOp,
+    Not,
+    Neg,
+    BitNot,
     Eq,
     Neq,
     Lt,
     Gt,
+    Le,
+    Ge,
     Add,
     Sub,
     Mul,
     Div,
+    FloatDiv,
+    IntDiv,
     Mod,
+    Pow,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+    AddAssign,
+    SubAssign,
+    MulAssign,
+    DivAssign,
+    ModAssign,
     Key,
     ClassDef,
     ClassMember,
@@ -55,6 +88,7 @@ pub enum Rule {
     MemberAccess,
     MethodCall,
     SelfReference,
+    SuperCall,
     Postfix,
     PostfixSuffix,
     Print,
@@ -68,22 +102,35 @@ impl FromStr for Rule {
             "Program" => Ok(Rule::Program),
             "Stmt" => Ok(Rule::Stmt),
             "Assignment" => Ok(Rule::Assignment),
+            "Declaration" => Ok(Rule::Declaration),
+            "CompoundAssignment" => Ok(Rule::CompoundAssignment),
+            "Nonlocal" => Ok(Rule::Nonlocal),
             "Return" => Ok(Rule::Return),
+            "Yield" => Ok(Rule::Yield),
+            "Throw" => Ok(Rule::Throw),
+            "TryCatch" => Ok(Rule::TryCatch),
             "Comparison" => Ok(Rule::Comparison),
             "LogicalOr" => Ok(Rule::LogicalOr),
             "LogicalAnd" => Ok(Rule::LogicalAnd),
             "Term" => Ok(Rule::Term),
             "Factor" => Ok(Rule::Factor),
             "Unary" => Ok(Rule::Unary),
+            "BitwiseOr" => Ok(Rule::BitwiseOr),
+            "BitwiseXor" => Ok(Rule::BitwiseXor),
+            "BitwiseAnd" => Ok(Rule::BitwiseAnd),
+            "Shift" => Ok(Rule::Shift),
+            "Power" => Ok(Rule::Power),
             "IfElse" => Ok(Rule::IfElse),
             "IfThen" => Ok(Rule::IfThen),
             "Int" => Ok(Rule::Int),
             "Float" => Ok(Rule::Float),
+            "Decimal" => Ok(Rule::Decimal),
             "String" => Ok(Rule::String),
             "True" => Ok(Rule::True),
             "False" => Ok(Rule::False),
             "FunctionDef" => Ok(Rule::FunctionDef),
             "FunctionCall" => Ok(Rule::FunctionCall),
+            "Lambda" => Ok(Rule::Lambda),
             "ParamList" => Ok(Rule::ParamList),
             "ArgList" => Ok(Rule::ArgList),
             "ListLiteral" => Ok(Rule::ListLiteral),
@@ -93,21 +140,41 @@ impl FromStr for Rule {
             "MapEntry" => Ok(Rule::MapEntry),
             "ForLoop" => Ok(Rule::ForLoop),
             "WhileLoop" => Ok(Rule::WhileLoop),
+            "Break" => Ok(Rule::Break),
+            "Continue" => Ok(Rule::Continue),
             "Block" => Ok(Rule::Block),
             "Identifier" => Ok(Rule::Identifier),
             "Expr" => Ok(Rule::Expr),
             "Atom" => Ok(Rule::Atom),
             "If" => Ok(Rule::If),
             "UnaryOp" => Ok(Rule::UnaryOp),
+            "Not" => Ok(Rule::Not),
+            "Neg" => Ok(Rule::Neg),
+            "BitNot" => Ok(Rule::BitNot),
             "Eq" => Ok(Rule::Eq),
             "Neq" => Ok(Rule::Neq),
             "Lt" => Ok(Rule::Lt),
             "Gt" => Ok(Rule::Gt),
+            "Le" => Ok(Rule::Le),
+            "Ge" => Ok(Rule::Ge),
             "Add" => Ok(Rule::Add),
             "Sub" => Ok(Rule::Sub),
             "Mul" => Ok(Rule::Mul),
             "Div" => Ok(Rule::Div),
+            "FloatDiv" => Ok(Rule::FloatDiv),
+            "IntDiv" => Ok(Rule::IntDiv),
             "Mod" => Ok(Rule::Mod),
+            "Pow" => Ok(Rule::Pow),
+            "BitAnd" => Ok(Rule::BitAnd),
+            "BitOr" => Ok(Rule::BitOr),
+            "BitXor" => Ok(Rule::BitXor),
+            "Shl" => Ok(Rule::Shl),
+            "Shr" => Ok(Rule::Shr),
+            "AddAssign" => Ok(Rule::AddAssign),
+            "SubAssign" => Ok(Rule::SubAssign),
+            "MulAssign" => Ok(Rule::MulAssign),
+            "DivAssign" => Ok(Rule::DivAssign),
+            "ModAssign" => Ok(Rule::ModAssign),
             "Key" => Ok(Rule::Key),
             "ClassDef" => Ok(Rule::ClassDef),
             "ClassMember" => Ok(Rule::ClassMember),
@@ -117,6 +184,7 @@ impl FromStr for Rule {
             "MemberAccess" => Ok(Rule::MemberAccess),
             "MethodCall" => Ok(Rule::MethodCall),
             "SelfReference" => Ok(Rule::SelfReference),
+            "SuperCall" => Ok(Rule::SuperCall),
             "Postfix" => Ok(Rule::Postfix),
             "PostfixSuffix" => Ok(Rule::PostfixSuffix),
             "Print" => Ok(Rule::Print),
@@ -254,9 +322,14 @@ impl Grammar {
                     rule,
                     Rule::FunctionDef
                         | Rule::WhileLoop
+                        | Rule::Break
+                        | Rule::Continue
+                        | Rule::Declaration
+                        | Rule::Nonlocal
                         | Rule::IfElse
                         | Rule::IfThen
                         | Rule::Return
+                        | Rule::Yield
                         | Rule::ClassDef
                         | Rule::MethodDef
                         | Rule::NewExpr
@@ -273,10 +346,10 @@ impl Grammar {
                             Pattern::Named(_, sub) => {
                                 if let Pattern::RuleReference(Rule::Identifier) = **sub {
                                     seen_identifier = true;
-                                } else if let Pattern::Literal(s) = &**sub {
-                                    if !seen_identifier {
-                                        grammar.keywords.insert(s.clone());
-                                    }
+                                } else if let Pattern::Literal(s) = &**sub
+                                    && !seen_identifier
+                                {
+                                    grammar.keywords.insert(s.clone());
                                 }
                             }
                             Pattern::RuleReference(Rule::Identifier) => {
@@ -0,0 +1,171 @@
+use crate::budget::Limits;
+use crate::convert::{FromValue, IntoValue};
+use crate::error::{ErrorKind, InterpreterError, RuntimeError};
+use crate::grammar::{Grammar, Rule};
+use crate::node::{Context, Value};
+use crate::output::{CaptureOutput, Output};
+use crate::parser::Parser;
+use std::fs;
+use std::path::Path;
+
+/// Runs code in a language defined by a grammar, keeping functions, classes
+/// and global variables between calls.
+///
+/// ```
+/// use multilang::interpreter::Interpreter;
+///
+/// let mut interp = Interpreter::from_grammar_str(r#"
+///     Program = Stmt*
+///     Stmt = Assignment | Expr
+///     Assignment = name:Identifier "=" value:Expr
+///     Expr = Term
+///     Term = Atom Add Term | Atom
+///     Atom = Int | Identifier
+///     Add = [\+]
+///     Int = [[0-9]+]
+///     Identifier = [[a-zA-Z_][a-zA-Z0-9_]*]
+/// "#);
+/// interp.eval("x = 40").unwrap();
+/// let answer: i32 = interp.eval_as("x + 2").unwrap();
+/// assert_eq!(answer, 42);
+/// ```
+pub struct Interpreter {
+    grammar: Grammar,
+    ctx: Context,
+    captured: Option<CaptureOutput>,
+}
+
+impl Interpreter {
+    pub fn new(grammar: Grammar) -> Self {
+        Self::with_context(grammar, Context::new())
+    }
+
+    /// Uses a prepared context, e.g. one with a custom built-in registry.
+    pub fn with_context(grammar: Grammar, ctx: Context) -> Self {
+        Self {
+            grammar,
+            ctx,
+            captured: None,
+        }
+    }
+
+    pub fn from_grammar_str(grammar_def: &str) -> Self {
+        Self::new(Grammar::parse(grammar_def))
+    }
+
+    pub fn from_grammar_file(path: impl AsRef<Path>) -> Result<Self, InterpreterError> {
+        Ok(Self::from_grammar_str(&read_source(path.as_ref())?))
+    }
+
+    pub fn grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+
+    /// The global context, e.g. to register built-ins.
+    pub fn context(&mut self) -> &mut Context {
+        &mut self.ctx
+    }
+
+    /// Parses and runs `code` as a `Program`, returning the value of a
+    /// top-level `return` or else of the last statement.
+    pub fn eval(&mut self, code: &str) -> Result<Value, InterpreterError> {
+        let program = Parser::new(&self.grammar, code).parse(Rule::Program)?;
+
+        self.ctx.control_flow = None;
+        let result = program.run(&mut self.ctx);
+        let returned = self.ctx.take_return();
+        self.ctx.control_flow = None;
+        self.ctx.flush_output()?;
+        Ok(returned.unwrap_or(result?))
+    }
+
+    pub fn eval_as<T: FromValue>(&mut self, code: &str) -> Result<T, InterpreterError> {
+        let value = self.eval(code)?;
+        Ok(T::from_value(value)?)
+    }
+
+    pub fn run_file(&mut self, path: impl AsRef<Path>) -> Result<Value, InterpreterError> {
+        let code = read_source(path.as_ref())?;
+        self.eval(&code)
+    }
+
+    /// Calls a script function, lambda stored in a global, or built-in.
+    pub fn call(&mut self, name: &str, args: Vec<Value>) -> Result<Value, InterpreterError> {
+        let callee = match self.ctx.get_variable(name) {
+            Some(Value::Function(callee)) => Some((*callee).clone()),
+            _ => self.ctx.lookup_function(name),
+        };
+        let callee = callee.ok_or_else(|| {
+            RuntimeError::new(
+                ErrorKind::NameError,
+                format!("Function '{}' not found", name),
+            )
+        })?;
+        let result = self.ctx.call_function(&callee, args);
+        self.ctx.flush_output()?;
+        Ok(result?)
+    }
+
+    pub fn call_as<T: FromValue>(
+        &mut self,
+        name: &str,
+        args: Vec<Value>,
+    ) -> Result<T, InterpreterError> {
+        let value = self.call(name, args)?;
+        Ok(T::from_value(value)?)
+    }
+
+    /// Reads a global variable, converting it to `T`. Missing globals read
+    /// as `Void`.
+    pub fn get_global<T: FromValue>(&self, name: &str) -> Result<T, InterpreterError> {
+        let value = self.ctx.get_variable(name).unwrap_or(Value::Void);
+        Ok(T::from_value(value)?)
+    }
+
+    pub fn set_global(&mut self, name: &str, value: impl IntoValue) {
+        self.ctx.define_variable(name, value.into_value());
+    }
+
+    /// Restricts everything run from now on to `limits`. Usage accumulates
+    /// across calls until the limits are set again.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.ctx.set_limits(limits);
+    }
+
+    /// Sends everything scripts print to `output`.
+    pub fn set_output(&mut self, output: impl Output + 'static) {
+        self.captured = None;
+        self.ctx.set_output(output);
+    }
+
+    /// Collects everything printed from now on instead of writing it to
+    /// stdout and stderr; read it with `take_output`/`take_error_output`.
+    pub fn capture_output(&mut self) {
+        let capture = CaptureOutput::new();
+        self.ctx.set_output(capture.clone());
+        self.captured = Some(capture);
+    }
+
+    /// Returns and clears the captured stdout.
+    pub fn take_output(&mut self) -> String {
+        self.captured
+            .as_ref()
+            .map(CaptureOutput::take_stdout)
+            .unwrap_or_default()
+    }
+
+    /// Returns and clears the captured stderr.
+    pub fn take_error_output(&mut self) -> String {
+        self.captured
+            .as_ref()
+            .map(CaptureOutput::take_stderr)
+            .unwrap_or_default()
+    }
+}
+
+fn read_source(path: &Path) -> Result<String, InterpreterError> {
+    fs::read_to_string(path).map_err(|e| InterpreterError::Io {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    })
+}
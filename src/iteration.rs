@@ -0,0 +1,222 @@
+//! The iteration protocol behind `for` loops. Lists, strings, maps, ranges,
+//! objects with `__iter__` or `__next__` and iterator values such as
+//! generators can all be iterated.
+
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::range::RangeBounds;
+use crate::generator::Generator;
+use crate::node::{Callable, Context, Value};
+use crate::special;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+/// A position in something being iterated.
+pub struct Iter {
+    source: Source,
+    /// Number of items produced so far.
+    count: usize,
+}
+
+enum Source {
+    /// Read as the loop goes, so elements appended meanwhile are visited.
+    List(Rc<RefCell<Vec<Value>>>),
+    Chars(Vec<char>),
+    /// Keys in sorted order, as of the start of the loop. Keys removed
+    /// meanwhile are skipped.
+    Map {
+        map: Rc<RefCell<HashMap<String, Value>>>,
+        keys: Vec<String>,
+        next: usize,
+    },
+    Range(RangeBounds),
+    /// An object whose `__next__` returns void once it is exhausted.
+    Object(Value),
+    Generator(Box<Generator>),
+    /// An iterator value, advanced in place.
+    Shared(Rc<RefCell<Iter>>),
+    /// `map` over an iterator, applying `func` as items are taken.
+    Mapped {
+        inner: Box<Iter>,
+        func: Rc<Callable>,
+    },
+    /// `filter` over an iterator, skipping items `predicate` rejects.
+    Filter {
+        inner: Box<Iter>,
+        predicate: Rc<Callable>,
+    },
+}
+
+impl Iter {
+    pub fn new(ctx: &mut Context, value: Value) -> Result<Iter, RuntimeError> {
+        let source = match value {
+            Value::List(list) => Source::List(list),
+            Value::String(s) => Source::Chars(s.borrow().chars().collect()),
+            Value::Map(map) => {
+                let mut keys: Vec<String> = map.borrow().keys().cloned().collect();
+                keys.sort();
+                Source::Map { map, keys, next: 0 }
+            }
+            Value::Object(_) => return Iter::from_object(ctx, value),
+            Value::Iterator(iter) => Source::Shared(iter),
+            other => {
+                return Err(RuntimeError::new(
+                    ErrorKind::TypeError,
+                    format!("Cannot iterate over {}", other.type_name()),
+                ));
+            }
+        };
+        Ok(Iter { source, count: 0 })
+    }
+
+    pub fn range(bounds: RangeBounds) -> Iter {
+        Iter::from_source(Source::Range(bounds))
+    }
+
+    pub fn generator(generator: Generator) -> Iter {
+        Iter::from_source(Source::Generator(Box::new(generator)))
+    }
+
+    /// Applies `func` to each item of `inner` as it is taken.
+    pub fn map(inner: Iter, func: Rc<Callable>) -> Iter {
+        Iter::from_source(Source::Mapped {
+            inner: Box::new(inner),
+            func,
+        })
+    }
+
+    /// The items of `inner` that `predicate` accepts, tested as they are taken.
+    pub fn filter(inner: Iter, predicate: Rc<Callable>) -> Iter {
+        Iter::from_source(Source::Filter {
+            inner: Box::new(inner),
+            predicate,
+        })
+    }
+
+    fn from_source(source: Source) -> Iter {
+        Iter { source, count: 0 }
+    }
+
+    /// An object iterates over what its `__iter__` returns, or is itself an
+    /// iterator if it only has `__next__`.
+    fn from_object(ctx: &mut Context, object: Value) -> Result<Iter, RuntimeError> {
+        let iterable = match special::call(ctx, &object, special::ITER, &[]) {
+            Some(result) => result?,
+            None => object,
+        };
+        if !matches!(iterable, Value::Object(_)) {
+            return Iter::new(ctx, iterable);
+        }
+        if !special::has(ctx, &iterable, special::NEXT) {
+            let Value::Object(object) = &iterable else {
+                unreachable!()
+            };
+            return Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                format!(
+                    "Object of class '{}' is not iterable: it needs __iter__ or __next__",
+                    object.borrow().class_name
+                ),
+            ));
+        }
+        Ok(Iter::from_source(Source::Object(iterable)))
+    }
+
+    /// The next item. Maps produce their keys.
+    pub fn next(&mut self, ctx: &mut Context) -> Result<Option<Value>, RuntimeError> {
+        let is_map = matches!(self.source, Source::Map { .. });
+        Ok(self
+            .next_pair(ctx)?
+            .map(|(key, value)| if is_map { key } else { value }))
+    }
+
+    /// The next key and value of a map, or the next index and item of
+    /// anything else.
+    pub fn next_pair(&mut self, ctx: &mut Context) -> Result<Option<(Value, Value)>, RuntimeError> {
+        let index = self.count;
+        let item = match &mut self.source {
+            Source::List(list) => list.borrow().get(index).cloned(),
+            Source::Chars(chars) => chars
+                .get(index)
+                .map(|c| Value::String(Rc::new(RefCell::new(c.to_string())))),
+            Source::Range(bounds) => (index < bounds.len()).then(|| bounds.get(index)),
+            Source::Object(object) => match special::call(ctx, object, special::NEXT, &[]) {
+                Some(result) => match result? {
+                    Value::Void => None,
+                    item => Some(item),
+                },
+                None => None,
+            },
+            Source::Generator(generator) => generator.resume(ctx)?,
+            Source::Shared(iter) => match iter.try_borrow_mut() {
+                Ok(mut iter) => iter.next(ctx)?,
+                Err(_) => {
+                    return Err(RuntimeError::new(
+                        ErrorKind::ValueError,
+                        "Generator is already running",
+                    ));
+                }
+            },
+            Source::Mapped { inner, func } => match inner.next(ctx)? {
+                Some(item) => Some(ctx.call_function(func, vec![item])?),
+                None => None,
+            },
+            Source::Filter { inner, predicate } => loop {
+                match inner.next(ctx)? {
+                    Some(item) => {
+                        if ctx
+                            .call_function(predicate, vec![item.clone()])?
+                            .is_truthy()
+                        {
+                            break Some(item);
+                        }
+                    }
+                    None => break None,
+                }
+            },
+            Source::Map { map, keys, next } => {
+                let map = map.borrow();
+                while let Some(key) = keys.get(*next) {
+                    *next += 1;
+                    if let Some(value) = map.get(key) {
+                        self.count += 1;
+                        let key = Value::String(Rc::new(RefCell::new(key.clone())));
+                        return Ok(Some((key, value.clone())));
+                    }
+                }
+                None
+            }
+        };
+        Ok(item.map(|item| {
+            self.count += 1;
+            (Value::Int(index as i64), item)
+        }))
+    }
+}
+
+impl fmt::Display for Iter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            Source::Generator(generator) => write!(f, "<generator {}>", generator.name()),
+            Source::Shared(iter) => match iter.try_borrow() {
+                Ok(iter) => iter.fmt(f),
+                Err(_) => write!(f, "<iterator>"),
+            },
+            _ => write!(f, "<iterator>"),
+        }
+    }
+}
+
+impl fmt::Debug for Iter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Iterators are equal only to themselves.
+impl PartialEq for Iter {
+    fn eq(&self, other: &Iter) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
@@ -1,6 +1,17 @@
+pub mod budget;
+pub mod check;
+pub mod convert;
+pub mod environment;
 pub mod error;
 pub mod functions;
+pub mod generator;
 pub mod grammar;
+pub mod interpreter;
+pub mod iteration;
 pub mod node;
 pub mod nodes;
+pub mod numeric;
+pub mod output;
 pub mod parser;
+pub mod special;
+pub mod trace;
@@ -1,46 +1,153 @@
+use multilang::budget::Limits;
+use multilang::error::InterpreterError;
 use multilang::grammar::{Grammar, Rule};
-use multilang::node::Context;
+use multilang::interpreter::Interpreter;
 use multilang::parser::Parser as MLParser;
 
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to the grammar file
-    grammar_path: PathBuf,
+    #[arg(required = true)]
+    grammar_path: Option<PathBuf>,
 
     /// Path to the code file
-    code_path: PathBuf,
+    #[arg(required = true)]
+    code_path: Option<PathBuf>,
+
+    #[command(flatten)]
+    limits: LimitArgs,
 }
 
-fn main() {
-    let cli = Cli::parse();
+// Limits for running untrusted code; unlimited unless given.
+#[derive(clap::Args)]
+struct LimitArgs {
+    /// Maximum number of loop iterations and function calls
+    #[arg(long)]
+    max_steps: Option<u64>,
+
+    /// Maximum running time in milliseconds
+    #[arg(long)]
+    timeout_ms: Option<u64>,
+
+    /// Maximum memory in units: list elements, map entries and bytes of strings and big integers created
+    #[arg(long)]
+    max_memory: Option<usize>,
+
+    /// Maximum number of bytes printed
+    #[arg(long)]
+    max_output: Option<usize>,
+
+    /// Maximum depth of nested function calls
+    #[arg(long)]
+    max_depth: Option<usize>,
+}
 
-    let grammar_def = fs::read_to_string(&cli.grammar_path).expect(&format!(
-        "Failed to read grammar file: {:?}",
-        cli.grammar_path
-    ));
+#[derive(Subcommand)]
+enum Command {
+    /// Parse a code file without running it
+    Parse {
+        /// Path to the grammar file
+        grammar_path: PathBuf,
 
-    let input = fs::read_to_string(&cli.code_path)
-        .expect(&format!("Failed to read code file: {:?}", cli.code_path));
+        /// Path to the code file
+        code_path: PathBuf,
 
-    let grammar = Grammar::parse(&grammar_def);
+        /// Record every rule attempt and print the trace
+        #[arg(long)]
+        trace: bool,
+
+        /// Output format of the trace
+        #[arg(long, value_enum, default_value_t = TraceFormat::Tree)]
+        format: TraceFormat,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TraceFormat {
+    Tree,
+    Json,
+}
+
+fn read_sources(grammar_path: &Path, code_path: &Path) -> (Grammar, String) {
+    let grammar_def = fs::read_to_string(grammar_path)
+        .unwrap_or_else(|_| panic!("Failed to read grammar file: {:?}", grammar_path));
+
+    let input = fs::read_to_string(code_path)
+        .unwrap_or_else(|_| panic!("Failed to read code file: {:?}", code_path));
+
+    (Grammar::parse(&grammar_def), input)
+}
 
-    let parser = MLParser::new(&grammar, &input);
-    match parser.parse(Rule::Program) {
-        Ok(program_node) => {
-            let mut ctx = Context::new();
+fn run(grammar_path: &Path, code_path: &Path, limits: &LimitArgs) {
+    let (grammar, input) = read_sources(grammar_path, code_path);
 
-            let result = program_node.run(&mut ctx);
-            if let Err(e) = result {
-                println!("Runtime Error: {}", e);
+    let mut interpreter = Interpreter::new(grammar);
+    if let Some(max_depth) = limits.max_depth {
+        interpreter.context().max_depth = max_depth;
+    }
+    interpreter.set_limits(Limits {
+        max_steps: limits.max_steps,
+        timeout: limits.timeout_ms.map(Duration::from_millis),
+        max_memory: limits.max_memory,
+        max_output: limits.max_output,
+    });
+    match interpreter.eval(&input) {
+        Ok(_) => {}
+        Err(InterpreterError::Parse(e)) => println!("Parsing Error: {}", e),
+        Err(e) => println!("Runtime Error: {}", e),
+    }
+}
+
+fn parse(grammar_path: &Path, code_path: &Path, trace: bool, format: TraceFormat) {
+    let (grammar, input) = read_sources(grammar_path, code_path);
+
+    let mut parser = MLParser::new(&grammar, &input);
+    if trace {
+        parser = parser.with_trace();
+    }
+    let result = parser.parse(Rule::Program);
+
+    if let Some(trace) = parser.trace() {
+        match format {
+            TraceFormat::Tree => {
+                print!("{}", trace.render_tree());
+                println!();
+                print!("{}", trace.summary());
             }
+            TraceFormat::Json => println!("{}", trace.to_json()),
         }
-        Err(e) => {
-            println!("Parsing Error: {}", e);
-        }
+    }
+
+    match result {
+        Ok(_) => eprintln!("Parsed successfully"),
+        Err(e) => eprintln!("Parsing Error: {}", e),
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Some(Command::Parse {
+            grammar_path,
+            code_path,
+            trace,
+            format,
+        }) => parse(&grammar_path, &code_path, trace, format),
+        None => run(
+            cli.grammar_path.as_deref().unwrap(),
+            cli.code_path.as_deref().unwrap(),
+            &cli.limits,
+        ),
     }
 }
@@ -1,23 +1,70 @@
+use num_bigint::BigInt;
+use num_traits::Zero;
+use rust_decimal::Decimal;
 use std::collections::HashMap;
 
-use std::cell::RefCell;
+use std::cell::{Ref, RefCell, RefMut};
 use std::rc::Rc;
 
-use crate::error::RuntimeError;
+use crate::budget::{Budget, Limits};
+use crate::environment::{Env, Environment};
+use crate::error::{ErrorKind, RuntimeError, Span};
+use crate::functions::registry::{BuiltIn, BuiltInRegistry};
+use crate::generator::Generator;
 use crate::grammar::Rule;
+use crate::iteration::Iter;
+use crate::output::{Output, SharedOutput, StdOutput, Stream};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
-    Int(i32),
+    Int(i64),
+    /// An integer outside the range of `Int`; arithmetic produces one only
+    /// when the result does not fit in an `Int`.
+    BigInt(BigInt),
     Float(f64),
+    Decimal(Decimal),
     String(Rc<RefCell<String>>),
     Bool(bool),
     List(Rc<RefCell<Vec<Value>>>),
     Map(Rc<RefCell<HashMap<String, Value>>>),
     Object(Rc<RefCell<Object>>),
+    Function(Rc<Callable>),
+    /// A lazy sequence, such as the result of calling a generator. Shared, so
+    /// every reference sees the items already taken from it.
+    Iterator(Rc<RefCell<Iter>>),
     Void,
 }
 
+impl Value {
+    /// Name of the value's type, as used in error messages.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Int(_) => "Int",
+            Value::BigInt(_) => "BigInt",
+            Value::Float(_) => "Float",
+            Value::Decimal(_) => "Decimal",
+            Value::String(_) => "String",
+            Value::Bool(_) => "Bool",
+            Value::List(_) => "List",
+            Value::Map(_) => "Map",
+            Value::Object(_) => "Object",
+            Value::Function(_) => "Function",
+            Value::Iterator(_) => "Iterator",
+            Value::Void => "Void",
+        }
+    }
+
+    /// Truthiness used by conditions: `true` and non-zero integers.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Bool(b) => *b,
+            Value::Int(i) => *i != 0,
+            Value::BigInt(i) => !i.is_zero(),
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Object {
     pub class_name: String,
@@ -27,26 +74,169 @@ pub struct Object {
 #[derive(Clone)]
 pub struct Class {
     pub name: String,
+    /// The class this one extends.
+    pub parent: Option<String>,
+    /// Fields declared by this class, not including inherited ones.
     pub fields: Vec<String>,
+    /// Initial values of fields, evaluated in `env` for each new object.
+    pub defaults: HashMap<String, Rc<dyn Node>>,
     pub methods: HashMap<String, Function>,
+    /// The method `new` runs, if the class declares one.
+    pub initializer: Option<String>,
+    /// Methods called on the class itself, e.g. `Point.origin()`.
+    pub static_methods: HashMap<String, Function>,
+    /// Fields of the class itself, shared by every copy of the class.
+    pub static_fields: Rc<RefCell<HashMap<String, Value>>>,
+    /// Environment the class was defined in.
+    pub env: Env,
 }
 
+/// Hidden variable holding the class that defines the running method, where
+/// `super` starts looking. It is not a valid identifier, so scripts cannot
+/// name it.
+pub const METHOD_CLASS: &str = "<class>";
+
 #[derive(Clone)]
 pub struct Function {
     pub params: Vec<String>,
     pub body: Rc<dyn Node>,
+    /// Environment the function was defined in; calls run in a child of it.
+    pub closure: Option<Env>,
+    /// Whether the body contains `yield`; calling a generator returns an
+    /// iterator over the values it yields instead of running the body.
+    pub generator: bool,
 }
 
-pub type BuiltInFunction = fn(Vec<Value>) -> Result<Value, RuntimeError>;
+/// Anything that can be called: a named function or lambda, a built-in, or a
+/// method bound to the object it was read from.
+#[derive(Clone)]
+pub enum Callable {
+    User {
+        /// `None` for lambdas.
+        name: Option<String>,
+        function: Function,
+    },
+    BuiltIn(BuiltIn),
+    BoundMethod {
+        object: Rc<RefCell<Object>>,
+        /// The class defining the method, which may be an ancestor of the
+        /// object's class.
+        class: String,
+        name: String,
+        function: Function,
+    },
+}
 
-pub struct Context {
-    // For now, context can be empty or hold variables later
-    pub variables: HashMap<String, Value>,
+impl Callable {
+    pub fn name(&self) -> &str {
+        match self {
+            Callable::User { name, .. } => name.as_deref().unwrap_or("<lambda>"),
+            Callable::BuiltIn(builtin) => &builtin.name,
+            Callable::BoundMethod { name, .. } => name,
+        }
+    }
+}
+
+impl std::fmt::Display for Callable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Callable::User {
+                name: Some(name), ..
+            } => write!(f, "<function {}>", name),
+            Callable::User { name: None, .. } => write!(f, "<lambda>"),
+            Callable::BuiltIn(builtin) => write!(f, "<built-in function {}>", builtin.name),
+            Callable::BoundMethod { object, name, .. } => {
+                write!(f, "<bound method {}.{}>", object.borrow().class_name, name)
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Callable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// Two function values are equal when they would run the same code in the
+/// same environment (and, for bound methods, on the same object).
+impl PartialEq for Callable {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Callable::User { function: a, .. }, Callable::User { function: b, .. }) => a.same(b),
+            (Callable::BuiltIn(a), Callable::BuiltIn(b)) => a.name == b.name,
+            (
+                Callable::BoundMethod {
+                    object: a,
+                    function: fa,
+                    ..
+                },
+                Callable::BoundMethod {
+                    object: b,
+                    function: fb,
+                    ..
+                },
+            ) => Rc::ptr_eq(a, b) && fa.same(fb),
+            _ => false,
+        }
+    }
+}
+
+impl Function {
+    fn same(&self, other: &Function) -> bool {
+        let same_closure = match (&self.closure, &other.closure) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        Rc::ptr_eq(&self.body, &other.body) && same_closure
+    }
+}
+
+/// Non-local control flow raised by a statement and still unwinding.
+///
+/// Statement containers (`Program`, `Block`, loops) stop executing as soon as
+/// a signal is pending; the construct that owns it takes it out of the context.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlFlow {
+    Return(Value),
+    /// `break`, optionally targeting the loop with the given label.
+    Break(Option<String>),
+    /// `continue`, optionally targeting the loop with the given label.
+    Continue(Option<String>),
+}
+
+/// Functions, classes and built-ins. A context shares them with the contexts
+/// of the calls it makes, so a definition is visible everywhere once it has
+/// run, and calls do not copy them.
+#[derive(Clone, Default)]
+pub struct Globals {
     pub functions: HashMap<String, Function>,
-    pub classes: HashMap<String, Class>,
-    pub builtins: HashMap<String, BuiltInFunction>,
+    pub classes: HashMap<String, Rc<Class>>,
+    pub builtins: BuiltInRegistry,
 }
 
+pub struct Context {
+    pub env: Env,
+    pub globals: Rc<RefCell<Globals>>,
+    pub control_flow: Option<ControlFlow>,
+    pub output: SharedOutput,
+    /// Number of user function calls currently running.
+    pub depth: usize,
+    /// Calls nested deeper than this fail instead of overflowing the stack.
+    pub max_depth: usize,
+    /// Resource use so far, shared with the contexts of called functions.
+    pub budget: Rc<RefCell<Budget>>,
+}
+
+/// Default `Context::max_depth`.
+pub const DEFAULT_MAX_DEPTH: usize = 1000;
+
+/// Remaining native stack below which a call switches to a new segment.
+pub(crate) const STACK_RED_ZONE: usize = 128 * 1024;
+/// Size of each newly allocated stack segment.
+pub(crate) const STACK_GROWTH: usize = 2 * 1024 * 1024;
+
 impl Default for Context {
     fn default() -> Self {
         Self::new()
@@ -55,58 +245,286 @@ impl Default for Context {
 
 impl Context {
     pub fn new() -> Self {
-        let mut ctx = Self {
-            variables: HashMap::new(),
-            functions: HashMap::new(),
-            classes: HashMap::new(),
-            builtins: HashMap::new(),
+        Self::with_builtins(BuiltInRegistry::standard())
+    }
+
+    /// A context whose scripts see exactly the given built-ins.
+    pub fn with_builtins(builtins: BuiltInRegistry) -> Self {
+        Self {
+            env: Environment::new_global(),
+            globals: Rc::new(RefCell::new(Globals {
+                functions: HashMap::new(),
+                classes: HashMap::new(),
+                builtins,
+            })),
+            control_flow: None,
+            output: Rc::new(RefCell::new(StdOutput::default())),
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            budget: Rc::new(RefCell::new(Budget::default())),
+        }
+    }
+
+    /// Sends everything the script prints to `output`.
+    pub fn set_output(&mut self, output: impl Output + 'static) {
+        self.output = Rc::new(RefCell::new(output));
+    }
+
+    /// Restricts everything run from now on to `limits`, resetting the
+    /// resources used so far.
+    pub fn set_limits(&mut self, limits: Limits) {
+        *self.budget.borrow_mut() = Budget::new(limits);
+    }
+
+    /// Counts a loop iteration or call against the budget.
+    pub fn step(&mut self) -> Result<(), RuntimeError> {
+        self.budget.borrow_mut().step()
+    }
+
+    /// Counts `size` elements or bytes of new collection data against the
+    /// budget.
+    pub fn allocate(&mut self, size: usize) -> Result<(), RuntimeError> {
+        self.budget.borrow_mut().allocate(size)
+    }
+
+    pub fn write_output(&mut self, stream: Stream, text: &str) -> Result<(), RuntimeError> {
+        self.budget.borrow_mut().write(text.len())?;
+        self.output.borrow_mut().write(stream, text).map_err(|e| {
+            RuntimeError::new(ErrorKind::IOError, format!("Failed to write output: {}", e))
+        })
+    }
+
+    pub fn flush_output(&mut self) -> Result<(), RuntimeError> {
+        self.output.borrow_mut().flush().map_err(|e| {
+            RuntimeError::new(ErrorKind::IOError, format!("Failed to flush output: {}", e))
+        })
+    }
+
+    /// The built-ins scripts can call.
+    pub fn builtins(&self) -> Ref<'_, BuiltInRegistry> {
+        Ref::map(self.globals.borrow(), |globals| &globals.builtins)
+    }
+
+    /// The built-ins, for registering or disabling some.
+    pub fn builtins_mut(&self) -> RefMut<'_, BuiltInRegistry> {
+        RefMut::map(self.globals.borrow_mut(), |globals| &mut globals.builtins)
+    }
+
+    /// Defines or replaces a user function.
+    pub fn define_function(&mut self, name: &str, function: Function) {
+        self.globals
+            .borrow_mut()
+            .functions
+            .insert(name.to_string(), function);
+    }
+
+    /// Defines or replaces a class.
+    pub fn define_class(&mut self, class: Class) {
+        self.globals
+            .borrow_mut()
+            .classes
+            .insert(class.name.clone(), Rc::new(class));
+    }
+
+    pub fn class(&self, name: &str) -> Option<Rc<Class>> {
+        self.globals.borrow().classes.get(name).cloned()
+    }
+
+    /// Resolves a function by name: function values in the lexical scope
+    /// (including nested definitions) first, then built-ins, then top-level
+    /// functions.
+    pub fn lookup_function(&self, name: &str) -> Option<Callable> {
+        if let Some(Value::Function(callee)) = self.get_variable(name) {
+            return Some((*callee).clone());
+        }
+        let globals = self.globals.borrow();
+        if let Some(builtin) = globals.builtins.get(name) {
+            return Some(Callable::BuiltIn(builtin.clone()));
+        }
+        globals.functions.get(name).map(|function| Callable::User {
+            name: Some(name.to_string()),
+            function: function.clone(),
+        })
+    }
+
+    /// Calls `callee` with already evaluated arguments. User functions and
+    /// methods run in a fresh context whose scope is chained to their closure.
+    pub fn call_function(
+        &mut self,
+        callee: &Callable,
+        args: Vec<Value>,
+    ) -> Result<Value, RuntimeError> {
+        let (function, this, kind) = match callee {
+            Callable::BuiltIn(builtin) => return builtin.call(self, &args),
+            Callable::User { function, .. } => (function, None, "Function"),
+            Callable::BoundMethod {
+                object,
+                class,
+                function,
+                ..
+            } => (function, Some((object.clone(), class)), "Method"),
+        };
+
+        if args.len() != function.params.len() {
+            return Err(RuntimeError::new(
+                ErrorKind::ArityError,
+                format!(
+                    "{} '{}' expects {} arguments, got {}",
+                    kind,
+                    callee.name(),
+                    function.params.len(),
+                    args.len()
+                ),
+            ));
+        }
+
+        self.step()?;
+        if self.depth >= self.max_depth {
+            return Err(RuntimeError::new(
+                ErrorKind::RecursionLimit,
+                "maximum recursion depth exceeded",
+            ));
+        }
+
+        let mut new_ctx = Context {
+            env: Environment::new_function(function.closure.clone()),
+            globals: self.globals.clone(),
+            control_flow: None,
+            output: self.output.clone(),
+            depth: self.depth + 1,
+            max_depth: self.max_depth,
+            budget: self.budget.clone(),
         };
-        // Register built-ins
-        ctx.builtins
-            .insert("print".to_string(), crate::functions::print::print_fn);
-        ctx.builtins
-            .insert("get".to_string(), crate::functions::get::get_fn);
-        ctx.builtins
-            .insert("set".to_string(), crate::functions::set::set_fn);
-        ctx.builtins
-            .insert("keys".to_string(), crate::functions::keys::keys_fn);
-        ctx.builtins
-            .insert("append".to_string(), crate::functions::append::append_fn);
-
-        ctx.builtins
-            .insert("len".to_string(), crate::functions::len::len_fn);
-        ctx.builtins
-            .insert("abs".to_string(), crate::functions::abs::abs_fn);
-        ctx.builtins
-            .insert("sum".to_string(), crate::functions::sum::sum_fn);
-        ctx.builtins
-            .insert("slice".to_string(), crate::functions::slice::slice_fn);
-        ctx.builtins
-            .insert("split".to_string(), crate::functions::split::split_fn);
-        ctx.builtins
-            .insert("join".to_string(), crate::functions::join::join_fn);
-        ctx.builtins
-            .insert("sort".to_string(), crate::functions::sort::sort_fn);
-        ctx.builtins
-            .insert("reverse".to_string(), crate::functions::reverse::reverse_fn);
-        ctx.builtins
-            .insert("range".to_string(), crate::functions::range::range_fn);
-        ctx.builtins.insert(
-            "read_file".to_string(),
-            crate::functions::read_file::read_file_fn,
-        );
-        ctx.builtins
-            .insert("ord".to_string(), crate::functions::ord::ord_fn);
-        ctx.builtins
-            .insert("chr".to_string(), crate::functions::chr::chr_fn);
-        ctx.builtins
-            .insert("to_int".to_string(), crate::functions::to_int::to_int_fn);
-        ctx.builtins.insert(
-            "to_float".to_string(),
-            crate::functions::to_float::to_float_fn,
-        );
-
-        ctx
+
+        for (param, value) in function.params.iter().zip(args) {
+            new_ctx.define_variable(param, value);
+        }
+        if let Some((object, class)) = this {
+            new_ctx.define_variable("this", Value::Object(object));
+            new_ctx.define_variable(
+                METHOD_CLASS,
+                Value::String(Rc::new(RefCell::new(class.clone()))),
+            );
+        }
+
+        if function.generator {
+            let generator =
+                Generator::new(callee.name().to_string(), function.body.clone(), new_ctx);
+            return Ok(Value::Iterator(Rc::new(RefCell::new(Iter::generator(
+                generator,
+            )))));
+        }
+
+        // Each script call nests several `run` frames, so grow the native
+        // stack on demand rather than relying on the thread's fixed size.
+        let val = stacker::maybe_grow(STACK_RED_ZONE, STACK_GROWTH, || {
+            function.body.run(&mut new_ctx)
+        })?;
+        Ok(new_ctx.take_return().unwrap_or(val))
+    }
+
+    /// Looks `method` up on `class_name` and then on its ancestors. Returns
+    /// the name of the class that defines it along with the method.
+    pub fn find_method(&self, class_name: &str, method: &str) -> Option<(String, Function)> {
+        self.class_chain(class_name).into_iter().find_map(|class| {
+            let function = class.methods.get(method)?;
+            Some((class.name.clone(), function.clone()))
+        })
+    }
+
+    /// Looks up a static method on `class_name` and its ancestors.
+    pub fn find_static_method(&self, class_name: &str, method: &str) -> Option<Function> {
+        self.class_chain(class_name)
+            .into_iter()
+            .find_map(|class| class.static_methods.get(method).cloned())
+    }
+
+    /// The static fields of the nearest class in the chain of `class_name`
+    /// that declares `field`.
+    pub fn find_static_field(
+        &self,
+        class_name: &str,
+        field: &str,
+    ) -> Option<Rc<RefCell<HashMap<String, Value>>>> {
+        self.class_chain(class_name)
+            .into_iter()
+            .find(|class| class.static_fields.borrow().contains_key(field))
+            .map(|class| class.static_fields.clone())
+    }
+
+    /// Whether an object of class `class_name` is an instance of `ancestor`,
+    /// directly or through inheritance.
+    pub fn is_instance(&self, class_name: &str, ancestor: &str) -> bool {
+        self.class_chain(class_name)
+            .iter()
+            .any(|class| class.name == ancestor)
+    }
+
+    /// `class_name` followed by its ancestors, nearest first. Empty if the
+    /// class is not defined.
+    pub fn class_chain(&self, class_name: &str) -> Vec<Rc<Class>> {
+        let globals = self.globals.borrow();
+        let mut chain: Vec<Rc<Class>> = Vec::new();
+        let mut next = globals.classes.get(class_name);
+        while let Some(class) = next {
+            chain.push(class.clone());
+            next = class.parent.as_ref().and_then(|p| globals.classes.get(p));
+        }
+        chain
+    }
+
+    pub fn get_variable(&self, name: &str) -> Option<Value> {
+        Environment::get(&self.env, name)
+    }
+
+    /// Binds `name` in the innermost scope, shadowing outer bindings.
+    pub fn define_variable(&mut self, name: &str, value: Value) {
+        self.env.borrow_mut().define(name, value);
+    }
+
+    /// Assigns following the scoping rules of `Environment::assign`.
+    pub fn assign_variable(&mut self, name: &str, value: Value) -> Result<(), RuntimeError> {
+        Environment::assign(&self.env, name, value)
+            .map_err(|message| RuntimeError::new(ErrorKind::NameError, message))
+    }
+
+    /// Runs `f` inside a fresh block scope.
+    pub fn with_scope<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let outer = self.env.clone();
+        self.env = Environment::new_block(&outer);
+        let result = f(self);
+        self.env = outer;
+        result
+    }
+
+    /// Called by a loop after each run of its body. Consumes a `break` or
+    /// `continue` addressed to this loop and reports whether to keep iterating.
+    /// Any other pending signal stops the loop so it can keep unwinding.
+    pub fn loop_should_continue(&mut self, label: Option<&str>) -> bool {
+        let targets_loop = |target: &Option<String>| target.is_none() || target.as_deref() == label;
+        match &self.control_flow {
+            None => true,
+            Some(ControlFlow::Continue(target)) if targets_loop(target) => {
+                self.control_flow = None;
+                true
+            }
+            Some(ControlFlow::Break(target)) if targets_loop(target) => {
+                self.control_flow = None;
+                false
+            }
+            Some(_) => false,
+        }
+    }
+
+    /// Takes the value of a pending `return`, leaving other signals in place.
+    pub fn take_return(&mut self) -> Option<Value> {
+        match self.control_flow.take() {
+            Some(ControlFlow::Return(value)) => Some(value),
+            other => {
+                self.control_flow = other;
+                None
+            }
+        }
     }
 }
 
@@ -141,6 +559,11 @@ pub trait Node: AsAny {
         None
     }
 
+    /// Direct child nodes, used by static checks that walk the tree.
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![]
+    }
+
     // Static method to construct node from children
     fn from_children(rule: Rule, children: ParsedChildren) -> Box<dyn Node>
     where
@@ -180,6 +603,47 @@ impl Node for RuleNode {
     }
 }
 
+/// Records where a statement or expression came from, so errors raised
+/// while running it can point at the source. The innermost span wins.
+#[derive(Clone)]
+pub struct SpannedNode {
+    pub span: Span,
+    pub inner: Box<dyn Node>,
+}
+
+impl Node for SpannedNode {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        self.inner.run(ctx).map_err(|mut err| {
+            err.span.get_or_insert(self.span);
+            err
+        })
+    }
+
+    fn text(&self) -> Option<String> {
+        self.inner.text()
+    }
+
+    fn params(&self) -> Option<Vec<String>> {
+        self.inner.params()
+    }
+
+    fn rule(&self) -> Option<Rule> {
+        self.inner.rule()
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.inner.as_ref()]
+    }
+
+    fn from_children(_rule: Rule, _children: ParsedChildren) -> Box<dyn Node> {
+        panic!("SpannedNode should not be created from children directly");
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+}
+
 pub trait AsAny: std::any::Any {
     fn as_any(&self) -> &dyn std::any::Any;
 }
@@ -199,11 +663,16 @@ impl Clone for Box<dyn Node> {
 pub struct ParsedChildren {
     pub children: Vec<(Option<String>, Box<dyn Node>)>,
     pub line: usize,
+    pub column: usize,
 }
 
 impl ParsedChildren {
-    pub fn new(children: Vec<(Option<String>, Box<dyn Node>)>, line: usize) -> Self {
-        Self { children, line }
+    pub fn new(children: Vec<(Option<String>, Box<dyn Node>)>, line: usize, column: usize) -> Self {
+        Self {
+            children,
+            line,
+            column,
+        }
     }
 
     pub fn take_child(&mut self, name: &str) -> Option<Box<dyn Node>> {
@@ -222,6 +691,15 @@ impl ParsedChildren {
         None
     }
 
+    /// Like `take_child`, but without falling back to an unnamed child.
+    pub fn take_named(&mut self, name: &str) -> Option<Box<dyn Node>> {
+        let pos = self
+            .children
+            .iter()
+            .position(|(n, _)| n.as_deref() == Some(name))?;
+        Some(self.children.remove(pos).1)
+    }
+
     pub fn remaining(self) -> Vec<(Option<String>, Box<dyn Node>)> {
         self.children
     }
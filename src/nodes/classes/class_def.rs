@@ -1,61 +1,157 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
 use crate::node::{Class, Context, Function, Node, Value};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
 
 #[derive(Clone)]
 pub struct ClassDef {
     pub name: String,
+    /// The class named in the `extends` clause.
+    pub parent: Option<String>,
     pub fields: Vec<String>,
+    pub defaults: HashMap<String, Rc<dyn Node>>,
     pub methods: HashMap<String, Function>,
+    pub initializer: Option<String>,
+    pub static_methods: HashMap<String, Function>,
+    /// Static fields in declaration order, with their initial values.
+    pub static_fields: Vec<(String, Option<Rc<dyn Node>>)>,
 }
 
 impl Node for ClassDef {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        if let Some(parent) = &self.parent {
+            let chain = ctx.class_chain(parent);
+            if chain.is_empty() {
+                return Err(RuntimeError::new(
+                    ErrorKind::NameError,
+                    format!("Undefined class '{}'", parent),
+                ));
+            }
+            // Redefining an ancestor must not make the chain circular.
+            if chain.iter().any(|class| class.name == self.name) {
+                return Err(RuntimeError::new(
+                    ErrorKind::TypeError,
+                    format!(
+                        "Class '{}' cannot extend its own subclass '{}'",
+                        self.name, parent
+                    ),
+                ));
+            }
+        }
+
+        let mut methods = self.methods.clone();
+        let mut static_methods = self.static_methods.clone();
+        for method in methods.values_mut().chain(static_methods.values_mut()) {
+            method.closure = Some(ctx.env.clone());
+        }
+
+        // Static fields start as void, so their initial values, evaluated
+        // once the class exists, may refer to the class and earlier fields.
+        let static_fields: HashMap<String, Value> = self
+            .static_fields
+            .iter()
+            .map(|(name, _)| (name.clone(), Value::Void))
+            .collect();
+        let static_fields = Rc::new(RefCell::new(static_fields));
         let class = Class {
             name: self.name.clone(),
+            parent: self.parent.clone(),
             fields: self.fields.clone(),
-            methods: self.methods.clone(),
+            defaults: self.defaults.clone(),
+            methods,
+            initializer: self.initializer.clone(),
+            static_methods,
+            static_fields: static_fields.clone(),
+            env: ctx.env.clone(),
         };
-        ctx.classes.insert(self.name.clone(), class);
+        ctx.define_class(class);
+
+        for (name, default) in &self.static_fields {
+            if let Some(default) = default {
+                let value = default.run(ctx)?;
+                static_fields.borrow_mut().insert(name.clone(), value);
+            }
+        }
         Ok(Value::Void)
     }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        // ClassDef = "class" name:Identifier "extends" parent:Identifier "{" ClassMember* "}"
         let name_node = children.take_child("name").unwrap();
         let name = name_node.text().unwrap_or_default();
+        let parent = children
+            .take_named("parent")
+            .and_then(|parent| parent.text());
 
         let mut fields = Vec::new();
+        let mut defaults = HashMap::new();
         let mut methods = HashMap::new();
+        let mut initializer = None;
+        let mut static_methods = HashMap::new();
+        let mut static_fields = Vec::new();
 
         for (_child_name, child) in children.remaining() {
             if let Some(field_def) = child
                 .as_any()
                 .downcast_ref::<crate::nodes::classes::FieldDef>()
             {
+                if field_def.is_static {
+                    let default = field_def.default.as_ref().map(|d| Rc::from(d.box_clone()));
+                    static_fields.push((field_def.name.clone(), default));
+                    continue;
+                }
                 fields.push(field_def.name.clone());
+                if let Some(default) = &field_def.default {
+                    defaults.insert(field_def.name.clone(), Rc::from(default.box_clone()));
+                }
             } else if let Some(method_def) = child
                 .as_any()
                 .downcast_ref::<crate::nodes::classes::MethodDef>()
             {
-                methods.insert(
-                    method_def.name.clone(),
-                    Function {
-                        params: method_def.params.clone(),
-                        body: method_def.body.clone(),
-                    },
-                );
+                if method_def.initializer {
+                    initializer = Some(method_def.name.clone());
+                }
+                let function = Function {
+                    params: method_def.params.clone(),
+                    body: method_def.body.clone(),
+                    closure: None,
+                    generator: crate::generator::contains_yield(method_def.body.as_ref()),
+                };
+                if method_def.is_static {
+                    static_methods.insert(method_def.name.clone(), function);
+                } else {
+                    methods.insert(method_def.name.clone(), function);
+                }
             }
         }
 
         Box::new(ClassDef {
             name,
+            parent,
             fields,
+            defaults,
             methods,
+            initializer,
+            static_methods,
+            static_fields,
         })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = self
+            .methods
+            .values()
+            .chain(self.static_methods.values())
+            .map(|m| m.body.as_ref())
+            .collect();
+        children.extend(self.defaults.values().map(|d| d.as_ref()));
+        children.extend(self.static_fields.iter().filter_map(|(_, d)| d.as_deref()));
+        children
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(self.clone())
     }
@@ -6,6 +6,10 @@ use crate::node::{Context, Node, Value};
 #[derive(Clone)]
 pub struct FieldDef {
     pub name: String,
+    /// Expression giving the field its initial value.
+    pub default: Option<Box<dyn Node>>,
+    /// Whether the field belongs to the class rather than its objects.
+    pub is_static: bool,
 }
 
 impl Node for FieldDef {
@@ -14,9 +18,21 @@ impl Node for FieldDef {
     }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        // FieldDef = name:Identifier | name:Identifier "=" default:Expr
+        // A `static` marker such as `static:[static\b]` makes it a class field.
+        let is_static = children.take_named("static").is_some();
         let name_node = children.take_child("name").unwrap();
         let name = name_node.text().unwrap_or_default();
-        Box::new(FieldDef { name })
+        let default = children.take_named("default");
+        Box::new(FieldDef {
+            name,
+            default,
+            is_static,
+        })
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.default.as_deref().into_iter().collect()
     }
 
     fn box_clone(&self) -> Box<dyn Node> {
@@ -1,7 +1,26 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
-use crate::node::{Context, Node, Value};
+use crate::node::{Callable, Context, Node, SpannedNode, Value};
+use crate::nodes::classes::PostfixNode;
+use crate::nodes::expressions::Variable;
+use std::rc::Rc;
+
+/// The class `node` refers to when it is a bare name that is not a variable
+/// but a class, as `Point` in `Point.origin()`.
+pub fn class_reference(ctx: &Context, node: &dyn Node) -> Option<String> {
+    let any = node.as_any();
+    if let Some(spanned) = any.downcast_ref::<SpannedNode>() {
+        class_reference(ctx, spanned.inner.as_ref())
+    } else if let Some(postfix) = any.downcast_ref::<PostfixNode>() {
+        class_reference(ctx, postfix.root.as_ref())
+    } else if let Some(variable) = any.downcast_ref::<Variable>() {
+        (ctx.get_variable(&variable.name).is_none() && ctx.class(&variable.name).is_some())
+            .then(|| variable.name.clone())
+    } else {
+        None
+    }
+}
 
 #[derive(Clone)]
 pub struct MemberAccess {
@@ -11,26 +30,53 @@ pub struct MemberAccess {
 
 impl Node for MemberAccess {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        if let Some(class_name) = class_reference(ctx, self.object.as_ref()) {
+            if let Some(fields) = ctx.find_static_field(&class_name, &self.member) {
+                return Ok(fields.borrow()[&self.member].clone());
+            }
+            if let Some(method) = ctx.find_static_method(&class_name, &self.member) {
+                return Ok(Value::Function(Rc::new(Callable::User {
+                    name: Some(self.member.clone()),
+                    function: method,
+                })));
+            }
+            return Err(RuntimeError::new(
+                ErrorKind::KeyError,
+                format!(
+                    "Class '{}' has no static member '{}'",
+                    class_name, self.member
+                ),
+            ));
+        }
+
         let obj_val = self.object.run(ctx)?;
 
         if let Value::Object(obj_rc) = obj_val {
             let obj = obj_rc.borrow();
             if let Some(val) = obj.fields.get(&self.member) {
-                return Ok(val.clone());
+                Ok(val.clone())
+            } else if let Some((class, method)) = ctx.find_method(&obj.class_name, &self.member) {
+                // Reading a method without calling it binds it to the object.
+                Ok(Value::Function(Rc::new(Callable::BoundMethod {
+                    object: obj_rc.clone(),
+                    class,
+                    name: self.member.clone(),
+                    function: method,
+                })))
             } else {
-                return Err(RuntimeError {
-                    message: format!(
+                Err(RuntimeError::new(
+                    ErrorKind::KeyError,
+                    format!(
                         "Object of class '{}' has no field '{}'",
                         obj.class_name, self.member
                     ),
-                    stack_trace: vec![],
-                });
+                ))
             }
         } else {
-            return Err(RuntimeError {
-                message: format!("Cannot access member '{}' on non-object", self.member),
-                stack_trace: vec![],
-            });
+            Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                format!("Cannot access member '{}' on non-object", self.member),
+            ))
         }
     }
 
@@ -42,6 +88,10 @@ impl Node for MemberAccess {
         Box::new(MemberAccess { object, member })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.object.as_ref()]
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(self.clone())
     }
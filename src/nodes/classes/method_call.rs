@@ -1,17 +1,24 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
-use crate::node::{Context, Node, Value};
+use crate::node::{Callable, Context, Node, Value};
+use crate::nodes::classes::member_access::class_reference;
 
 #[derive(Clone)]
 pub struct MethodCall {
     pub object: Box<dyn Node>,
     pub method_name: String,
     pub args: Vec<Box<dyn Node>>,
+    pub line: usize,
 }
 
 impl Node for MethodCall {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        // A static method, called on the class itself
+        if let Some(class_name) = class_reference(ctx, self.object.as_ref()) {
+            return self.call_static(ctx, &class_name);
+        }
+
         // 1. Evaluate object
         let obj_val = self.object.run(ctx)?;
 
@@ -19,26 +26,36 @@ impl Node for MethodCall {
             let obj = obj_rc.borrow();
 
             // 2. Look up class
-            let class = ctx
-                .classes
-                .get(&obj.class_name)
-                .ok_or_else(|| RuntimeError {
-                    message: format!("Class '{}' not found", obj.class_name),
-                    stack_trace: vec![],
-                })?;
+            if ctx.class(&obj.class_name).is_none() {
+                return Err(RuntimeError::new(
+                    ErrorKind::NameError,
+                    format!("Class '{}' not found", obj.class_name),
+                ));
+            }
 
-            // 3. Look up method
-            let method = class
-                .methods
-                .get(&self.method_name)
-                .ok_or_else(|| RuntimeError {
-                    message: format!(
+            // 3. Look up method up the class chain, falling back to a field
+            // holding a function
+            let callee = if let Some((class, method)) =
+                ctx.find_method(&obj.class_name, &self.method_name)
+            {
+                Callable::BoundMethod {
+                    object: obj_rc.clone(),
+                    class,
+                    name: self.method_name.clone(),
+                    function: method,
+                }
+            } else if let Some(Value::Function(func)) = obj.fields.get(&self.method_name) {
+                (**func).clone()
+            } else {
+                return Err(RuntimeError::new(
+                    ErrorKind::KeyError,
+                    format!(
                         "Method '{}' not found in class '{}'",
                         self.method_name, obj.class_name
                     ),
-                    stack_trace: vec![],
-                })?
-                .clone();
+                ));
+            };
+            drop(obj);
 
             // 4. Evaluate args
             let mut arg_values = Vec::new();
@@ -46,59 +63,21 @@ impl Node for MethodCall {
                 arg_values.push(arg.run(ctx)?);
             }
 
-            // 5. Check arg count
-            if arg_values.len() != method.params.len() {
-                return Err(RuntimeError {
-                    message: format!(
-                        "Method '{}' expects {} arguments, got {}",
-                        self.method_name,
-                        method.params.len(),
-                        arg_values.len()
-                    ),
-                    stack_trace: vec![],
-                });
-            }
-
-            // 6. Create new context
-            // We need to capture functions/classes/builtins from current context?
-            // Yes, methods should be able to call other functions/classes.
-            // But variables should be local + 'this'.
-            // Actually, `FunctionCall` creates a new context with global functions/builtins.
-            // Here we should do similar.
-
-            let mut new_ctx = Context::new();
-            new_ctx.functions = ctx.functions.clone(); // Should be reference or COW? For now clone is fine (expensive but correct)
-            new_ctx.classes = ctx.classes.clone();
-            new_ctx.builtins = ctx.builtins.clone();
-
-            // Bind params
-            for (i, param) in method.params.iter().enumerate() {
-                new_ctx
-                    .variables
-                    .insert(param.clone(), arg_values[i].clone());
-            }
-
-            // Bind 'this'
-            // We need to drop the borrow on obj_rc before inserting into new_ctx?
-            // obj_rc is Rc<RefCell<Object>>. Value::Object holds it.
-            // We borrowed it as `obj`.
-            // We need to clone obj_rc.
-            drop(obj); // Drop borrow
-            new_ctx
-                .variables
-                .insert("this".to_string(), Value::Object(obj_rc.clone()));
-
-            // 7. Run body
-            method.body.run(&mut new_ctx)
+            // 5. Run it with `this` bound for methods
+            ctx.call_function(&callee, arg_values).map_err(|mut err| {
+                err.push_frame(&self.method_name, self.line);
+                err
+            })
         } else {
-            return Err(RuntimeError {
-                message: format!("Cannot call method '{}' on non-object", self.method_name),
-                stack_trace: vec![],
-            });
+            Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                format!("Cannot call method '{}' on non-object", self.method_name),
+            ))
         }
     }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        let line = children.line;
         let object = children.take_child("object").unwrap();
         let method_node = children.take_child("method").unwrap();
         let method_name = method_node.text().unwrap_or_default();
@@ -112,10 +91,49 @@ impl Node for MethodCall {
             object,
             method_name,
             args,
+            line,
         })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        let mut children = vec![self.object.as_ref()];
+        children.extend(self.args.iter().map(|a| a.as_ref()));
+        children
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(self.clone())
     }
 }
+
+impl MethodCall {
+    fn call_static(&self, ctx: &mut Context, class_name: &str) -> Result<Value, RuntimeError> {
+        let callee = if let Some(method) = ctx.find_static_method(class_name, &self.method_name) {
+            Callable::User {
+                name: Some(self.method_name.clone()),
+                function: method,
+            }
+        } else if let Some(fields) = ctx.find_static_field(class_name, &self.method_name)
+            && let Value::Function(func) = &fields.borrow()[&self.method_name]
+        {
+            (**func).clone()
+        } else {
+            return Err(RuntimeError::new(
+                ErrorKind::KeyError,
+                format!(
+                    "Static method '{}' not found in class '{}'",
+                    self.method_name, class_name
+                ),
+            ));
+        };
+
+        let mut arg_values = Vec::new();
+        for arg in &self.args {
+            arg_values.push(arg.run(ctx)?);
+        }
+        ctx.call_function(&callee, arg_values).map_err(|mut err| {
+            err.push_frame(&self.method_name, self.line);
+            err
+        })
+    }
+}
@@ -9,6 +9,10 @@ pub struct MethodDef {
     pub name: String,
     pub params: Vec<String>,
     pub body: Rc<dyn Node>,
+    /// Whether this is the initializer `new` runs.
+    pub initializer: bool,
+    /// Whether the method is called on the class rather than its objects.
+    pub is_static: bool,
 }
 
 impl Node for MethodDef {
@@ -17,7 +21,13 @@ impl Node for MethodDef {
     }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
-        let name_node = children.take_child("name").unwrap();
+        // An `initializer` marker such as `initializer:[init]` names the
+        // method and makes it the one `new` runs.
+        let initializer = children.take_named("initializer");
+        let is_initializer = initializer.is_some();
+        // A `static` marker makes it a method of the class itself.
+        let is_static = children.take_named("static").is_some();
+        let name_node = initializer.or_else(|| children.take_child("name")).unwrap();
         let name = name_node.text().unwrap_or_default();
 
         let mut params = Vec::new();
@@ -32,9 +42,15 @@ impl Node for MethodDef {
             name,
             params,
             body: Rc::from(body),
+            initializer: is_initializer,
+            is_static,
         })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.body.as_ref()]
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(self.clone())
     }
@@ -7,6 +7,7 @@ pub mod new_expr;
 pub mod postfix;
 pub mod postfix_suffix;
 pub mod self_reference;
+pub mod super_call;
 
 pub use class_def::ClassDef;
 pub use field_def::FieldDef;
@@ -17,3 +18,4 @@ pub use new_expr::NewExpr;
 pub use postfix::PostfixNode;
 pub use postfix_suffix::PostfixSuffixNode;
 pub use self_reference::SelfReference;
+pub use super_call::SuperCall;
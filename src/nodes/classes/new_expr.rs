@@ -1,7 +1,8 @@
-use crate::error::RuntimeError;
+use crate::environment::Environment;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
-use crate::node::{Context, Node, Object, Value};
+use crate::node::{Callable, Class, Context, Node, Object, Value};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -10,48 +11,85 @@ use std::rc::Rc;
 pub struct NewExpr {
     pub class_name: String,
     pub args: Vec<Box<dyn Node>>,
+    pub line: usize,
 }
 
 impl Node for NewExpr {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
-        // 1. Look up class
-        let class = ctx
-            .classes
-            .get(&self.class_name)
-            .ok_or_else(|| RuntimeError {
-                message: format!("Undefined class '{}'", self.class_name),
-                stack_trace: vec![],
-            })?
-            .clone();
+        // 1. Look up the class and its ancestors, root first
+        let mut chain = ctx.class_chain(&self.class_name);
+        if chain.is_empty() {
+            return Err(RuntimeError::new(
+                ErrorKind::NameError,
+                format!("Undefined class '{}'", self.class_name),
+            ));
+        }
+        chain.reverse();
+        let fields: Vec<(&Class, &String)> = chain
+            .iter()
+            .flat_map(|class| {
+                class
+                    .fields
+                    .iter()
+                    .map(move |field| (class.as_ref(), field))
+            })
+            .collect();
+
+        // 2. Evaluate args
+        let mut arg_values = Vec::new();
+        for arg in &self.args {
+            arg_values.push(arg.run(ctx)?);
+        }
 
-        // 2. Check arg count
-        if self.args.len() != class.fields.len() {
-            return Err(RuntimeError {
-                message: format!(
-                    "Class '{}' expects {} arguments, got {}",
-                    self.class_name,
-                    class.fields.len(),
-                    self.args.len()
-                ),
-                stack_trace: vec![],
-            });
+        // 3. With an initializer, fields start at their defaults and the
+        // initializer gets the arguments
+        let initializer = chain.iter().rev().find_map(|class| {
+            let name = class.initializer.as_ref()?;
+            Some((
+                class.name.clone(),
+                name.clone(),
+                class.methods[name].clone(),
+            ))
+        });
+        let mut positional = Vec::new().into_iter();
+        if initializer.is_none() {
+            check_arg_count(&self.class_name, &fields, arg_values.len())?;
+            positional = std::mem::take(&mut arg_values).into_iter();
         }
 
-        // 3. Evaluate args and populate fields
-        let mut fields = HashMap::new();
-        for (i, arg_node) in self.args.iter().enumerate() {
-            let val = arg_node.run(ctx)?;
-            fields.insert(class.fields[i].clone(), val);
+        // 4. Populate fields, positionally from the args without an initializer
+        let mut values = HashMap::new();
+        for (class, field) in &fields {
+            let value = match positional.next() {
+                Some(value) => value,
+                None => field_default(ctx, class, field)?,
+            };
+            values.insert((*field).clone(), value);
         }
 
-        // 4. Create object
-        Ok(Value::Object(Rc::new(RefCell::new(Object {
+        // 5. Create object
+        let object = Rc::new(RefCell::new(Object {
             class_name: self.class_name.clone(),
-            fields,
-        }))))
+            fields: values,
+        }));
+
+        if let Some((class, name, function)) = initializer {
+            let callee = Callable::BoundMethod {
+                object: object.clone(),
+                class,
+                name: name.clone(),
+                function,
+            };
+            ctx.call_function(&callee, arg_values).map_err(|mut err| {
+                err.push_frame(&name, self.line);
+                err
+            })?;
+        }
+        Ok(Value::Object(object))
     }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        let line = children.line;
         let name_node = children.take_child("class_name").unwrap();
         let class_name = name_node.text().unwrap_or_default();
 
@@ -60,10 +98,59 @@ impl Node for NewExpr {
             args = arg_list.into_args();
         }
 
-        Box::new(NewExpr { class_name, args })
+        Box::new(NewExpr {
+            class_name,
+            args,
+            line,
+        })
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.args.iter().map(|a| a.as_ref()).collect()
     }
 
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(self.clone())
     }
 }
+
+/// Without an initializer, arguments fill the fields in order; trailing
+/// fields with defaults may be left out.
+fn check_arg_count(
+    class_name: &str,
+    fields: &[(&Class, &String)],
+    count: usize,
+) -> Result<(), RuntimeError> {
+    let max = fields.len();
+    let min = fields
+        .iter()
+        .rposition(|(class, field)| !class.defaults.contains_key(*field))
+        .map_or(0, |last_required| last_required + 1);
+    if (min..=max).contains(&count) {
+        return Ok(());
+    }
+    let expected = if min == max {
+        max.to_string()
+    } else {
+        format!("{} to {}", min, max)
+    };
+    Err(RuntimeError::new(
+        ErrorKind::ArityError,
+        format!(
+            "Class '{}' expects {} arguments, got {}",
+            class_name, expected, count
+        ),
+    ))
+}
+
+/// Evaluates the default of `field` in the scope the class was defined in,
+/// or gives `Void` if it has none.
+fn field_default(ctx: &mut Context, class: &Class, field: &str) -> Result<Value, RuntimeError> {
+    let Some(default) = class.defaults.get(field) else {
+        return Ok(Value::Void);
+    };
+    let outer = std::mem::replace(&mut ctx.env, Environment::new_block(&class.env));
+    let result = default.run(ctx);
+    ctx.env = outer;
+    result
+}
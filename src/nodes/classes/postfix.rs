@@ -3,6 +3,8 @@ use crate::grammar::Rule;
 use crate::node::ParsedChildren;
 use crate::node::{Context, Node, Value};
 use crate::nodes::classes::{MemberAccess, MethodCall};
+use crate::nodes::expressions::{Index, Slice};
+use crate::nodes::functions::CallExpr;
 
 #[derive(Clone)]
 pub struct PostfixNode {
@@ -35,11 +37,36 @@ impl Node for PostfixNode {
                             member: member.clone(),
                         });
                     }
-                    crate::nodes::classes::postfix_suffix::SuffixType::Method(method, args) => {
+                    crate::nodes::classes::postfix_suffix::SuffixType::Method(
+                        method,
+                        args,
+                        line,
+                    ) => {
                         current_node = Box::new(MethodCall {
                             object: current_node,
                             method_name: method.clone(),
                             args: args.iter().map(|a| a.box_clone()).collect(),
+                            line: *line,
+                        });
+                    }
+                    crate::nodes::classes::postfix_suffix::SuffixType::Call(args, line) => {
+                        current_node = Box::new(CallExpr {
+                            callee: current_node,
+                            args: args.iter().map(|a| a.box_clone()).collect(),
+                            line: *line,
+                        });
+                    }
+                    crate::nodes::classes::postfix_suffix::SuffixType::Index(index) => {
+                        current_node = Box::new(Index {
+                            object: current_node,
+                            index: index.clone(),
+                        });
+                    }
+                    crate::nodes::classes::postfix_suffix::SuffixType::Slice(start, end) => {
+                        current_node = Box::new(Slice {
+                            object: current_node,
+                            start: start.clone(),
+                            end: end.clone(),
                         });
                     }
                 }
@@ -52,6 +79,10 @@ impl Node for PostfixNode {
         Box::new(PostfixNode { root: current_node })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.root.as_ref()]
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(self.clone())
     }
@@ -1,4 +1,4 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
 use crate::node::{Context, Node, Value};
@@ -6,7 +6,13 @@ use crate::node::{Context, Node, Value};
 #[derive(Clone)]
 pub enum SuffixType {
     Member(String),
-    Method(String, Vec<Box<dyn Node>>),
+    /// Method call with its arguments and line.
+    Method(String, Vec<Box<dyn Node>>, usize),
+    /// Call of the value built so far, with its arguments and line.
+    Call(Vec<Box<dyn Node>>, usize),
+    Index(Box<dyn Node>),
+    /// Slice with optional start and end.
+    Slice(Option<Box<dyn Node>>, Option<Box<dyn Node>>),
 }
 
 #[derive(Clone)]
@@ -16,43 +22,63 @@ pub struct PostfixSuffixNode {
 
 impl Node for PostfixSuffixNode {
     fn run(&self, _ctx: &mut Context) -> Result<Value, RuntimeError> {
-        Err(RuntimeError {
-            message: "PostfixSuffixNode should not be run directly".to_string(),
-            stack_trace: vec![],
-        })
+        Err(RuntimeError::new(
+            ErrorKind::Other,
+            "PostfixSuffixNode should not be run directly",
+        ))
     }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
         // PostfixSuffix = "." member:Identifier
         // PostfixSuffix = "." method:Identifier "(" args:ArgList ")"
         // PostfixSuffix = "." method:Identifier "(" ")"
+        // PostfixSuffix = "(" args:ArgList ")"
+        // PostfixSuffix = "(" ")"
+        // PostfixSuffix = "[" index:Expr "]"
+        // PostfixSuffix = "[" start:Expr slice:[:] end:Expr "]", either bound optional
 
-        if let Some(member_node) = children.take_child("member") {
+        if children.take_named("slice").is_some() {
+            let start = children.take_named("start");
+            let end = children.take_named("end");
+            return Box::new(PostfixSuffixNode {
+                suffix_type: SuffixType::Slice(start, end),
+            });
+        } else if let Some(index) = children.take_named("index") {
+            return Box::new(PostfixSuffixNode {
+                suffix_type: SuffixType::Index(index),
+            });
+        } else if let Some(member_node) = children.take_child("member") {
             let member = member_node.text().unwrap_or_default();
             return Box::new(PostfixSuffixNode {
                 suffix_type: SuffixType::Member(member),
             });
         } else if let Some(method_node) = children.take_child("method") {
+            let line = children.line;
             let method = method_node.text().unwrap_or_default();
             let mut args = Vec::new();
 
-            if let Some(args_node) = children.take_child("args") {
-                if let Some(arg_list) = args_node
+            if let Some(args_node) = children.take_child("args")
+                && let Some(arg_list) = args_node
                     .as_any()
                     .downcast_ref::<crate::nodes::functions::ArgListNode>()
-                {
-                    if let Some(ref args_vec) = arg_list.args {
-                        args = args_vec.iter().map(|a| a.box_clone()).collect();
-                    }
-                }
+                && let Some(ref args_vec) = arg_list.args
+            {
+                args = args_vec.iter().map(|a| a.box_clone()).collect();
             }
 
             return Box::new(PostfixSuffixNode {
-                suffix_type: SuffixType::Method(method, args),
+                suffix_type: SuffixType::Method(method, args, line),
             });
         }
 
-        panic!("Invalid PostfixSuffix children");
+        let line = children.line;
+        let args = children
+            .take_named("args")
+            .map(|args| args.into_args())
+            .unwrap_or_default();
+        Box::new(PostfixSuffixNode {
+            suffix_type: SuffixType::Call(args, line),
+        })
     }
 
     fn box_clone(&self) -> Box<dyn Node> {
@@ -1,4 +1,4 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
 use crate::node::{Context, Node, Value};
@@ -8,13 +8,12 @@ pub struct SelfReference {}
 
 impl Node for SelfReference {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
-        ctx.variables
-            .get("this")
-            .cloned()
-            .ok_or_else(|| RuntimeError {
-                message: "'this' used outside of method context".to_string(),
-                stack_trace: vec![],
-            })
+        ctx.get_variable("this").ok_or_else(|| {
+            RuntimeError::new(
+                ErrorKind::NameError,
+                "'this' used outside of method context",
+            )
+        })
     }
 
     fn from_children(_rule: Rule, _children: ParsedChildren) -> Box<dyn Node> {
@@ -0,0 +1,85 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Callable, Context, METHOD_CLASS, Node, Value};
+
+/// `super.method(args)`: calls the method as inherited by the class that
+/// defines the running method, on the same `this`.
+#[derive(Clone)]
+pub struct SuperCall {
+    pub method_name: String,
+    pub args: Vec<Box<dyn Node>>,
+    pub line: usize,
+}
+
+impl Node for SuperCall {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        let (Some(Value::Object(object)), Some(Value::String(class))) =
+            (ctx.get_variable("this"), ctx.get_variable(METHOD_CLASS))
+        else {
+            return Err(RuntimeError::new(
+                ErrorKind::NameError,
+                "'super' used outside of method context",
+            ));
+        };
+        let class = class.borrow().clone();
+        let Some(parent) = ctx.class(&class).and_then(|c| c.parent.clone()) else {
+            return Err(RuntimeError::new(
+                ErrorKind::NameError,
+                format!("'super' used in class '{}', which extends no class", class),
+            ));
+        };
+        let Some((defining_class, function)) = ctx.find_method(&parent, &self.method_name) else {
+            return Err(RuntimeError::new(
+                ErrorKind::KeyError,
+                format!(
+                    "Method '{}' not found in class '{}'",
+                    self.method_name, parent
+                ),
+            ));
+        };
+
+        let mut arg_values = Vec::new();
+        for arg in &self.args {
+            arg_values.push(arg.run(ctx)?);
+        }
+
+        let callee = Callable::BoundMethod {
+            object,
+            class: defining_class,
+            name: self.method_name.clone(),
+            function,
+        };
+        ctx.call_function(&callee, arg_values).map_err(|mut err| {
+            err.push_frame(&self.method_name, self.line);
+            err
+        })
+    }
+
+    fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        // SuperCall = "super" "." method:Identifier "(" args:ArgList ")"
+        let line = children.line;
+        let method_name = children
+            .take_child("method")
+            .and_then(|method| method.text())
+            .expect("SuperCall missing method");
+        let args = children
+            .take_named("args")
+            .map(|args| args.into_args())
+            .unwrap_or_default();
+
+        Box::new(SuperCall {
+            method_name,
+            args,
+            line,
+        })
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.args.iter().map(|a| a.as_ref()).collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+}
@@ -10,11 +10,16 @@ use crate::node::ParsedChildren;
 
 impl Node for Block {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
-        let mut last_value = Value::Void;
-        for stmt in &self.statements {
-            last_value = stmt.run(ctx)?;
-        }
-        Ok(last_value)
+        ctx.with_scope(|ctx| {
+            let mut last_value = Value::Void;
+            for stmt in &self.statements {
+                last_value = stmt.run(ctx)?;
+                if ctx.control_flow.is_some() {
+                    break;
+                }
+            }
+            Ok(last_value)
+        })
     }
 
     fn from_children(_rule: Rule, children: ParsedChildren) -> Box<dyn Node> {
@@ -26,6 +31,10 @@ impl Node for Block {
         Box::new(Block { statements })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        self.statements.iter().map(|s| s.as_ref()).collect()
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(Block {
             statements: self.statements.iter().map(|s| s.box_clone()).collect(),
@@ -0,0 +1,30 @@
+use crate::error::RuntimeError;
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Context, ControlFlow, Node, Value};
+
+pub struct Break {
+    pub label: Option<String>,
+    pub line: usize,
+}
+
+impl Node for Break {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        ctx.control_flow = Some(ControlFlow::Break(self.label.clone()));
+        Ok(Value::Void)
+    }
+
+    fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        // Break = "break" label:Identifier | "break"
+        let line = children.line;
+        let label = children.take_named("label").and_then(|l| l.text());
+        Box::new(Break { label, line })
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(Break {
+            label: self.label.clone(),
+            line: self.line,
+        })
+    }
+}
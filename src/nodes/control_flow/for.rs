@@ -1,42 +1,123 @@
 use crate::error::RuntimeError;
+use crate::functions::range::RangeBounds;
 use crate::grammar::Rule;
+use crate::iteration::Iter;
 use crate::node::ParsedChildren;
-use crate::node::{Context, Node, Value};
+use crate::node::{Callable, Context, Node, SpannedNode, Value};
+use crate::nodes::FunctionCall;
+use crate::nodes::classes::PostfixNode;
 
 pub struct ForNode {
+    pub label: Option<String>,
+    /// Bound to the index of each item, or to the key when iterating a map.
+    pub index_name: Option<String>,
+    /// Bound to each item, or to the value when `index_name` is set and the
+    /// loop iterates a map.
     pub variable_name: String,
     pub iterable: Box<dyn Node>,
     pub body: Box<dyn Node>,
 }
 
+impl ForNode {
+    /// Evaluates the iterable and starts iterating it.
+    pub fn iterate(&self, ctx: &mut Context) -> Result<Iter, RuntimeError> {
+        match self.range_bounds(ctx) {
+            Some(bounds) => Ok(Iter::range(bounds?)),
+            None => {
+                let iterable = self.iterable.run(ctx)?;
+                Iter::new(ctx, iterable)
+            }
+        }
+    }
+
+    /// The next element, with its index or key if the loop binds one.
+    pub fn next_binding(
+        &self,
+        iter: &mut Iter,
+        ctx: &mut Context,
+    ) -> Result<Option<(Option<Value>, Value)>, RuntimeError> {
+        if self.index_name.is_some() {
+            Ok(iter
+                .next_pair(ctx)?
+                .map(|(index, element)| (Some(index), element)))
+        } else {
+            Ok(iter.next(ctx)?.map(|element| (None, element)))
+        }
+    }
+
+    /// Binds the loop variables in the current scope.
+    pub fn bind(&self, ctx: &mut Context, index: Option<Value>, element: Value) {
+        if let (Some(name), Some(index)) = (&self.index_name, index) {
+            ctx.define_variable(name, index);
+        }
+        ctx.define_variable(&self.variable_name, element);
+    }
+
+    /// The bounds of the range when the loop iterates a call to the built-in
+    /// `range`, so its numbers are produced one at a time instead of as a
+    /// list.
+    fn range_bounds(&self, ctx: &mut Context) -> Option<Result<RangeBounds, RuntimeError>> {
+        let mut node = self.iterable.as_ref();
+        loop {
+            let any = node.as_any();
+            if let Some(spanned) = any.downcast_ref::<SpannedNode>() {
+                node = spanned.inner.as_ref();
+            } else if let Some(postfix) = any.downcast_ref::<PostfixNode>() {
+                node = postfix.root.as_ref();
+            } else {
+                break;
+            }
+        }
+        let call = node.as_any().downcast_ref::<FunctionCall>()?;
+        if call.name != "range"
+            || !(1..=3).contains(&call.args.len())
+            || ctx.get_variable(&call.name).is_some()
+        {
+            return None;
+        }
+        match ctx.lookup_function(&call.name)? {
+            Callable::BuiltIn(builtin) if builtin.module == "collections" => {}
+            _ => return None,
+        }
+
+        let mut args = Vec::new();
+        for arg in &call.args {
+            match arg.run(ctx) {
+                Ok(value) => args.push(value),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        Some(RangeBounds::from_args(&args).map_err(|mut err| {
+            err.push_frame(&call.name, call.line);
+            err
+        }))
+    }
+}
+
 impl Node for ForNode {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
-        let iterable_val = self.iterable.run(ctx)?;
-
-        if let Value::List(list_rc) = iterable_val {
-            // We need to clone the list to iterate safely without holding a borrow across body execution
-            // because body execution might modify the list (though that would be weird in a for loop, but possible via append)
-            // Actually, if we modify the list while iterating, it might be tricky.
-            // Standard behavior: iterate over the snapshot or live?
-            // Let's iterate over a snapshot (clone the vector).
-            let elements = list_rc.borrow().clone();
-
-            for element in elements {
-                ctx.variables.insert(self.variable_name.clone(), element);
-                self.body.run(ctx)?;
+        let mut iter = self.iterate(ctx)?;
+        while let Some((index, element)) = self.next_binding(&mut iter, ctx)? {
+            ctx.step()?;
+            // Each iteration gets its own binding, so closures created in
+            // the body capture the element of that iteration.
+            ctx.with_scope(|ctx| {
+                self.bind(ctx, index, element);
+                self.body.run(ctx)
+            })?;
+            if !ctx.loop_should_continue(self.label.as_deref()) {
+                break;
             }
-            Ok(Value::Void)
-        } else {
-            Err(RuntimeError {
-                message: format!("For loop expects a list, got {:?}", iterable_val),
-                stack_trace: vec![],
-            })
         }
+        Ok(Value::Void)
     }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
         // ForLoop = "for" variable:Identifier "in" iterable:Expr "{" body:Block "}"
+        // ForLoop = "for" index:Identifier "," variable:Identifier "in" iterable:Expr "{" body:Block "}"
 
+        let label = children.take_named("label").and_then(|l| l.text());
+        let index_name = children.take_named("index").and_then(|i| i.text());
         let variable_node = children.take_child("variable").unwrap();
         let variable_name = variable_node
             .text()
@@ -46,14 +127,22 @@ impl Node for ForNode {
         let body = children.take_child("body").unwrap();
 
         Box::new(ForNode {
+            label,
+            index_name,
             variable_name,
             iterable,
             body,
         })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.iterable.as_ref(), self.body.as_ref()]
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(ForNode {
+            label: self.label.clone(),
+            index_name: self.index_name.clone(),
             variable_name: self.variable_name.clone(),
             iterable: self.iterable.clone(),
             body: self.body.clone(),
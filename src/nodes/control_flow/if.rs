@@ -13,13 +13,7 @@ impl Node for If {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
         let condition_val = self.condition.run(ctx)?;
 
-        let is_true = match condition_val {
-            Value::Bool(b) => b,
-            Value::Int(i) => i != 0,
-            _ => false,
-        };
-
-        if is_true {
+        if condition_val.is_truthy() {
             self.then_block.run(ctx)
         } else if let Some(else_block) = &self.else_block {
             else_block.run(ctx)
@@ -39,6 +33,14 @@ impl Node for If {
         })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        let mut children = vec![self.condition.as_ref(), self.then_block.as_ref()];
+        if let Some(else_block) = &self.else_block {
+            children.push(else_block.as_ref());
+        }
+        children
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(If {
             condition: self.condition.clone(),
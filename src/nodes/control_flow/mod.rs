@@ -1,11 +1,21 @@
 pub mod block;
+pub mod r#break;
+pub mod r#continue;
 pub mod r#for;
 pub mod r#if;
 pub mod r#return;
+pub mod throw;
+pub mod try_catch;
 pub mod r#while;
+pub mod r#yield;
 
 pub use block::Block;
+pub use r#break::Break;
+pub use r#continue::Continue;
 pub use r#for::ForNode;
 pub use r#if::If;
 pub use r#return::Return;
+pub use throw::Throw;
+pub use try_catch::TryCatch;
 pub use r#while::WhileNode;
+pub use r#yield::Yield;
@@ -1,7 +1,7 @@
 use crate::error::RuntimeError;
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
-use crate::node::{Context, Node, Value};
+use crate::node::{Context, ControlFlow, Node, Value};
 
 pub struct Return {
     pub expression: Box<dyn Node>,
@@ -9,7 +9,9 @@ pub struct Return {
 
 impl Node for Return {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
-        self.expression.run(ctx)
+        let value = self.expression.run(ctx)?;
+        ctx.control_flow = Some(ControlFlow::Return(value.clone()));
+        Ok(value)
     }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
@@ -17,6 +19,10 @@ impl Node for Return {
         Box::new(Return { expression: expr })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.expression.as_ref()]
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(Return {
             expression: self.expression.box_clone(),
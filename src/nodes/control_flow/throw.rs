@@ -0,0 +1,34 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::print::display_value;
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Context, Node, Value};
+
+/// Raises any value as an error that a `catch` can handle.
+pub struct Throw {
+    pub expression: Box<dyn Node>,
+}
+
+impl Node for Throw {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        let value = self.expression.run(ctx)?;
+        let mut error = RuntimeError::new(ErrorKind::Thrown, display_value(ctx, &value)?);
+        error.thrown = Some(value);
+        Err(error)
+    }
+
+    fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        let expr = children.take_child("value").expect("Throw missing value");
+        Box::new(Throw { expression: expr })
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.expression.as_ref()]
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(Throw {
+            expression: self.expression.box_clone(),
+        })
+    }
+}
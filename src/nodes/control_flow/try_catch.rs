@@ -0,0 +1,117 @@
+use crate::error::RuntimeError;
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Context, Node, Object, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// `try { ... } catch e { ... } finally { ... }`, with either the `catch` or
+/// the `finally` part optional.
+pub struct TryCatch {
+    pub body: Box<dyn Node>,
+    /// The variable bound to the error object, and the handler.
+    pub handler: Option<(String, Box<dyn Node>)>,
+    pub finally: Option<Box<dyn Node>>,
+}
+
+impl Node for TryCatch {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        let result = match (self.body.run(ctx), &self.handler) {
+            (Err(error), Some((name, handler))) if error.kind.is_catchable() => {
+                ctx.with_scope(|ctx| {
+                    ctx.define_variable(name, error_object(&error));
+                    handler.run(ctx)
+                })
+            }
+            // Exhausted budgets end the script without running more code.
+            (Err(error), _) if !error.kind.is_catchable() => return Err(error),
+            (result, _) => result,
+        };
+
+        let Some(finally) = &self.finally else {
+            return result;
+        };
+        // Run the finally block with any pending return, break or continue
+        // set aside; a signal of its own replaces both it and the result.
+        let pending = ctx.control_flow.take();
+        finally.run(ctx)?;
+        if ctx.control_flow.is_some() {
+            return Ok(Value::Void);
+        }
+        ctx.control_flow = pending;
+        result
+    }
+
+    fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        // TryCatch = "try" "{" body:Block "}" "catch" name:Identifier "{" handler:Block "}"
+        // TryCatch = ... "finally" "{" finally:Block "}"
+        let body = children.take_named("body").expect("TryCatch missing body");
+        let handler = children.take_named("handler").map(|handler| {
+            let name = children
+                .take_named("name")
+                .and_then(|name| name.text())
+                .expect("TryCatch missing error variable");
+            (name, handler)
+        });
+        let finally = children.take_named("finally");
+
+        Box::new(TryCatch {
+            body,
+            handler,
+            finally,
+        })
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        let mut children = vec![self.body.as_ref()];
+        children.extend(self.handler.as_ref().map(|(_, handler)| handler.as_ref()));
+        children.extend(self.finally.as_deref());
+        children
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(TryCatch {
+            body: self.body.box_clone(),
+            handler: self
+                .handler
+                .as_ref()
+                .map(|(name, handler)| (name.clone(), handler.box_clone())),
+            finally: self.finally.as_ref().map(|finally| finally.box_clone()),
+        })
+    }
+}
+
+/// The `Error` object a `catch` binds: the message, the kind's name and code,
+/// the line that failed (`Void` if unknown), the stack trace as a list of
+/// strings, and the thrown value (`Void` for errors raised by the
+/// interpreter).
+fn error_object(error: &RuntimeError) -> Value {
+    let string = |s: String| Value::String(Rc::new(RefCell::new(s)));
+    let frames = error
+        .stack_trace
+        .iter()
+        .map(|frame| string(frame.to_string()))
+        .collect();
+    let line = error
+        .span
+        .map_or(Value::Void, |span| Value::Int(span.line as i64));
+    let fields = HashMap::from([
+        ("message".to_string(), string(error.message.clone())),
+        ("kind".to_string(), string(format!("{:?}", error.kind))),
+        ("code".to_string(), string(error.kind.code().to_string())),
+        ("line".to_string(), line),
+        (
+            "stack_trace".to_string(),
+            Value::List(Rc::new(RefCell::new(frames))),
+        ),
+        (
+            "value".to_string(),
+            error.thrown.clone().unwrap_or(Value::Void),
+        ),
+    ]);
+    Value::Object(Rc::new(RefCell::new(Object {
+        class_name: "Error".to_string(),
+        fields,
+    })))
+}
@@ -4,6 +4,7 @@ use crate::node::ParsedChildren;
 use crate::node::{Context, Node, Value};
 
 pub struct WhileNode {
+    pub label: Option<String>,
     pub condition: Box<dyn Node>,
     pub body: Box<dyn Node>,
 }
@@ -11,18 +12,15 @@ pub struct WhileNode {
 impl Node for WhileNode {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
         loop {
-            let condition_val = self.condition.run(ctx)?;
-            let is_true = match condition_val {
-                Value::Bool(b) => b,
-                Value::Int(i) => i != 0,
-                _ => false,
-            };
-
-            if !is_true {
+            ctx.step()?;
+            if !self.condition.run(ctx)?.is_truthy() {
                 break;
             }
 
             self.body.run(ctx)?;
+            if !ctx.loop_should_continue(self.label.as_deref()) {
+                break;
+            }
         }
         Ok(Value::Void)
     }
@@ -32,14 +30,24 @@ impl Node for WhileNode {
         // Or similar. The parser usually names children if we use labels in grammar.
         // Assuming grammar: WhileLoop = "while" condition:Expr "{" body:Block "}"
 
+        let label = children.take_named("label").and_then(|l| l.text());
         let condition = children.take_child("condition").unwrap();
         let body = children.take_child("body").unwrap();
 
-        Box::new(WhileNode { condition, body })
+        Box::new(WhileNode {
+            label,
+            condition,
+            body,
+        })
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.condition.as_ref(), self.body.as_ref()]
     }
 
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(WhileNode {
+            label: self.label.clone(),
             condition: self.condition.clone(),
             body: self.body.clone(),
         })
@@ -0,0 +1,38 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Context, Node, Value};
+
+/// `yield value`. Makes the enclosing function a generator; the generator
+/// runs the statement itself, suspending with the value.
+pub struct Yield {
+    pub value: Box<dyn Node>,
+    pub line: usize,
+}
+
+impl Node for Yield {
+    fn run(&self, _ctx: &mut Context) -> Result<Value, RuntimeError> {
+        Err(RuntimeError::new(
+            ErrorKind::Other,
+            "'yield' used outside of a generator",
+        ))
+    }
+
+    fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        // Yield = "yield" value:Expr
+        let line = children.line;
+        let value = children.take_child("value").expect("Yield missing value");
+        Box::new(Yield { value, line })
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.value.as_ref()]
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(Yield {
+            value: self.value.box_clone(),
+            line: self.line,
+        })
+    }
+}
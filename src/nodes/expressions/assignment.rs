@@ -1,10 +1,161 @@
-use crate::error::RuntimeError;
-use crate::node::{Context, Node, ParsedChildren, Value};
+use crate::error::{ErrorKind, RuntimeError};
+use crate::node::{Context, Node, Object, ParsedChildren, SpannedNode, Value};
+use crate::nodes::classes::member_access::class_reference;
+use crate::nodes::classes::{MemberAccess, PostfixNode};
+use crate::nodes::expressions::index::{assign_index, read_index};
+use crate::nodes::expressions::{Index, Variable};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// What an assignment stores into.
+#[derive(Clone)]
+pub enum Target {
+    Variable(String),
+    /// `object.member`, which must be a declared field.
+    Member {
+        object: Box<dyn Node>,
+        member: String,
+    },
+    /// `object[index]` on a list or map.
+    Index {
+        object: Box<dyn Node>,
+        index: Box<dyn Node>,
+    },
+    /// Any other expression; rejected by the static checks.
+    Invalid,
+}
+
+impl Target {
+    /// The target an assignable expression such as `a`, `a.b` or `a[i]`
+    /// stands for.
+    pub fn from_node(node: &dyn Node) -> Target {
+        let any = node.as_any();
+        if let Some(spanned) = any.downcast_ref::<SpannedNode>() {
+            Target::from_node(spanned.inner.as_ref())
+        } else if let Some(postfix) = any.downcast_ref::<PostfixNode>() {
+            Target::from_node(postfix.root.as_ref())
+        } else if let Some(variable) = any.downcast_ref::<Variable>() {
+            Target::Variable(variable.name.clone())
+        } else if let Some(access) = any.downcast_ref::<MemberAccess>() {
+            Target::Member {
+                object: access.object.clone(),
+                member: access.member.clone(),
+            }
+        } else if let Some(index) = any.downcast_ref::<Index>() {
+            Target::Index {
+                object: index.object.clone(),
+                index: index.index.clone(),
+            }
+        } else {
+            Target::Invalid
+        }
+    }
+
+    /// Evaluates the object and index of the target, once.
+    pub fn resolve(&self, ctx: &mut Context) -> Result<Place, RuntimeError> {
+        match self {
+            Target::Variable(name) => Ok(Place::Variable(name.clone())),
+            Target::Member { object, member } => {
+                if let Some(class_name) = class_reference(ctx, object.as_ref()) {
+                    return match ctx.find_static_field(&class_name, member) {
+                        Some(fields) => Ok(Place::StaticField(fields, member.clone())),
+                        None => Err(RuntimeError::new(
+                            ErrorKind::KeyError,
+                            format!("Class '{}' has no static field '{}'", class_name, member),
+                        )),
+                    };
+                }
+                match object.run(ctx)? {
+                    Value::Object(obj) => {
+                        if !obj.borrow().fields.contains_key(member) {
+                            return Err(RuntimeError::new(
+                                ErrorKind::KeyError,
+                                format!(
+                                    "Object of class '{}' has no field '{}'",
+                                    obj.borrow().class_name,
+                                    member
+                                ),
+                            ));
+                        }
+                        Ok(Place::Member(obj, member.clone()))
+                    }
+                    _ => Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        format!("Cannot assign member '{}' on non-object", member),
+                    )),
+                }
+            }
+            Target::Index { object, index } => {
+                let object = object.run(ctx)?;
+                let index = index.run(ctx)?;
+                Ok(Place::Index(object, index))
+            }
+            Target::Invalid => Err(RuntimeError::new(
+                ErrorKind::Other,
+                "Invalid assignment target",
+            )),
+        }
+    }
+
+    /// The expressions the target evaluates.
+    pub fn children(&self) -> Vec<&dyn Node> {
+        match self {
+            Target::Member { object, .. } => vec![object.as_ref()],
+            Target::Index { object, index } => vec![object.as_ref(), index.as_ref()],
+            Target::Variable(_) | Target::Invalid => vec![],
+        }
+    }
+}
+
+/// A resolved assignment target.
+pub enum Place {
+    Variable(String),
+    Member(Rc<RefCell<Object>>, String),
+    /// A static field, in the fields of the class declaring it.
+    StaticField(Rc<RefCell<HashMap<String, Value>>>, String),
+    Index(Value, Value),
+}
+
+impl Place {
+    pub fn get(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        match self {
+            Place::Variable(name) => ctx.get_variable(name).ok_or_else(|| {
+                RuntimeError::new(
+                    ErrorKind::NameError,
+                    format!("Variable '{}' not found", name),
+                )
+            }),
+            Place::Member(obj, member) => Ok(obj.borrow().fields[member].clone()),
+            Place::StaticField(fields, name) => Ok(fields.borrow()[name].clone()),
+            Place::Index(object, index) => read_index(ctx, object, index),
+        }
+    }
+
+    pub fn set(&self, ctx: &mut Context, value: Value) -> Result<(), RuntimeError> {
+        match self {
+            Place::Variable(name) => ctx.assign_variable(name, value),
+            Place::Member(obj, member) => {
+                obj.borrow_mut().fields.insert(member.clone(), value);
+                Ok(())
+            }
+            Place::StaticField(fields, name) => {
+                fields.borrow_mut().insert(name.clone(), value);
+                Ok(())
+            }
+            Place::Index(object, index) => assign_index(ctx, object, index, value),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct Assignment {
-    pub variable_name: String,
+    pub target: Target,
     pub expr: Box<dyn Node>,
+    /// `Declaration` binds in the current block; `Assignment` may update an
+    /// existing binding of the enclosing function.
+    pub declare: bool,
+    pub line: usize,
 }
 
 use crate::grammar::Rule;
@@ -12,7 +163,10 @@ use crate::grammar::Rule;
 impl Node for Assignment {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
         let value = self.expr.run(ctx)?;
-        ctx.variables.insert(self.variable_name.clone(), value);
+        match &self.target {
+            Target::Variable(name) if self.declare => ctx.define_variable(name, value),
+            target => target.resolve(ctx)?.set(ctx, value)?,
+        }
         Ok(Value::Void)
     }
 
@@ -20,25 +174,46 @@ impl Node for Assignment {
     where
         Self: Sized,
     {
-        if rule != Rule::Assignment {
-            panic!("Assignment::from_children called with rule {:?}", rule);
-        }
+        let declare = match rule {
+            Rule::Assignment => false,
+            Rule::Declaration => true,
+            _ => panic!("Assignment::from_children called with rule {:?}", rule),
+        };
 
-        let variable_node = children
-            .take_child("name")
-            .expect("Assignment missing name");
-        let variable_name = variable_node.text().expect("Variable node missing text");
+        // Assignment = name:Identifier "=" value:Expr
+        // Assignment = target:Postfix "=" value:Expr
+        let target = if let Some(variable_node) = children.take_named("name") {
+            Target::Variable(variable_node.text().expect("Variable node missing text"))
+        } else {
+            let target_node = children
+                .take_named("target")
+                .expect("Assignment missing name or target");
+            match Target::from_node(target_node.as_ref()) {
+                // Only plain variables can be declared.
+                Target::Variable(name) => Target::Variable(name),
+                _ if declare => Target::Invalid,
+                target => target,
+            }
+        };
 
         let expr = children
             .take_child("value")
             .expect("Assignment missing value");
 
         Box::new(Assignment {
-            variable_name,
+            target,
             expr,
+            declare,
+            line: children.line,
         })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        let mut children = self.target.children();
+        children.push(self.expr.as_ref());
+        children
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(self.clone())
     }
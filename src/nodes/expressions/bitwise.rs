@@ -0,0 +1,121 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Context, Node, Value};
+use crate::numeric;
+use num_traits::{Signed, ToPrimitive};
+
+#[derive(Debug, Clone, Copy)]
+pub enum BitOp {
+    And,
+    Or,
+    Xor,
+    Shl,
+    Shr,
+}
+
+/// Bitwise operators on integers, with two's complement semantics for
+/// negative numbers. `&`, `|` and `^` also combine booleans.
+pub struct Bitwise {
+    pub op: BitOp,
+    pub left: Box<dyn Node>,
+    pub right: Box<dyn Node>,
+}
+
+impl Node for Bitwise {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        let left_val = self.left.run(ctx)?;
+        let right_val = self.right.run(ctx)?;
+
+        match (&left_val, &right_val) {
+            (Value::Bool(l), Value::Bool(r)) => match self.op {
+                BitOp::And => return Ok(Value::Bool(l & r)),
+                BitOp::Or => return Ok(Value::Bool(l | r)),
+                BitOp::Xor => return Ok(Value::Bool(l ^ r)),
+                BitOp::Shl | BitOp::Shr => {}
+            },
+            (Value::Int(l), Value::Int(r)) => match self.op {
+                BitOp::And => return Ok(Value::Int(l & r)),
+                BitOp::Or => return Ok(Value::Int(l | r)),
+                BitOp::Xor => return Ok(Value::Int(l ^ r)),
+                BitOp::Shl | BitOp::Shr => {}
+            },
+            _ => {}
+        }
+
+        let (Some(l), Some(r)) = (numeric::to_big(&left_val), numeric::to_big(&right_val)) else {
+            return Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                format!(
+                    "Invalid operands for bitwise operation: {:?} and {:?}",
+                    left_val, right_val
+                ),
+            ));
+        };
+        let result = match self.op {
+            BitOp::And => l & r,
+            BitOp::Or => l | r,
+            BitOp::Xor => l ^ r,
+            BitOp::Shl | BitOp::Shr => {
+                let amount = shift_amount(&r)?;
+                if let BitOp::Shl = self.op {
+                    ctx.allocate((l.bits() as usize + amount) / 8)?;
+                    l << amount
+                } else {
+                    l >> amount
+                }
+            }
+        };
+        Ok(numeric::int_value(result))
+    }
+
+    fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        // BitwiseOr = BitwiseXor BitOr BitwiseOr | BitwiseXor
+        // BitwiseXor = BitwiseAnd BitXor BitwiseXor | BitwiseAnd
+        // BitwiseAnd = Shift BitAnd BitwiseAnd | Shift
+        // Shift = Term Shl Shift | Term Shr Shift | Term
+        let left = children.take_child("").unwrap();
+
+        if let Some(op_node) = children.take_child("") {
+            let right = children.take_child("").unwrap();
+            let op = match op_node.rule() {
+                Some(Rule::BitAnd) => BitOp::And,
+                Some(Rule::BitOr) => BitOp::Or,
+                Some(Rule::BitXor) => BitOp::Xor,
+                Some(Rule::Shl) => BitOp::Shl,
+                Some(Rule::Shr) => BitOp::Shr,
+                _ => panic!("Unknown BitOp rule: {:?}", op_node.rule()),
+            };
+            Box::new(Bitwise { op, left, right })
+        } else {
+            left
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(Bitwise {
+            op: self.op,
+            left: self.left.clone(),
+            right: self.right.clone(),
+        })
+    }
+}
+
+fn shift_amount(amount: &num_bigint::BigInt) -> Result<usize, RuntimeError> {
+    if amount.is_negative() {
+        return Err(RuntimeError::new(
+            ErrorKind::ValueError,
+            format!("Negative shift amount {}", amount),
+        ));
+    }
+    amount.to_u32().map(|n| n as usize).ok_or_else(|| {
+        RuntimeError::new(
+            ErrorKind::Overflow,
+            format!("Shift amount {} is too large", amount),
+        )
+    })
+}
@@ -1,7 +1,11 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
 use crate::node::{Context, Node, Value};
+use crate::numeric;
+use crate::special;
+use std::cmp::Ordering;
+use std::slice::from_ref;
 
 #[derive(Debug, Clone, Copy)]
 pub enum CompOp {
@@ -9,6 +13,22 @@ pub enum CompOp {
     NotEqual,
     Less,
     Greater,
+    LessEqual,
+    GreaterEqual,
+}
+
+impl CompOp {
+    /// Whether two values ordered as `ordering` satisfy the operator.
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            CompOp::Equal => ordering.is_eq(),
+            CompOp::NotEqual => ordering.is_ne(),
+            CompOp::Less => ordering.is_lt(),
+            CompOp::Greater => ordering.is_gt(),
+            CompOp::LessEqual => ordering.is_le(),
+            CompOp::GreaterEqual => ordering.is_ge(),
+        }
+    }
 }
 
 pub struct Comparison {
@@ -21,49 +41,46 @@ impl Node for Comparison {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
         let left_val = self.left.run(ctx)?;
         let right_val = self.right.run(ctx)?;
+        if let Some(result) = compare_objects(ctx, self.op, &left_val, &right_val) {
+            return result.map(Value::Bool);
+        }
 
         let result = match (&left_val, &right_val) {
-            (Value::Int(l), Value::Int(r)) => match self.op {
-                CompOp::Equal => l == r,
-                CompOp::NotEqual => l != r,
-                CompOp::Less => l < r,
-                CompOp::Greater => l > r,
-            },
-            (Value::Float(l), Value::Float(r)) => match self.op {
-                CompOp::Equal => (l - r).abs() < f64::EPSILON,
-                CompOp::NotEqual => (l - r).abs() >= f64::EPSILON,
-                CompOp::Less => l < r,
-                CompOp::Greater => l > r,
-            },
-            (Value::String(a), Value::String(b)) => {
-                let a = a.borrow();
-                let b = b.borrow();
+            (l, r) if numeric::is_exact(l) && numeric::is_exact(r) => {
+                self.op.holds(numeric::compare(l, r).unwrap())
+            }
+            // At least one float: compare as floats, with a tolerance for
+            // equality.
+            (l, r) if numeric::is_number(l) && numeric::is_number(r) => {
+                let (l, r) = (numeric::to_f64(l).unwrap(), numeric::to_f64(r).unwrap());
+                let equal = (l - r).abs() < f64::EPSILON;
                 match self.op {
-                    CompOp::Equal => *a == *b,
-                    CompOp::NotEqual => *a != *b,
-                    CompOp::Less => *a < *b,
-                    CompOp::Greater => *a > *b,
+                    CompOp::Equal => equal,
+                    CompOp::NotEqual => !equal,
+                    CompOp::Less => l < r,
+                    CompOp::Greater => l > r,
+                    CompOp::LessEqual => l < r || equal,
+                    CompOp::GreaterEqual => l > r || equal,
                 }
             }
-            (Value::Bool(l), Value::Bool(r)) => match self.op {
-                CompOp::Equal => l == r,
-                CompOp::NotEqual => l != r,
-                CompOp::Less => l < r, // False < True
-                CompOp::Greater => l > r,
-            },
+            (Value::String(a), Value::String(b)) => self.op.holds(a.borrow().cmp(&b.borrow())),
+            // false < true
+            (Value::Bool(l), Value::Bool(r)) => self.op.holds(l.cmp(r)),
+            (Value::Function(a), Value::Function(b))
+                if matches!(self.op, CompOp::Equal | CompOp::NotEqual) =>
+            {
+                (a == b) == matches!(self.op, CompOp::Equal)
+            }
             (l, r) => {
                 // For equality, we can say they are not equal if types differ
                 match self.op {
                     CompOp::Equal => false,
                     CompOp::NotEqual => true,
                     _ => {
-                        return Err(RuntimeError {
-                            message: format!(
-                                "Invalid operands for comparison: {:?} and {:?}",
-                                l, r
-                            ),
-                            stack_trace: vec![],
-                        });
+                        return Err(RuntimeError::new(
+                            ErrorKind::TypeError,
+                            format!("Invalid operands for comparison: {:?} and {:?}", l, r),
+                        ));
                     }
                 }
             }
@@ -82,15 +99,16 @@ impl Node for Comparison {
         let left = children.take_child("").unwrap(); // Term
 
         if let Some(op_node) = children.take_child("") {
-            // CompOp
+            // CompOp, identified by its rule so dialects can spell it freely
             let right = children.take_child("").unwrap(); // Term
-            let op_text = op_node.text().unwrap();
-            let op = match op_text.as_str() {
-                "==" | "equals" => CompOp::Equal,
-                "!=" | "notequals" => CompOp::NotEqual,
-                "<" | "less" => CompOp::Less,
-                ">" | "greater" => CompOp::Greater,
-                _ => panic!("Unknown CompOp: {}", op_text),
+            let op = match op_node.rule() {
+                Some(Rule::Eq) => CompOp::Equal,
+                Some(Rule::Neq) => CompOp::NotEqual,
+                Some(Rule::Lt) => CompOp::Less,
+                Some(Rule::Gt) => CompOp::Greater,
+                Some(Rule::Le) => CompOp::LessEqual,
+                Some(Rule::Ge) => CompOp::GreaterEqual,
+                _ => panic!("Unknown CompOp rule: {:?}", op_node.rule()),
             };
             Box::new(Comparison { op, left, right })
         } else {
@@ -98,6 +116,10 @@ impl Node for Comparison {
         }
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(Comparison {
             op: self.op, // CompOp is Copy
@@ -106,3 +128,41 @@ impl Node for Comparison {
         })
     }
 }
+
+/// Compares through `__eq__` and `__lt__` when an operand is an object whose
+/// class defines them; `None` otherwise.
+fn compare_objects(
+    ctx: &mut Context,
+    op: CompOp,
+    left: &Value,
+    right: &Value,
+) -> Option<Result<bool, RuntimeError>> {
+    let truthy = |result: Result<Value, RuntimeError>| result.map(|value| value.is_truthy());
+    match op {
+        CompOp::Equal | CompOp::NotEqual => {
+            let equal = special::call(ctx, left, special::EQ, from_ref(right))
+                .or_else(|| special::call(ctx, right, special::EQ, from_ref(left)))?;
+            Some(truthy(equal).map(|equal| equal == matches!(op, CompOp::Equal)))
+        }
+        CompOp::Less => Some(truthy(special::call(
+            ctx,
+            left,
+            special::LT,
+            from_ref(right),
+        )?)),
+        CompOp::Greater => Some(truthy(special::call(
+            ctx,
+            right,
+            special::LT,
+            from_ref(left),
+        )?)),
+        CompOp::LessEqual => {
+            let greater = special::call(ctx, right, special::LT, from_ref(left))?;
+            Some(truthy(greater).map(|greater| !greater))
+        }
+        CompOp::GreaterEqual => {
+            let less = special::call(ctx, left, special::LT, from_ref(right))?;
+            Some(truthy(less).map(|less| !less))
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::error::RuntimeError;
+use crate::grammar::Rule;
+use crate::node::{Context, Node, ParsedChildren, Value};
+use crate::nodes::expressions::assignment::Target;
+use crate::nodes::expressions::factor::MulOp;
+use crate::nodes::expressions::term::AddOp;
+
+#[derive(Debug, Clone, Copy)]
+pub enum CompoundOp {
+    Add(AddOp),
+    Mul(MulOp),
+}
+
+/// `target op= value`, e.g. `i += 1` or `counts[k] *= 2`. The target's object
+/// and index are evaluated once.
+#[derive(Clone)]
+pub struct CompoundAssignment {
+    pub target: Target,
+    pub op: CompoundOp,
+    pub expr: Box<dyn Node>,
+    pub line: usize,
+}
+
+impl Node for CompoundAssignment {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        let place = self.target.resolve(ctx)?;
+        let current = place.get(ctx)?;
+        let operand = self.expr.run(ctx)?;
+        let value = match self.op {
+            CompoundOp::Add(op) => op.apply(ctx, current, operand)?,
+            CompoundOp::Mul(op) => op.apply(ctx, current, operand)?,
+        };
+        place.set(ctx, value)?;
+        Ok(Value::Void)
+    }
+
+    fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        // CompoundAssignment = target:Postfix AddAssign value:Expr
+        // CompoundAssignment = "increase" target:Identifier AddAssign value:Expr
+        let target_node = children
+            .take_named("target")
+            .expect("CompoundAssignment missing target");
+        let op_node = children
+            .take_child("")
+            .expect("CompoundAssignment missing operator");
+        let op = match op_node.rule() {
+            Some(Rule::AddAssign) => CompoundOp::Add(AddOp::Add),
+            Some(Rule::SubAssign) => CompoundOp::Add(AddOp::Sub),
+            Some(Rule::MulAssign) => CompoundOp::Mul(MulOp::Mul),
+            Some(Rule::DivAssign) => CompoundOp::Mul(MulOp::Div),
+            Some(Rule::ModAssign) => CompoundOp::Mul(MulOp::Mod),
+            _ => panic!("Unknown compound assignment rule: {:?}", op_node.rule()),
+        };
+        let expr = children
+            .take_named("value")
+            .expect("CompoundAssignment missing value");
+
+        Box::new(CompoundAssignment {
+            target: Target::from_node(target_node.as_ref()),
+            op,
+            expr,
+            line: children.line,
+        })
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        let mut children = self.target.children();
+        children.push(self.expr.as_ref());
+        children
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+}
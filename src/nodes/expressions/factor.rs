@@ -1,12 +1,19 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
 use crate::node::{Context, Node, Value};
+use crate::numeric::{self, ArithOp};
+use crate::special;
 
 #[derive(Debug, Clone, Copy)]
 pub enum MulOp {
     Mul,
+    /// Truncating for two integers.
     Div,
+    /// Float-producing for two integers.
+    FloatDiv,
+    /// Truncated quotient for any numbers.
+    IntDiv,
     Mod,
 }
 
@@ -16,48 +23,44 @@ pub struct Factor {
     pub right: Box<dyn Node>,
 }
 
+impl MulOp {
+    /// Multiplies, divides or takes the remainder of two numbers, or of an
+    /// object defining the matching special method and any right operand.
+    pub fn apply(
+        self,
+        ctx: &mut Context,
+        left: Value,
+        right: Value,
+    ) -> Result<Value, RuntimeError> {
+        let (op, method) = match self {
+            MulOp::Mul => (ArithOp::Mul, special::MUL),
+            MulOp::Div => (ArithOp::Div, special::DIV),
+            MulOp::FloatDiv => (ArithOp::FloatDiv, special::DIV),
+            MulOp::IntDiv => (ArithOp::IntDiv, special::INT_DIV),
+            MulOp::Mod => (ArithOp::Mod, special::MOD),
+        };
+        if let Some(result) = special::call(ctx, &left, method, std::slice::from_ref(&right)) {
+            return result;
+        }
+        if let Some(result) = numeric::arith(op, &left, &right) {
+            return result;
+        }
+
+        Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            format!(
+                "Invalid operands for multiplication/division/modulo: {:?} and {:?}",
+                left, right
+            ),
+        ))
+    }
+}
+
 impl Node for Factor {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
         let left_val = self.left.run(ctx)?;
         let right_val = self.right.run(ctx)?;
-
-        match (left_val, right_val) {
-            (Value::Int(l), Value::Int(r)) => match self.op {
-                MulOp::Mul => Ok(Value::Int(l * r)),
-                MulOp::Div => {
-                    if r == 0 {
-                        Err(RuntimeError {
-                            message: "Division by zero".to_string(),
-                            stack_trace: vec![],
-                        })
-                    } else {
-                        Ok(Value::Int(l / r))
-                    }
-                }
-                MulOp::Mod => {
-                    if r == 0 {
-                        Err(RuntimeError {
-                            message: "Modulo by zero".to_string(),
-                            stack_trace: vec![],
-                        })
-                    } else {
-                        Ok(Value::Int(l % r))
-                    }
-                }
-            },
-            (Value::Float(l), Value::Float(r)) => match self.op {
-                MulOp::Mul => Ok(Value::Float(l * r)),
-                MulOp::Div => Ok(Value::Float(l / r)),
-                MulOp::Mod => Ok(Value::Float(l % r)),
-            },
-            (l, r) => Err(RuntimeError {
-                message: format!(
-                    "Invalid operands for multiplication/division/modulo: {:?} and {:?}",
-                    l, r
-                ),
-                stack_trace: vec![],
-            }),
-        }
+        self.op.apply(ctx, left_val, right_val)
     }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
@@ -69,6 +72,8 @@ impl Node for Factor {
             let op = match op_node.rule() {
                 Some(Rule::Mul) => MulOp::Mul,
                 Some(Rule::Div) => MulOp::Div,
+                Some(Rule::FloatDiv) => MulOp::FloatDiv,
+                Some(Rule::IntDiv) => MulOp::IntDiv,
                 Some(Rule::Mod) => MulOp::Mod,
                 _ => panic!("Unknown MulOp rule: {:?}", op_node.rule()),
             };
@@ -78,6 +83,10 @@ impl Node for Factor {
         }
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(Factor {
             op: self.op,
@@ -0,0 +1,131 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Context, Node, Value};
+use crate::special;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::slice::from_ref;
+
+/// `object[index]` on lists, strings and maps. Negative list and string
+/// indices count from the end; a missing map key gives `Void`, like `get`.
+#[derive(Clone)]
+pub struct Index {
+    pub object: Box<dyn Node>,
+    pub index: Box<dyn Node>,
+}
+
+impl Node for Index {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        let object = self.object.run(ctx)?;
+        let index = self.index.run(ctx)?;
+
+        read_index(ctx, &object, &index)
+    }
+
+    fn from_children(_rule: Rule, _children: ParsedChildren) -> Box<dyn Node> {
+        panic!("Index is built by PostfixNode from an index suffix");
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.object.as_ref(), self.index.as_ref()]
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+}
+
+/// Reads `object[index]`, through `__getitem__` for objects.
+pub fn read_index(ctx: &mut Context, object: &Value, index: &Value) -> Result<Value, RuntimeError> {
+    if let Some(result) = special::call(ctx, object, special::GET_ITEM, from_ref(index)) {
+        return result;
+    }
+    match object {
+        Value::List(l) => {
+            let list = l.borrow();
+            Ok(list[position(index, list.len(), "List")?].clone())
+        }
+        Value::String(s) => {
+            let chars: Vec<char> = s.borrow().chars().collect();
+            let ch = chars[position(index, chars.len(), "String")?];
+            Ok(Value::String(Rc::new(RefCell::new(ch.to_string()))))
+        }
+        Value::Map(m) => Ok(m
+            .borrow()
+            .get(map_key(index)?.as_str())
+            .cloned()
+            .unwrap_or(Value::Void)),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            format!("Cannot index into {}", object.type_name()),
+        )),
+    }
+}
+
+/// Stores `value` at `object[index]`, for assignments to an index, through
+/// `__setitem__` for objects.
+pub fn assign_index(
+    ctx: &mut Context,
+    object: &Value,
+    index: &Value,
+    value: Value,
+) -> Result<(), RuntimeError> {
+    if let Value::Object(_) = object
+        && let Some(result) = special::call(
+            ctx,
+            object,
+            special::SET_ITEM,
+            &[index.clone(), value.clone()],
+        )
+    {
+        return result.map(|_| ());
+    }
+    match object {
+        Value::List(l) => {
+            let mut list = l.borrow_mut();
+            let position = position(index, list.len(), "List")?;
+            list[position] = value;
+            Ok(())
+        }
+        Value::Map(m) => {
+            m.borrow_mut().insert(map_key(index)?, value);
+            Ok(())
+        }
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            format!("Cannot assign to an index of {}", object.type_name()),
+        )),
+    }
+}
+
+fn position(index: &Value, len: usize, type_name: &str) -> Result<usize, RuntimeError> {
+    let Value::Int(i) = index else {
+        return Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            format!(
+                "{} index must be an integer, got {}",
+                type_name,
+                index.type_name()
+            ),
+        ));
+    };
+    let position = if *i < 0 { *i + len as i64 } else { *i };
+    if position < 0 || position >= len as i64 {
+        return Err(RuntimeError::new(
+            ErrorKind::IndexError,
+            format!("Index {} out of bounds (len {})", i, len),
+        ));
+    }
+    Ok(position as usize)
+}
+
+fn map_key(index: &Value) -> Result<String, RuntimeError> {
+    match index {
+        Value::String(s) => Ok(s.borrow().clone()),
+        _ => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            format!("Map key must be a string, got {}", index.type_name()),
+        )),
+    }
+}
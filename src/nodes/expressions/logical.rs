@@ -1,4 +1,4 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
 use crate::node::{Context, Node, Value};
@@ -26,20 +26,20 @@ impl Node for Logical {
                         return Ok(Value::Bool(false));
                     }
                 } else {
-                    return Err(RuntimeError {
-                        message: format!("Expected boolean for logical AND, got {:?}", left_val),
-                        stack_trace: vec![],
-                    });
+                    return Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        format!("Expected boolean for logical AND, got {:?}", left_val),
+                    ));
                 }
 
                 let right_val = self.right.run(ctx)?;
                 if let Value::Bool(b) = right_val {
                     Ok(Value::Bool(b))
                 } else {
-                    Err(RuntimeError {
-                        message: format!("Expected boolean for logical AND, got {:?}", right_val),
-                        stack_trace: vec![],
-                    })
+                    Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        format!("Expected boolean for logical AND, got {:?}", right_val),
+                    ))
                 }
             }
             LogOp::Or => {
@@ -48,20 +48,20 @@ impl Node for Logical {
                         return Ok(Value::Bool(true));
                     }
                 } else {
-                    return Err(RuntimeError {
-                        message: format!("Expected boolean for logical OR, got {:?}", left_val),
-                        stack_trace: vec![],
-                    });
+                    return Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        format!("Expected boolean for logical OR, got {:?}", left_val),
+                    ));
                 }
 
                 let right_val = self.right.run(ctx)?;
                 if let Value::Bool(b) = right_val {
                     Ok(Value::Bool(b))
                 } else {
-                    Err(RuntimeError {
-                        message: format!("Expected boolean for logical OR, got {:?}", right_val),
-                        stack_trace: vec![],
-                    })
+                    Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        format!("Expected boolean for logical OR, got {:?}", right_val),
+                    ))
                 }
             }
         }
@@ -86,6 +86,10 @@ impl Node for Logical {
         }
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(Logical {
             op: self.op, // LogOp is Copy
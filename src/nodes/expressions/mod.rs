@@ -1,15 +1,27 @@
 pub mod assignment;
+pub mod bitwise;
 pub mod comparison;
+pub mod compound_assignment;
 pub mod factor;
+pub mod index;
 pub mod logical;
+pub mod nonlocal;
+pub mod power;
+pub mod slice;
 pub mod term;
 pub mod unary;
 pub mod variable;
 
 pub use assignment::Assignment;
+pub use bitwise::Bitwise;
 pub use comparison::Comparison;
+pub use compound_assignment::CompoundAssignment;
 pub use factor::Factor;
+pub use index::Index;
 pub use logical::Logical;
+pub use nonlocal::Nonlocal;
+pub use power::Power;
+pub use slice::Slice;
 pub use term::Term;
 pub use unary::Unary;
 pub use variable::Variable;
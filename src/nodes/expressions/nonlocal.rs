@@ -0,0 +1,37 @@
+use crate::environment::Environment;
+use crate::error::RuntimeError;
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Context, Node, Value};
+
+/// Makes plain assignments to the listed names in the current function
+/// update the binding in an enclosing scope instead of creating a local.
+#[derive(Clone)]
+pub struct Nonlocal {
+    pub names: Vec<String>,
+}
+
+impl Node for Nonlocal {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        for name in &self.names {
+            Environment::declare_nonlocal(&ctx.env, name);
+        }
+        Ok(Value::Void)
+    }
+
+    fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        // Nonlocal = "nonlocal" names:ParamList | "nonlocal" name:Identifier
+        let names_node = children
+            .take_child("names")
+            .expect("Nonlocal missing names");
+        let names = names_node
+            .params()
+            .or_else(|| names_node.text().map(|name| vec![name]))
+            .unwrap_or_default();
+        Box::new(Nonlocal { names })
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+}
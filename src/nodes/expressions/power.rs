@@ -0,0 +1,52 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Context, Node, Value};
+use crate::numeric;
+
+pub struct Power {
+    pub base: Box<dyn Node>,
+    pub exponent: Box<dyn Node>,
+}
+
+impl Node for Power {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        let base = self.base.run(ctx)?;
+        let exponent = self.exponent.run(ctx)?;
+
+        ctx.allocate(numeric::power_size(&base, &exponent))?;
+        numeric::power(&base, &exponent).unwrap_or_else(|| {
+            Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                format!(
+                    "Invalid operands for exponentiation: {:?} and {:?}",
+                    base, exponent
+                ),
+            ))
+        })
+    }
+
+    fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        // Power = Atom Pow Unary | Atom
+        let base = children.take_child("").unwrap();
+
+        if let Some(op_node) = children.take_child("") {
+            assert_eq!(op_node.rule(), Some(Rule::Pow), "Unknown power operator");
+            let exponent = children.take_child("").unwrap();
+            Box::new(Power { base, exponent })
+        } else {
+            base
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.base.as_ref(), self.exponent.as_ref()]
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(Power {
+            base: self.base.clone(),
+            exponent: self.exponent.clone(),
+        })
+    }
+}
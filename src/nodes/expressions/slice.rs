@@ -0,0 +1,60 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::functions::slice::{slice_size, slice_value};
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Context, Node, Value};
+
+/// `object[start:end]` on lists and strings, with the semantics of the
+/// `slice` built-in. Either bound may be omitted.
+#[derive(Clone)]
+pub struct Slice {
+    pub object: Box<dyn Node>,
+    pub start: Option<Box<dyn Node>>,
+    pub end: Option<Box<dyn Node>>,
+}
+
+impl Node for Slice {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        let object = self.object.run(ctx)?;
+        let start = bound(&self.start, ctx)?;
+        let end = bound(&self.end, ctx)?;
+
+        if !matches!(object, Value::List(_) | Value::String(_)) {
+            return Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                format!("Cannot slice {}", object.type_name()),
+            ));
+        }
+        let result = slice_value(&object, start, end)?;
+        ctx.allocate(slice_size(&result))?;
+        Ok(result)
+    }
+
+    fn from_children(_rule: Rule, _children: ParsedChildren) -> Box<dyn Node> {
+        panic!("Slice is built by PostfixNode from a slice suffix");
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        let mut children = vec![self.object.as_ref()];
+        children.extend(self.start.as_deref());
+        children.extend(self.end.as_deref());
+        children
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+}
+
+fn bound(node: &Option<Box<dyn Node>>, ctx: &mut Context) -> Result<Option<i64>, RuntimeError> {
+    let Some(node) = node else {
+        return Ok(None);
+    };
+    match node.run(ctx)? {
+        Value::Int(i) => Ok(Some(i)),
+        other => Err(RuntimeError::new(
+            ErrorKind::TypeError,
+            format!("Slice bounds must be integers, got {}", other.type_name()),
+        )),
+    }
+}
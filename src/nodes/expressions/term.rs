@@ -1,7 +1,9 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
 use crate::node::{Context, Node, Value};
+use crate::numeric::{self, ArithOp};
+use crate::special;
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -17,38 +19,55 @@ pub struct Term {
     pub right: Box<dyn Node>,
 }
 
-impl Node for Term {
-    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
-        let left_val = self.left.run(ctx)?;
-        let right_val = self.right.run(ctx)?;
+impl AddOp {
+    /// Adds or subtracts two values: numbers, strings for `+`, or an object
+    /// defining `__add__`/`__sub__` on the left.
+    pub fn apply(
+        self,
+        ctx: &mut Context,
+        left: Value,
+        right: Value,
+    ) -> Result<Value, RuntimeError> {
+        let (op, method) = match self {
+            AddOp::Add => (ArithOp::Add, special::ADD),
+            AddOp::Sub => (ArithOp::Sub, special::SUB),
+        };
+        if let Some(result) = special::call(ctx, &left, method, std::slice::from_ref(&right)) {
+            return result;
+        }
+        if let Some(result) = numeric::arith(op, &left, &right) {
+            return result;
+        }
 
-        match (left_val, right_val) {
-            (Value::Int(l), Value::Int(r)) => match self.op {
-                AddOp::Add => Ok(Value::Int(l + r)),
-                AddOp::Sub => Ok(Value::Int(l - r)),
-            },
-            (Value::Float(l), Value::Float(r)) => match self.op {
-                AddOp::Add => Ok(Value::Float(l + r)),
-                AddOp::Sub => Ok(Value::Float(l - r)),
+        match (left, right) {
+            (Value::String(l), Value::String(r)) => match self {
+                AddOp::Add => {
+                    let joined = l.borrow().clone() + &r.borrow();
+                    ctx.allocate(joined.len())?;
+                    Ok(Value::String(Rc::new(RefCell::new(joined))))
+                }
+                AddOp::Sub => Err(RuntimeError::new(
+                    ErrorKind::TypeError,
+                    "Subtraction not supported for strings",
+                )),
             },
-            (Value::String(l), Value::String(r)) => match self.op {
-                AddOp::Add => Ok(Value::String(Rc::new(RefCell::new(
-                    l.borrow().clone() + &r.borrow(),
-                )))),
-                AddOp::Sub => Err(RuntimeError {
-                    message: "Subtraction not supported for strings".to_string(),
-                    stack_trace: vec![],
-                }),
-            },
-            (l, r) => Err(RuntimeError {
-                message: format!(
+            (l, r) => Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                format!(
                     "Invalid operands for addition/subtraction: {:?} and {:?}",
                     l, r
                 ),
-                stack_trace: vec![],
-            }),
+            )),
         }
     }
+}
+
+impl Node for Term {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        let left_val = self.left.run(ctx)?;
+        let right_val = self.right.run(ctx)?;
+        self.op.apply(ctx, left_val, right_val)
+    }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
         // Term = Factor AddOp Term | Factor
@@ -68,6 +87,10 @@ impl Node for Term {
         }
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(Term {
             op: self.op,
@@ -1,12 +1,15 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
 use crate::node::{Context, Node, Value};
+use crate::numeric;
+use crate::special;
 
 #[derive(Debug, Clone, Copy)]
 pub enum UnaryOp {
     Not,
     Neg,
+    BitNot,
 }
 
 pub struct Unary {
@@ -22,37 +25,54 @@ impl Node for Unary {
                 if let Value::Bool(b) = val {
                     Ok(Value::Bool(!b))
                 } else {
-                    Err(RuntimeError {
-                        message: format!("Expected boolean for unary NOT, got {:?}", val),
-                        stack_trace: vec![],
-                    })
+                    Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        format!("Expected boolean for unary NOT, got {:?}", val),
+                    ))
                 }
             }
             UnaryOp::Neg => match val {
-                Value::Int(i) => Ok(Value::Int(-i)),
                 Value::Float(f) => Ok(Value::Float(-f)),
-                _ => Err(RuntimeError {
-                    message: format!("Expected number for unary negation, got {:?}", val),
-                    stack_trace: vec![],
+                Value::Object(_) => {
+                    special::call(ctx, &val, special::NEG, &[]).unwrap_or_else(|| {
+                        Err(RuntimeError::new(
+                            ErrorKind::TypeError,
+                            format!("Expected number for unary negation, got {:?}", val),
+                        ))
+                    })
+                }
+                _ => numeric::negate(&val).ok_or_else(|| {
+                    RuntimeError::new(
+                        ErrorKind::TypeError,
+                        format!("Expected number for unary negation, got {:?}", val),
+                    )
                 }),
             },
+            UnaryOp::BitNot => match val {
+                Value::Int(n) => Ok(Value::Int(!n)),
+                Value::BigInt(n) => Ok(numeric::int_value(!n)),
+                _ => Err(RuntimeError::new(
+                    ErrorKind::TypeError,
+                    format!("Expected integer for bitwise NOT, got {:?}", val),
+                )),
+            },
         }
     }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
-        // Unary = UnaryOp Unary | Atom
-        // UnaryOp = [!] | [-]
+        // Unary = Not Unary | Neg Unary | BitNot Unary | Atom
+        // UnaryOp Unary also lands here: the parser resolves UnaryOp to its operator rule.
 
         // If we have 2 children, it's UnaryOp Unary
         if children.children.len() == 2 {
             let op_node = children.take_child("").unwrap();
             let expr = children.take_child("").unwrap();
 
-            let op_text = op_node.text().unwrap();
-            let op = match op_text.as_str() {
-                "!" => UnaryOp::Not,
-                "-" => UnaryOp::Neg,
-                _ => panic!("Unknown UnaryOp: {}", op_text),
+            let op = match op_node.rule() {
+                Some(Rule::Not) => UnaryOp::Not,
+                Some(Rule::Neg) => UnaryOp::Neg,
+                Some(Rule::BitNot) => UnaryOp::BitNot,
+                _ => panic!("Unknown UnaryOp rule: {:?}", op_node.rule()),
             };
 
             Box::new(Unary { op, expr })
@@ -62,6 +82,10 @@ impl Node for Unary {
         }
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.expr.as_ref()]
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(Unary {
             op: self.op, // UnaryOp is Copy
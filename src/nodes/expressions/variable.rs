@@ -1,7 +1,8 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
 use crate::node::{Context, Node, Value};
+use std::rc::Rc;
 
 pub struct Variable {
     pub name: String,
@@ -9,13 +10,15 @@ pub struct Variable {
 
 impl Node for Variable {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
-        if let Some(val) = ctx.variables.get(&self.name) {
-            Ok(val.clone())
+        if let Some(val) = ctx.get_variable(&self.name) {
+            Ok(val)
+        } else if let Some(func) = ctx.lookup_function(&self.name) {
+            Ok(Value::Function(Rc::new(func)))
         } else {
-            Err(RuntimeError {
-                message: format!("Variable '{}' not found", self.name),
-                stack_trace: vec![],
-            })
+            Err(RuntimeError::new(
+                ErrorKind::NameError,
+                format!("Variable '{}' not found", self.name),
+            ))
         }
     }
 
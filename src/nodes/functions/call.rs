@@ -0,0 +1,52 @@
+use crate::error::{ErrorKind, RuntimeError};
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Context, Node, Value};
+
+/// Calls the result of an arbitrary expression, e.g. `make_adder(1)(2)`.
+#[derive(Clone)]
+pub struct CallExpr {
+    pub callee: Box<dyn Node>,
+    pub args: Vec<Box<dyn Node>>,
+    pub line: usize,
+}
+
+impl Node for CallExpr {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        let callee = match self.callee.run(ctx)? {
+            Value::Function(callee) => callee,
+            _ => {
+                let mut err = RuntimeError::new(
+                    ErrorKind::TypeError,
+                    "Cannot call a value that is not a function",
+                );
+                err.push_frame("<call>", self.line);
+                return Err(err);
+            }
+        };
+
+        let mut arg_values = Vec::new();
+        for arg in &self.args {
+            arg_values.push(arg.run(ctx)?);
+        }
+
+        ctx.call_function(&callee, arg_values).map_err(|mut err| {
+            err.push_frame(callee.name(), self.line);
+            err
+        })
+    }
+
+    fn from_children(_rule: Rule, _children: ParsedChildren) -> Box<dyn Node> {
+        panic!("CallExpr is built by Postfix, not from children");
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        let mut children = vec![self.callee.as_ref()];
+        children.extend(self.args.iter().map(|a| a.as_ref()));
+        children
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+}
@@ -1,7 +1,7 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
-use crate::node::{Context, Node, Value};
+use crate::node::{Callable, Context, Node, Value};
 
 pub struct FunctionCall {
     pub name: String,
@@ -11,65 +11,44 @@ pub struct FunctionCall {
 
 impl Node for FunctionCall {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
-        // 1. Check built-ins
-        let builtin = ctx.builtins.get(&self.name).copied();
-        if let Some(func) = builtin {
-            let mut arg_values = Vec::new();
-            for arg in &self.args {
-                arg_values.push(arg.run(ctx)?);
+        // A variable holding a function is called directly. Otherwise the name
+        // goes through `lookup_function`, which tries built-ins before top-level
+        // functions, so a non-function variable only errors when neither exists.
+        let callee = match ctx.get_variable(&self.name) {
+            Some(Value::Function(callee)) => Some((*callee).clone()),
+            Some(_) if ctx.lookup_function(&self.name).is_none() => {
+                let mut err = RuntimeError::new(
+                    ErrorKind::TypeError,
+                    format!("'{}' is not a function", self.name),
+                );
+                err.push_frame(&self.name, self.line);
+                return Err(err);
             }
-            return func(arg_values);
-        }
-
-        // 2. Check user-defined functions
-        if let Some(func) = ctx.functions.get(&self.name) {
-            let func_params = func.params.clone();
-            let func_body = func.body.clone();
-
-            // Evaluate arguments in current context
-            let mut arg_values = Vec::new();
-            for arg in &self.args {
-                arg_values.push(arg.run(ctx)?);
-            }
-
-            if arg_values.len() != func_params.len() {
-                return Err(RuntimeError {
-                    message: format!(
-                        "Function '{}' expects {} arguments, got {}",
-                        self.name,
-                        func_params.len(),
-                        arg_values.len()
-                    ),
-                    stack_trace: vec![format!("at {}:{}", self.name, self.line)],
-                });
-            }
-
-            // Create new context for function execution
-            // We need to copy functions to the new context so it can call other functions
-            // Variables are NOT copied (scoping)
-            let mut new_ctx = Context::new();
-            new_ctx.functions = ctx.functions.clone(); // Shallow clone of HashMap, Rc are cheap
-            new_ctx.builtins = ctx.builtins.clone(); // Also copy builtins
+            _ => ctx.lookup_function(&self.name),
+        };
+        let Some(callee) = callee else {
+            let mut err = RuntimeError::new(
+                ErrorKind::NameError,
+                format!("Function '{}' not found", self.name),
+            );
+            err.push_frame(&self.name, self.line);
+            return Err(err);
+        };
 
-            // Bind arguments to parameters
-            for (param, value) in func_params.iter().zip(arg_values.into_iter()) {
-                new_ctx.variables.insert(param.clone(), value);
-            }
-
-            match func_body.run(&mut new_ctx) {
-                Ok(val) => Ok(val),
-                Err(mut err) => {
-                    err.stack_trace
-                        .push(format!("at {}:{}", self.name, self.line));
-                    Err(err)
-                }
-            }
-        } else {
-            Err(RuntimeError {
-                message: format!("Function '{}' not found", self.name),
-                stack_trace: vec![format!("at {}:{}", self.name, self.line)],
-            })
+        // Evaluate arguments in current context
+        let mut arg_values = Vec::new();
+        for arg in &self.args {
+            arg_values.push(arg.run(ctx)?);
         }
+
+        let result = match &callee {
+            Callable::BuiltIn(builtin) => builtin.call(ctx, &arg_values),
+            _ => ctx.call_function(&callee, arg_values),
+        };
+        result.map_err(|mut err| {
+            err.push_frame(&self.name, self.line);
+            err
+        })
     }
 
     fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
@@ -87,6 +66,10 @@ impl Node for FunctionCall {
         Box::new(FunctionCall { name, args, line })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        self.args.iter().map(|a| a.as_ref()).collect()
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(FunctionCall {
             name: self.name.clone(),
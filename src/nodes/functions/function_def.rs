@@ -1,7 +1,8 @@
+use crate::environment::Environment;
 use crate::error::RuntimeError;
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
-use crate::node::{Context, Node, Value};
+use crate::node::{Callable, Context, Node, Value};
 use std::rc::Rc;
 
 pub struct FunctionDef {
@@ -13,13 +14,23 @@ pub struct FunctionDef {
 
 impl Node for FunctionDef {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
-        ctx.functions.insert(
-            self.name.clone(),
-            crate::node::Function {
-                params: self.params.clone(),
-                body: self.body.clone(),
-            },
-        );
+        let function = crate::node::Function {
+            params: self.params.clone(),
+            body: self.body.clone(),
+            closure: Some(ctx.env.clone()),
+            generator: crate::generator::contains_yield(self.body.as_ref()),
+        };
+        // Top-level functions are global; nested ones are local to the
+        // enclosing scope like any other binding.
+        if Environment::is_top_level(&ctx.env) {
+            ctx.define_function(&self.name, function);
+        } else {
+            let callee = Callable::User {
+                name: Some(self.name.clone()),
+                function,
+            };
+            ctx.define_variable(&self.name, Value::Function(Rc::new(callee)));
+        }
         Ok(Value::Void)
     }
 
@@ -44,6 +55,10 @@ impl Node for FunctionDef {
         })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.body.as_ref()]
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(FunctionDef {
             name: self.name.clone(),
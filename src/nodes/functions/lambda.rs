@@ -0,0 +1,58 @@
+use crate::error::RuntimeError;
+use crate::grammar::Rule;
+use crate::node::ParsedChildren;
+use crate::node::{Callable, Context, Function, Node, Value};
+use std::rc::Rc;
+
+/// An anonymous function. Evaluates to a function value closing over the
+/// current environment; the body may be a block or a single expression.
+pub struct Lambda {
+    pub params: Vec<String>,
+    pub body: Rc<dyn Node>,
+    pub line: usize,
+}
+
+impl Node for Lambda {
+    fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        Ok(Value::Function(Rc::new(Callable::User {
+            name: None,
+            function: Function {
+                params: self.params.clone(),
+                body: self.body.clone(),
+                closure: Some(ctx.env.clone()),
+                generator: crate::generator::contains_yield(self.body.as_ref()),
+            },
+        })))
+    }
+
+    fn from_children(_rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        let line = children.line;
+
+        let mut params = Vec::new();
+        if let Some(param_list) = children.take_named("params") {
+            // A single parameter may be matched directly as an Identifier.
+            params = param_list
+                .params()
+                .unwrap_or_else(|| param_list.text().into_iter().collect());
+        }
+
+        let body = children.take_named("body").unwrap();
+        Box::new(Lambda {
+            params,
+            body: Rc::from(body),
+            line,
+        })
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.body.as_ref()]
+    }
+
+    fn box_clone(&self) -> Box<dyn Node> {
+        Box::new(Lambda {
+            params: self.params.clone(),
+            body: self.body.clone(),
+            line: self.line,
+        })
+    }
+}
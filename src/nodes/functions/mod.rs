@@ -1,7 +1,11 @@
 pub mod arg_list;
+pub mod call;
 pub mod function_call;
 pub mod function_def;
+pub mod lambda;
 
 pub use arg_list::ArgListNode;
+pub use call::CallExpr;
 pub use function_call::FunctionCall;
 pub use function_def::FunctionDef;
+pub use lambda::Lambda;
@@ -14,8 +14,14 @@ pub use classes::NewExpr;
 pub use classes::PostfixNode;
 pub use classes::PostfixSuffixNode;
 pub use classes::SelfReference;
-pub use control_flow::{Block, ForNode, If, Return, WhileNode};
-pub use expressions::{Assignment, Comparison, Factor, Logical, Term, Unary, Variable};
-pub use functions::{ArgListNode, FunctionCall, FunctionDef};
+pub use classes::SuperCall;
+pub use control_flow::{
+    Block, Break, Continue, ForNode, If, Return, Throw, TryCatch, WhileNode, Yield,
+};
+pub use expressions::{
+    Assignment, Bitwise, Comparison, CompoundAssignment, Factor, Index, Logical, Nonlocal, Power,
+    Slice, Term, Unary, Variable,
+};
+pub use functions::{ArgListNode, CallExpr, FunctionCall, FunctionDef, Lambda};
 pub use program::Program;
 pub use types::{ElementsNode, ListNode, Literal, MapEntriesNode, MapEntryNode, MapNode};
@@ -11,8 +11,10 @@ impl Node for Program {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
         let mut last_value = Value::Void;
         for stmt in &self.children {
-            // Changed from self.statements to self.children to match struct definition
             last_value = stmt.run(ctx)?;
+            if ctx.control_flow.is_some() {
+                break;
+            }
         }
         Ok(last_value)
     }
@@ -26,6 +28,10 @@ impl Node for Program {
         Box::new(Program { children })
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|c| c.as_ref()).collect()
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(Program {
             children: self.children.iter().map(|c| c.box_clone()).collect(),
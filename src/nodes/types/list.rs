@@ -1,4 +1,4 @@
-use crate::error::RuntimeError;
+use crate::error::{ErrorKind, RuntimeError};
 use crate::grammar::Rule;
 use crate::node::ParsedChildren;
 use crate::node::{Context, Node, Value};
@@ -15,10 +15,10 @@ pub struct ElementsNode {
 
 impl Node for ElementsNode {
     fn run(&self, _ctx: &mut Context) -> Result<Value, RuntimeError> {
-        Err(RuntimeError {
-            message: "ElementsNode should not be run directly".to_string(),
-            stack_trace: vec![],
-        })
+        Err(RuntimeError::new(
+            ErrorKind::Other,
+            "ElementsNode should not be run directly",
+        ))
     }
 
     fn from_children(rule: Rule, children: ParsedChildren) -> Box<dyn Node> {
@@ -61,6 +61,7 @@ impl Node for ElementsNode {
 
 impl Node for ListNode {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        ctx.allocate(self.elements.len())?;
         let mut values = Vec::new();
         for element in &self.elements {
             values.push(element.run(ctx)?);
@@ -84,6 +85,10 @@ impl Node for ListNode {
         }
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        self.elements.iter().map(|e| e.as_ref()).collect()
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(ListNode {
             elements: self.elements.iter().map(|e| e.box_clone()).collect(),
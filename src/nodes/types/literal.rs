@@ -2,11 +2,20 @@ use crate::grammar::Rule;
 
 use crate::error::RuntimeError;
 use crate::node::{Context, Node, ParsedChildren, Value};
+use crate::numeric;
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::str::FromStr;
 
 pub struct Literal {
     pub value: Value,
+    /// Why the matched text is not a valid literal; reported by the checks
+    /// that run after parsing.
+    pub error: Option<String>,
+    pub line: usize,
+    pub column: usize,
 }
 
 impl Node for Literal {
@@ -17,7 +26,9 @@ impl Node for Literal {
     fn text(&self) -> Option<String> {
         match &self.value {
             Value::Int(v) => Some(v.to_string()),
+            Value::BigInt(v) => Some(v.to_string()),
             Value::Float(v) => Some(v.to_string()),
+            Value::Decimal(v) => Some(v.to_string()),
             Value::String(v) => Some(format!("\"{}\"", v.borrow())), // Re-add quotes to match original token if possible, or just return content?
             // The parser expects the original token text including quotes for String literals in MapEntryNode logic.
             // However, we stripped quotes in from_children.
@@ -29,18 +40,44 @@ impl Node for Literal {
     }
 
     fn from_children(rule: Rule, mut children: ParsedChildren) -> Box<dyn Node> {
+        let (line, column) = (children.line, children.column);
         let child = children
             .take_child("")
             .or_else(|| children.remaining().into_iter().next().map(|(_, n)| n));
 
+        let mut error = None;
         let value = match rule {
             Rule::Int => {
                 let text = child.unwrap().text().unwrap_or_default();
-                Value::Int(text.parse().unwrap_or(0))
+                match text.parse::<BigInt>() {
+                    Ok(n) => numeric::int_value(n),
+                    Err(_) => {
+                        error = Some(format!("Invalid integer literal '{}'", text));
+                        Value::Void
+                    }
+                }
             }
             Rule::Float => {
                 let text = child.unwrap().text().unwrap_or_default();
-                Value::Float(text.parse().unwrap_or(0.0))
+                match text.parse::<f64>() {
+                    Ok(f) => Value::Float(f),
+                    Err(_) => {
+                        error = Some(format!("Invalid float literal '{}'", text));
+                        Value::Void
+                    }
+                }
+            }
+            Rule::Decimal => {
+                // Dialects mark decimals with a prefix or suffix, e.g. `9.99d`.
+                let text = child.unwrap().text().unwrap_or_default();
+                let digits = text.trim_matches(|c: char| !c.is_ascii_digit() && c != '.');
+                match Decimal::from_str(digits) {
+                    Ok(d) => Value::Decimal(d),
+                    Err(_) => {
+                        error = Some(format!("Invalid decimal literal '{}'", text));
+                        Value::Void
+                    }
+                }
             }
             Rule::String => {
                 let text = child.unwrap().text().unwrap_or_default();
@@ -57,12 +94,20 @@ impl Node for Literal {
             _ => panic!("Unknown rule for Literal: {:?}", rule),
         };
 
-        Box::new(Literal { value })
+        Box::new(Literal {
+            value,
+            error,
+            line,
+            column,
+        })
     }
 
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(Literal {
             value: self.value.clone(),
+            error: self.error.clone(),
+            line: self.line,
+            column: self.column,
         })
     }
 }
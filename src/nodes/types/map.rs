@@ -17,6 +17,7 @@ pub struct MapNode {
 
 impl Node for MapNode {
     fn run(&self, ctx: &mut Context) -> Result<Value, RuntimeError> {
+        ctx.allocate(self.entries.len())?;
         let mut map = HashMap::new();
         for (key, value_node) in &self.entries {
             let value = value_node.run(ctx)?;
@@ -54,6 +55,10 @@ impl Node for MapNode {
         }
     }
 
+    fn children(&self) -> Vec<&dyn Node> {
+        self.entries.iter().map(|(_, v)| v.as_ref()).collect()
+    }
+
     fn box_clone(&self) -> Box<dyn Node> {
         Box::new(self.clone())
     }
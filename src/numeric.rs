@@ -0,0 +1,319 @@
+//! Arithmetic shared by the operator nodes and the numeric built-ins.
+//!
+//! Integers overflow from `Int` into `BigInt` and shrink back to `Int` once
+//! they fit again. `Decimal` is exact and mixes with integers. Any operation
+//! involving a `Float` is done in floating point.
+
+use crate::error::{ErrorKind, RuntimeError};
+use crate::node::Value;
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+use rust_decimal::Decimal;
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    /// Truncates when both operands are integers.
+    Div,
+    /// Always produces a float for integer operands.
+    FloatDiv,
+    /// Quotient truncated towards zero, for every numeric type.
+    IntDiv,
+    Mod,
+}
+
+/// `n` as an `Int` if it fits, else as a `BigInt`.
+pub fn int_value(n: BigInt) -> Value {
+    match n.to_i64() {
+        Some(n) => Value::Int(n),
+        None => Value::BigInt(n),
+    }
+}
+
+pub fn to_big(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::Int(n) => Some(BigInt::from(*n)),
+        Value::BigInt(n) => Some(n.clone()),
+        _ => None,
+    }
+}
+
+/// Integers and decimals as a `Decimal`, if they fit in one.
+pub fn to_decimal(value: &Value) -> Option<Decimal> {
+    match value {
+        Value::Int(n) => Some(Decimal::from(*n)),
+        Value::BigInt(n) => Decimal::from_str(&n.to_string()).ok(),
+        Value::Decimal(d) => Some(*d),
+        _ => None,
+    }
+}
+
+pub fn to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int(n) => Some(*n as f64),
+        Value::BigInt(n) => n.to_f64(),
+        Value::Decimal(d) => d.to_f64(),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// The integer part of a float, or `None` for infinities and NaN.
+pub fn truncate_f64(f: f64) -> Option<Value> {
+    BigInt::from_f64(f.trunc()).map(int_value)
+}
+
+/// The integer part of a decimal.
+pub fn truncate_decimal(d: Decimal) -> Value {
+    let truncated = d.trunc();
+    match truncated.to_i64() {
+        Some(n) => Value::Int(n),
+        None => int_value(BigInt::from_str(&truncated.to_string()).unwrap_or_default()),
+    }
+}
+
+fn is_integer(value: &Value) -> bool {
+    matches!(value, Value::Int(_) | Value::BigInt(_))
+}
+
+/// Whether `value` is an integer or decimal, i.e. a number without
+/// rounding error.
+pub fn is_exact(value: &Value) -> bool {
+    matches!(value, Value::Int(_) | Value::BigInt(_) | Value::Decimal(_))
+}
+
+pub fn is_number(value: &Value) -> bool {
+    is_exact(value) || matches!(value, Value::Float(_))
+}
+
+/// Applies `op` to two numbers. Returns `None` if either operand is not a
+/// number, leaving it to the caller.
+pub fn arith(op: ArithOp, left: &Value, right: &Value) -> Option<Result<Value, RuntimeError>> {
+    if !is_number(left) || !is_number(right) {
+        return None;
+    }
+    if is_integer(left) && is_integer(right) {
+        return Some(integer_arith(op, left, right));
+    }
+    if matches!(left, Value::Float(_)) || matches!(right, Value::Float(_)) {
+        return Some(Ok(float_arith(op, to_f64(left)?, to_f64(right)?)));
+    }
+    if matches!(left, Value::Decimal(_)) || matches!(right, Value::Decimal(_)) {
+        let (l, r) = (to_decimal(left)?, to_decimal(right)?);
+        return Some(decimal_arith(op, l, r));
+    }
+    None
+}
+
+fn integer_arith(op: ArithOp, left: &Value, right: &Value) -> Result<Value, RuntimeError> {
+    if op == ArithOp::FloatDiv {
+        check_divisor(op, to_big(right).unwrap().is_zero())?;
+        return Ok(float_arith(
+            op,
+            to_f64(left).unwrap(),
+            to_f64(right).unwrap(),
+        ));
+    }
+    if let (Value::Int(l), Value::Int(r)) = (left, right) {
+        check_divisor(op, *r == 0)?;
+        let result = match op {
+            ArithOp::Add => l.checked_add(*r),
+            ArithOp::Sub => l.checked_sub(*r),
+            ArithOp::Mul => l.checked_mul(*r),
+            ArithOp::Div | ArithOp::FloatDiv | ArithOp::IntDiv => l.checked_div(*r),
+            ArithOp::Mod => l.checked_rem(*r),
+        };
+        if let Some(n) = result {
+            return Ok(Value::Int(n));
+        }
+    }
+
+    let (l, r) = (to_big(left).unwrap(), to_big(right).unwrap());
+    check_divisor(op, r.is_zero())?;
+    Ok(int_value(match op {
+        ArithOp::Add => l + r,
+        ArithOp::Sub => l - r,
+        ArithOp::Mul => l * r,
+        ArithOp::Div | ArithOp::FloatDiv | ArithOp::IntDiv => l / r,
+        ArithOp::Mod => l % r,
+    }))
+}
+
+/// Floats follow IEEE 754: dividing by zero gives an infinity or NaN.
+fn float_arith(op: ArithOp, l: f64, r: f64) -> Value {
+    Value::Float(match op {
+        ArithOp::Add => l + r,
+        ArithOp::Sub => l - r,
+        ArithOp::Mul => l * r,
+        ArithOp::Div | ArithOp::FloatDiv => l / r,
+        ArithOp::IntDiv => (l / r).trunc(),
+        ArithOp::Mod => l % r,
+    })
+}
+
+fn decimal_arith(op: ArithOp, l: Decimal, r: Decimal) -> Result<Value, RuntimeError> {
+    check_divisor(op, r.is_zero())?;
+    let (result, symbol) = match op {
+        ArithOp::Add => (l.checked_add(r), "+"),
+        ArithOp::Sub => (l.checked_sub(r), "-"),
+        ArithOp::Mul => (l.checked_mul(r), "*"),
+        ArithOp::Div | ArithOp::FloatDiv => (l.checked_div(r), "/"),
+        ArithOp::IntDiv => (l.checked_div(r).map(|q| q.trunc()), "//"),
+        ArithOp::Mod => (l.checked_rem(r), "%"),
+    };
+    result.map(Value::Decimal).ok_or_else(|| {
+        RuntimeError::new(
+            ErrorKind::Overflow,
+            format!("Decimal overflow in {} {} {}", l, symbol, r),
+        )
+    })
+}
+
+fn check_divisor(op: ArithOp, is_zero: bool) -> Result<(), RuntimeError> {
+    let message = match op {
+        ArithOp::Div | ArithOp::FloatDiv | ArithOp::IntDiv if is_zero => "Division by zero",
+        ArithOp::Mod if is_zero => "Modulo by zero",
+        _ => return Ok(()),
+    };
+    Err(RuntimeError::new(ErrorKind::ZeroDivision, message))
+}
+
+/// Raises `base` to `exponent`. Integers stay exact for non-negative integer
+/// exponents and decimals for integer exponents; anything else is done in
+/// floating point. Returns `None` if either operand is not a number.
+pub fn power(base: &Value, exponent: &Value) -> Option<Result<Value, RuntimeError>> {
+    if !is_number(base) || !is_number(exponent) {
+        return None;
+    }
+    let integral_exponent = match exponent {
+        Value::Int(_) | Value::BigInt(_) => to_big(exponent),
+        Value::Decimal(d) if d.fract().is_zero() => BigInt::from_str(&d.trunc().to_string()).ok(),
+        _ => None,
+    };
+    let Some(exp) = integral_exponent else {
+        return Some(Ok(Value::Float(to_f64(base)?.powf(to_f64(exponent)?))));
+    };
+    if matches!(base, Value::Float(_)) || (is_integer(base) && exp.is_negative()) {
+        return Some(Ok(Value::Float(to_f64(base)?.powf(to_f64(exponent)?))));
+    }
+    let Some(magnitude) = exp.magnitude().to_u32() else {
+        return Some(Err(RuntimeError::new(
+            ErrorKind::Overflow,
+            format!("Exponent {} is too large", exp),
+        )));
+    };
+    if is_integer(base) {
+        if let Value::Int(n) = base
+            && let Some(result) = n.checked_pow(magnitude)
+        {
+            return Some(Ok(Value::Int(result)));
+        }
+        return Some(Ok(int_value(to_big(base)?.pow(magnitude))));
+    }
+    Some(decimal_power(
+        to_decimal(base)?,
+        magnitude,
+        exp.is_negative(),
+    ))
+}
+
+/// Exponentiation by squaring, since `rust_decimal` only offers `powi` with
+/// its `maths` feature.
+fn decimal_power(base: Decimal, exponent: u32, invert: bool) -> Result<Value, RuntimeError> {
+    let overflow = || {
+        RuntimeError::new(
+            ErrorKind::Overflow,
+            format!("Decimal overflow in {} ** {}", base, exponent),
+        )
+    };
+    let (mut result, mut square, mut remaining) = (Decimal::ONE, base, exponent);
+    while remaining > 0 {
+        if remaining & 1 == 1 {
+            result = result.checked_mul(square).ok_or_else(overflow)?;
+        }
+        remaining >>= 1;
+        if remaining > 0 {
+            square = square.checked_mul(square).ok_or_else(overflow)?;
+        }
+    }
+    if invert {
+        check_divisor(ArithOp::Div, result.is_zero())?;
+        result = Decimal::ONE.checked_div(result).ok_or_else(overflow)?;
+    }
+    Ok(Value::Decimal(result))
+}
+
+/// Roughly how many bytes `base ** exponent` takes for integer operands, so
+/// callers can charge it to the memory budget (one unit per byte) before
+/// computing it.
+pub fn power_size(base: &Value, exponent: &Value) -> usize {
+    match (to_big(base), exponent) {
+        (Some(base), Value::Int(exp)) if *exp > 0 => (base.bits().saturating_mul(*exp as u64) / 8)
+            .try_into()
+            .unwrap_or(usize::MAX),
+        _ => 0,
+    }
+}
+
+/// Negates an integer or decimal; `None` for other types.
+pub fn negate(value: &Value) -> Option<Value> {
+    match value {
+        Value::Int(n) => Some(
+            n.checked_neg()
+                .map(Value::Int)
+                .unwrap_or_else(|| int_value(-BigInt::from(*n))),
+        ),
+        Value::BigInt(n) => Some(int_value(-n)),
+        Value::Decimal(d) => Some(Value::Decimal(-d)),
+        _ => None,
+    }
+}
+
+/// Absolute value of an integer or decimal; `None` for other types.
+pub fn abs(value: &Value) -> Option<Value> {
+    match value {
+        Value::Int(n) => Some(
+            n.checked_abs()
+                .map(Value::Int)
+                .unwrap_or_else(|| int_value(BigInt::from(*n).abs())),
+        ),
+        Value::BigInt(n) => Some(int_value(n.abs())),
+        Value::Decimal(d) => Some(Value::Decimal(d.abs())),
+        _ => None,
+    }
+}
+
+/// Orders two numbers of any numeric type. Integers and decimals compare
+/// exactly; comparisons with a float go through `f64`, with NaN equal to
+/// everything. `None` if either value is not a number.
+pub fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Int(l), Value::Int(r)) => Some(l.cmp(r)),
+        (Value::Float(_), _) | (_, Value::Float(_)) => {
+            let (l, r) = (to_f64(left)?, to_f64(right)?);
+            Some(l.partial_cmp(&r).unwrap_or(Ordering::Equal))
+        }
+        (Value::Decimal(_), _) | (_, Value::Decimal(_)) => {
+            match (to_decimal(left), to_decimal(right)) {
+                (Some(l), Some(r)) => Some(l.cmp(&r)),
+                // An integer too large for a decimal lies beyond its range.
+                (Some(_), None) => Some(beyond_decimal(right)?.reverse()),
+                (None, Some(_)) => beyond_decimal(left),
+                (None, None) => None,
+            }
+        }
+        _ => Some(to_big(left)?.cmp(&to_big(right)?)),
+    }
+}
+
+/// How an integer too large for a `Decimal` orders against any decimal.
+fn beyond_decimal(value: &Value) -> Option<Ordering> {
+    match to_big(value)?.is_positive() {
+        true => Some(Ordering::Greater),
+        false => Some(Ordering::Less),
+    }
+}
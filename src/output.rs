@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+/// Which stream a piece of output goes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Destination of everything a script prints. A `Context` owns one and
+/// shares it with the contexts of the calls it makes.
+pub trait Output {
+    fn write(&mut self, stream: Stream, text: &str) -> io::Result<()>;
+    fn flush(&mut self) -> io::Result<()>;
+}
+
+pub type SharedOutput = Rc<RefCell<dyn Output>>;
+
+// Buffered stdout is written out once it grows past this many bytes.
+const STDOUT_BUFFER_LIMIT: usize = 8 * 1024;
+
+/// Writes to the process's stdout and stderr. Stdout is buffered until
+/// `flush`, a write to stderr, or the buffer filling up.
+#[derive(Default)]
+pub struct StdOutput {
+    buffer: String,
+}
+
+impl Output for StdOutput {
+    fn write(&mut self, stream: Stream, text: &str) -> io::Result<()> {
+        match stream {
+            Stream::Stdout => {
+                self.buffer.push_str(text);
+                if self.buffer.len() >= STDOUT_BUFFER_LIMIT {
+                    self.flush()?;
+                }
+                Ok(())
+            }
+            Stream::Stderr => {
+                // Keep the relative order of the two streams.
+                self.flush()?;
+                io::stderr().write_all(text.as_bytes())
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        stdout.write_all(self.buffer.as_bytes())?;
+        self.buffer.clear();
+        stdout.flush()
+    }
+}
+
+impl Drop for StdOutput {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Keeps output in memory. Clones share the same buffers, so keep one to
+/// read what the script printed through the other.
+#[derive(Clone, Default)]
+pub struct CaptureOutput {
+    stdout: Rc<RefCell<String>>,
+    stderr: Rc<RefCell<String>>,
+}
+
+impl CaptureOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stdout(&self) -> String {
+        self.stdout.borrow().clone()
+    }
+
+    pub fn stderr(&self) -> String {
+        self.stderr.borrow().clone()
+    }
+
+    /// Returns and clears the captured stdout.
+    pub fn take_stdout(&self) -> String {
+        self.stdout.take()
+    }
+
+    /// Returns and clears the captured stderr.
+    pub fn take_stderr(&self) -> String {
+        self.stderr.take()
+    }
+}
+
+impl Output for CaptureOutput {
+    fn write(&mut self, stream: Stream, text: &str) -> io::Result<()> {
+        match stream {
+            Stream::Stdout => self.stdout.borrow_mut().push_str(text),
+            Stream::Stderr => self.stderr.borrow_mut().push_str(text),
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
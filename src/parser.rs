@@ -1,12 +1,14 @@
-use crate::error::{ParseError, RuntimeError};
+use crate::error::{ParseError, RuntimeError, Span};
 use crate::grammar::{Grammar, Pattern, Rule};
-use crate::node::{Node, ParsedChildren, Value};
+use crate::node::{Node, ParsedChildren, SpannedNode, Value};
 use crate::nodes::{
-    ArgListNode, Assignment, Block, ClassDef, Comparison, ElementsNode, Factor, FieldDef, ForNode,
-    FunctionCall, FunctionDef, If, ListNode, Literal, Logical, MapEntriesNode, MapEntryNode,
-    MapNode, MemberAccess, MethodCall, MethodDef, NewExpr, PostfixNode, PostfixSuffixNode, Program,
-    Return, SelfReference, Term, Unary, Variable, WhileNode,
+    ArgListNode, Assignment, Bitwise, Block, Break, ClassDef, Comparison, CompoundAssignment,
+    Continue, ElementsNode, Factor, FieldDef, ForNode, FunctionCall, FunctionDef, If, Lambda,
+    ListNode, Literal, Logical, MapEntriesNode, MapEntryNode, MapNode, MemberAccess, MethodCall,
+    MethodDef, NewExpr, Nonlocal, PostfixNode, PostfixSuffixNode, Power, Program, Return,
+    SelfReference, SuperCall, Term, Throw, TryCatch, Unary, Variable, WhileNode, Yield,
 };
+use crate::trace::{ParseTrace, TraceOutcome, Tracer};
 use regex::Regex;
 
 use std::cell::RefCell;
@@ -21,6 +23,10 @@ pub struct Parser<'a> {
     grammar: &'a Grammar,
     input: &'a str,
     cache: RefCell<HashMap<CacheKey, CacheEntry>>,
+    tracer: Option<RefCell<Tracer>>,
+    // A grammar mistake found while parsing, reported even if backtracking
+    // recovers from it.
+    grammar_error: RefCell<Option<ParseError>>,
 }
 
 impl<'a> Parser<'a> {
@@ -29,11 +35,33 @@ impl<'a> Parser<'a> {
             grammar,
             input,
             cache: RefCell::new(HashMap::new()),
+            tracer: None,
+            grammar_error: RefCell::new(None),
         }
     }
 
+    /// Enables recording of every rule attempt for grammar debugging.
+    pub fn with_trace(mut self) -> Self {
+        self.tracer = Some(RefCell::new(Tracer::default()));
+        self
+    }
+
+    /// Returns the trace recorded so far, if tracing is enabled.
+    pub fn trace(&self) -> Option<ParseTrace> {
+        self.tracer.as_ref().map(|t| t.borrow().snapshot())
+    }
+
+    fn trace_location(&self, pos: usize) -> (usize, usize) {
+        let (line, col, _) = self.get_location(pos);
+        (line, col)
+    }
+
     pub fn parse(&self, rule_name: Rule) -> Result<Box<dyn Node>, ParseError> {
-        let (node, pos) = self.parse_rule(rule_name, 0)?;
+        let result = self.parse_rule(rule_name, 0);
+        if let Some(err) = self.grammar_error.take() {
+            return Err(err);
+        }
+        let (node, pos) = result?;
         let final_pos = self.skip_whitespace(pos);
         if final_pos < self.input.len() {
             let (line, col, line_content) = self.get_location(final_pos);
@@ -44,6 +72,18 @@ impl<'a> Parser<'a> {
                 line_content,
             });
         }
+        if let Err(e) = crate::check::check(node.as_ref()) {
+            let line_content = self.input.lines().nth(e.line - 1).unwrap_or("");
+            let column = e
+                .column
+                .unwrap_or(line_content.len() - line_content.trim_start().len() + 1);
+            return Err(ParseError {
+                message: e.message,
+                line: e.line,
+                column,
+                line_content: line_content.to_string(),
+            });
+        }
         Ok(node)
     }
 
@@ -72,6 +112,23 @@ impl<'a> Parser<'a> {
         (line, col, line_content)
     }
 
+    /// Whether the literal `s` matches at `pos`. A literal ending in a word
+    /// character must not be followed by one, so `"return"` does not match
+    /// the start of `returned`.
+    fn matches_literal(&self, pos: usize, s: &str) -> bool {
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        if !self.input[pos..].starts_with(s) {
+            return false;
+        }
+        match s.chars().last() {
+            Some(last) if is_word(last) => !self.input[pos + s.len()..]
+                .chars()
+                .next()
+                .is_some_and(is_word),
+            _ => true,
+        }
+    }
+
     fn parse_rule(
         &self,
         rule_name: Rule,
@@ -80,6 +137,16 @@ impl<'a> Parser<'a> {
         // Check cache
         let key = (rule_name, pos);
         if let Some(cached) = self.cache.borrow().get(&key) {
+            if let Some(tracer) = &self.tracer {
+                tracer.borrow_mut().leaf(
+                    rule_name,
+                    pos,
+                    self.trace_location(pos),
+                    TraceOutcome::CacheHit {
+                        matched: cached.is_some(),
+                    },
+                );
+            }
             return match cached {
                 Some((node, new_pos)) => Ok((node.box_clone(), *new_pos)),
                 None => {
@@ -95,6 +162,14 @@ impl<'a> Parser<'a> {
         }
 
         let rules = self.grammar.rules.get(&rule_name).ok_or_else(|| {
+            if let Some(tracer) = &self.tracer {
+                tracer.borrow_mut().leaf(
+                    rule_name,
+                    pos,
+                    self.trace_location(pos),
+                    TraceOutcome::MissingRule,
+                );
+            }
             let (line, col, line_content) = self.get_location(pos);
             ParseError {
                 message: format!("Rule not found: {:?}", rule_name),
@@ -104,17 +179,39 @@ impl<'a> Parser<'a> {
             }
         })?;
 
-        for rule in rules {
-            match self.parse_sequence(&rule.patterns, pos) {
+        for (alternative, rule) in rules.iter().enumerate() {
+            if let Some(tracer) = &self.tracer {
+                tracer
+                    .borrow_mut()
+                    .enter(rule_name, pos, self.trace_location(pos), alternative);
+            }
+            let result = self.parse_sequence(&rule.patterns, pos);
+            if let Some(tracer) = &self.tracer {
+                tracer.borrow_mut().exit(match &result {
+                    Ok((_, end)) => TraceOutcome::Matched { end: *end },
+                    Err(_) => TraceOutcome::Failed,
+                });
+            }
+            match result {
                 Ok((children_with_names, new_pos)) => {
-                    let (line, _, _) = self.get_location(pos);
-                    let parsed_children = ParsedChildren::new(children_with_names, line);
+                    let (line, column, _) = self.get_location(pos);
+                    let spanned = records_span(rule_name, children_with_names.len());
+                    let parsed_children = ParsedChildren::new(children_with_names, line, column);
 
                     let node: Box<dyn Node> = match rule_name {
                         Rule::Program => Program::from_children(rule_name, parsed_children),
                         Rule::Stmt => parsed_children.remaining().into_iter().next().unwrap().1,
-                        Rule::Assignment => Assignment::from_children(rule_name, parsed_children),
+                        Rule::Assignment | Rule::Declaration => {
+                            Assignment::from_children(rule_name, parsed_children)
+                        }
+                        Rule::CompoundAssignment => {
+                            CompoundAssignment::from_children(rule_name, parsed_children)
+                        }
+                        Rule::Nonlocal => Nonlocal::from_children(rule_name, parsed_children),
                         Rule::Return => Return::from_children(rule_name, parsed_children),
+                        Rule::Yield => Yield::from_children(rule_name, parsed_children),
+                        Rule::Throw => Throw::from_children(rule_name, parsed_children),
+                        Rule::TryCatch => TryCatch::from_children(rule_name, parsed_children),
                         Rule::Comparison => Comparison::from_children(rule_name, parsed_children),
                         Rule::LogicalOr | Rule::LogicalAnd => {
                             Logical::from_children(rule_name, parsed_children)
@@ -122,16 +219,24 @@ impl<'a> Parser<'a> {
                         Rule::Term => Term::from_children(rule_name, parsed_children),
                         Rule::Factor => Factor::from_children(rule_name, parsed_children),
                         Rule::Unary => Unary::from_children(rule_name, parsed_children),
+                        Rule::BitwiseOr | Rule::BitwiseXor | Rule::BitwiseAnd | Rule::Shift => {
+                            Bitwise::from_children(rule_name, parsed_children)
+                        }
+                        Rule::Power => Power::from_children(rule_name, parsed_children),
                         Rule::IfElse | Rule::IfThen => {
                             If::from_children(rule_name, parsed_children)
                         }
-                        Rule::Int | Rule::Float | Rule::String | Rule::True | Rule::False => {
-                            Literal::from_children(rule_name, parsed_children)
-                        }
+                        Rule::Int
+                        | Rule::Float
+                        | Rule::Decimal
+                        | Rule::String
+                        | Rule::True
+                        | Rule::False => Literal::from_children(rule_name, parsed_children),
                         Rule::FunctionDef => FunctionDef::from_children(rule_name, parsed_children),
                         Rule::FunctionCall => {
                             FunctionCall::from_children(rule_name, parsed_children)
                         }
+                        Rule::Lambda => Lambda::from_children(rule_name, parsed_children),
                         Rule::ParamList | Rule::ArgList => {
                             ArgListNode::from_children(rule_name, parsed_children)
                         }
@@ -144,6 +249,8 @@ impl<'a> Parser<'a> {
                         Rule::MapEntry => MapEntryNode::from_children(rule_name, parsed_children),
                         Rule::ForLoop => ForNode::from_children(rule_name, parsed_children),
                         Rule::WhileLoop => WhileNode::from_children(rule_name, parsed_children),
+                        Rule::Break => Break::from_children(rule_name, parsed_children),
+                        Rule::Continue => Continue::from_children(rule_name, parsed_children),
                         Rule::Block => Block::from_children(rule_name, parsed_children),
                         Rule::Identifier => Variable::from_children(rule_name, parsed_children),
                         Rule::ClassDef => ClassDef::from_children(rule_name, parsed_children),
@@ -161,6 +268,7 @@ impl<'a> Parser<'a> {
                         Rule::SelfReference => {
                             SelfReference::from_children(rule_name, parsed_children)
                         }
+                        Rule::SuperCall => SuperCall::from_children(rule_name, parsed_children),
                         Rule::Postfix => PostfixNode::from_children(rule_name, parsed_children),
                         Rule::PostfixSuffix => {
                             PostfixSuffixNode::from_children(rule_name, parsed_children)
@@ -177,17 +285,52 @@ impl<'a> Parser<'a> {
                         Rule::Expr | Rule::Atom | Rule::If | Rule::Key => {
                             parsed_children.remaining().into_iter().next().unwrap().1
                         }
+                        Rule::UnaryOp => {
+                            // UnaryOp = Not | Neg | BitNot stands for the operator
+                            // rule it matched.
+                            let inner = parsed_children.remaining().into_iter().next().unwrap().1;
+                            if !matches!(inner.rule(), Some(Rule::Not | Rule::Neg | Rule::BitNot)) {
+                                let (line, column, line_content) = self.get_location(pos);
+                                let err = ParseError {
+                                    message: "UnaryOp must match one of Not, Neg or BitNot"
+                                        .to_string(),
+                                    line,
+                                    column,
+                                    line_content,
+                                };
+                                self.grammar_error.borrow_mut().get_or_insert(err.clone());
+                                return Err(err);
+                            }
+                            inner
+                        }
 
-                        Rule::UnaryOp
+                        Rule::Not
+                        | Rule::Neg
+                        | Rule::BitNot
                         | Rule::Eq
                         | Rule::Neq
                         | Rule::Lt
                         | Rule::Gt
+                        | Rule::Le
+                        | Rule::Ge
                         | Rule::Add
                         | Rule::Sub
                         | Rule::Mul
                         | Rule::Div
-                        | Rule::Mod => {
+                        | Rule::FloatDiv
+                        | Rule::IntDiv
+                        | Rule::Mod
+                        | Rule::Pow
+                        | Rule::BitAnd
+                        | Rule::BitOr
+                        | Rule::BitXor
+                        | Rule::Shl
+                        | Rule::Shr
+                        | Rule::AddAssign
+                        | Rule::SubAssign
+                        | Rule::MulAssign
+                        | Rule::DivAssign
+                        | Rule::ModAssign => {
                             let inner = parsed_children.remaining().into_iter().next().unwrap().1;
                             Box::new(crate::node::RuleNode {
                                 rule: rule_name,
@@ -195,6 +338,18 @@ impl<'a> Parser<'a> {
                             })
                         }
                     };
+                    let node = if spanned && !node.as_any().is::<SpannedNode>() {
+                        let (end_line, end_column, _) = self.get_location(new_pos);
+                        let span = Span {
+                            line: line as u32,
+                            column: column as u32,
+                            end_line: end_line as u32,
+                            end_column: end_column as u32,
+                        };
+                        Box::new(SpannedNode { span, inner: node })
+                    } else {
+                        node
+                    };
 
                     // Cache success
                     self.cache
@@ -225,7 +380,7 @@ impl<'a> Parser<'a> {
             match pattern {
                 Pattern::Literal(s) => {
                     let len = s.len();
-                    if self.input[pos..].starts_with(s) {
+                    if self.matches_literal(pos, s) {
                         pos += len;
                     } else {
                         let (line, col, line_content) = self.get_location(pos);
@@ -289,7 +444,7 @@ impl<'a> Parser<'a> {
                 Pattern::Named(name, sub_pattern) => match &**sub_pattern {
                     Pattern::Literal(s) => {
                         let len = s.len();
-                        if self.input[pos..].starts_with(s) {
+                        if self.matches_literal(pos, s) {
                             pos += len;
                         } else {
                             let (line, col, line_content) = self.get_location(pos);
@@ -436,3 +591,25 @@ impl Node for RawTokenNode {
         })
     }
 }
+
+/// Whether errors raised by the node built for `rule` should carry its source
+/// span. Statements and calls always do; operator rules only when they
+/// applied an operator rather than passing a single operand through.
+fn records_span(rule: Rule, child_count: usize) -> bool {
+    match rule {
+        Rule::Stmt | Rule::FunctionCall => true,
+        Rule::LogicalOr
+        | Rule::LogicalAnd
+        | Rule::Comparison
+        | Rule::BitwiseOr
+        | Rule::BitwiseXor
+        | Rule::BitwiseAnd
+        | Rule::Shift
+        | Rule::Term
+        | Rule::Factor
+        | Rule::Unary
+        | Rule::Power
+        | Rule::Postfix => child_count > 1,
+        _ => false,
+    }
+}
@@ -0,0 +1,68 @@
+//! Special methods. An object whose class (or an ancestor) defines a method
+//! with one of these names takes part in the matching operator or built-in.
+
+use crate::error::RuntimeError;
+use crate::node::{Callable, Context, Value};
+
+/// `a + b`
+pub const ADD: &str = "__add__";
+/// `a - b`
+pub const SUB: &str = "__sub__";
+/// `a * b`
+pub const MUL: &str = "__mul__";
+/// `a / b`, for both `Div` and `FloatDiv`.
+pub const DIV: &str = "__div__";
+/// `IntDiv`
+pub const INT_DIV: &str = "__intdiv__";
+/// `a % b`
+pub const MOD: &str = "__mod__";
+/// `-a`
+pub const NEG: &str = "__neg__";
+/// `a == b` and `a != b`.
+pub const EQ: &str = "__eq__";
+/// `a < b`; `a > b` is `b < a`, `a <= b` is not `b < a` and `a >= b` is
+/// not `a < b`.
+pub const LT: &str = "__lt__";
+/// Text shown by `print`, `eprint` and `throw`.
+pub const STR: &str = "__str__";
+/// `len(a)`
+pub const LEN: &str = "__len__";
+/// What a `for` loop iterates over instead of the object: a list, string,
+/// map or iterator object.
+pub const ITER: &str = "__iter__";
+/// The next item of an iterator object, or void once it is exhausted.
+pub const NEXT: &str = "__next__";
+/// `a[i]`
+pub const GET_ITEM: &str = "__getitem__";
+/// `a[i] = v`
+pub const SET_ITEM: &str = "__setitem__";
+
+/// Whether `value` is an object whose class defines the special method `name`.
+pub fn has(ctx: &Context, value: &Value, name: &str) -> bool {
+    match value {
+        Value::Object(object) => ctx.find_method(&object.borrow().class_name, name).is_some(),
+        _ => false,
+    }
+}
+
+/// Calls the special method `name` on `value` with `args`. Returns `None` if
+/// `value` is not an object or its class does not define the method.
+pub fn call(
+    ctx: &mut Context,
+    value: &Value,
+    name: &str,
+    args: &[Value],
+) -> Option<Result<Value, RuntimeError>> {
+    let Value::Object(object) = value else {
+        return None;
+    };
+    let class_name = object.borrow().class_name.clone();
+    let (class, function) = ctx.find_method(&class_name, name)?;
+    let callee = Callable::BoundMethod {
+        object: object.clone(),
+        class,
+        name: name.to_string(),
+        function,
+    };
+    Some(ctx.call_function(&callee, args.to_vec()))
+}
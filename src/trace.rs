@@ -0,0 +1,301 @@
+use crate::grammar::Rule;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Result of a single traced `parse_rule` attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceOutcome {
+    /// The alternative matched and consumed input up to `end`.
+    Matched { end: usize },
+    /// The alternative did not match.
+    Failed,
+    /// The result was served from the memoization cache.
+    CacheHit { matched: bool },
+    /// The rule has no productions in the grammar.
+    MissingRule,
+}
+
+/// One node of the parse trace tree.
+///
+/// Every alternative the parser tries becomes an entry; the nested rules it
+/// attempted while matching that alternative become its children. Cache hits
+/// and missing rules are leaves without an alternative index.
+#[derive(Debug, Clone)]
+pub struct TraceEntry {
+    pub rule: Rule,
+    pub pos: usize,
+    pub line: usize,
+    pub column: usize,
+    pub alternative: Option<usize>,
+    pub outcome: TraceOutcome,
+    pub children: Vec<TraceEntry>,
+}
+
+impl TraceEntry {
+    fn render(&self, depth: usize, out: &mut String) {
+        let status = match self.outcome {
+            TraceOutcome::Matched { end } => format!("ok -> {}", end),
+            TraceOutcome::Failed => "fail".to_string(),
+            TraceOutcome::CacheHit { matched: true } => "cache hit (ok)".to_string(),
+            TraceOutcome::CacheHit { matched: false } => "cache hit (fail)".to_string(),
+            TraceOutcome::MissingRule => "rule not found".to_string(),
+        };
+        let alternative = self
+            .alternative
+            .map(|i| format!(" #{}", i))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "{}{:?}{} @ {}:{} {}\n",
+            "  ".repeat(depth),
+            self.rule,
+            alternative,
+            self.line,
+            self.column,
+            status
+        ));
+        for child in &self.children {
+            child.render(depth + 1, out);
+        }
+    }
+
+    fn write_json(&self, out: &mut String) {
+        let (outcome, end, cached) = match self.outcome {
+            TraceOutcome::Matched { end } => ("matched", Some(end), false),
+            TraceOutcome::Failed => ("failed", None, false),
+            TraceOutcome::CacheHit { matched: true } => ("matched", None, true),
+            TraceOutcome::CacheHit { matched: false } => ("failed", None, true),
+            TraceOutcome::MissingRule => ("missing_rule", None, false),
+        };
+        out.push_str(&format!(
+            "{{\"rule\":\"{:?}\",\"pos\":{},\"line\":{},\"column\":{},",
+            self.rule, self.pos, self.line, self.column
+        ));
+        match self.alternative {
+            Some(i) => out.push_str(&format!("\"alternative\":{},", i)),
+            None => out.push_str("\"alternative\":null,"),
+        }
+        out.push_str(&format!(
+            "\"outcome\":\"{}\",\"cached\":{},",
+            outcome, cached
+        ));
+        match end {
+            Some(end) => out.push_str(&format!("\"end\":{},", end)),
+            None => out.push_str("\"end\":null,"),
+        }
+        out.push_str("\"children\":[");
+        for (i, child) in self.children.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            child.write_json(out);
+        }
+        out.push_str("]}");
+    }
+}
+
+/// Complete trace of a parse, as recorded by `Parser::with_trace`.
+#[derive(Debug, Clone, Default)]
+pub struct ParseTrace {
+    pub roots: Vec<TraceEntry>,
+}
+
+impl ParseTrace {
+    /// Renders the trace as an indented tree, one attempt per line.
+    pub fn render_tree(&self) -> String {
+        let mut out = String::new();
+        for root in &self.roots {
+            root.render(0, &mut out);
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"trace\":[");
+        for (i, root) in self.roots.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            root.write_json(&mut out);
+        }
+        out.push_str("],\"summary\":");
+        self.summary().write_json(&mut out);
+        out.push('}');
+        out
+    }
+
+    pub fn summary(&self) -> TraceSummary {
+        let mut stats: HashMap<Rule, RuleStats> = HashMap::new();
+        let mut lookups = 0;
+        let mut cache_hits = 0;
+
+        let mut pending: Vec<&TraceEntry> = self.roots.iter().collect();
+        while let Some(entry) = pending.pop() {
+            let rule_stats = stats.entry(entry.rule).or_insert(RuleStats {
+                rule: entry.rule,
+                attempts: 0,
+                matches: 0,
+                cache_hits: 0,
+            });
+            match entry.outcome {
+                TraceOutcome::CacheHit { .. } => {
+                    lookups += 1;
+                    cache_hits += 1;
+                    rule_stats.cache_hits += 1;
+                }
+                TraceOutcome::Matched { .. } => {
+                    rule_stats.attempts += 1;
+                    rule_stats.matches += 1;
+                }
+                TraceOutcome::Failed | TraceOutcome::MissingRule => {
+                    rule_stats.attempts += 1;
+                }
+            }
+            // The first alternative of a rule marks one uncached lookup.
+            if matches!(entry.alternative, Some(0)) || entry.outcome == TraceOutcome::MissingRule {
+                lookups += 1;
+            }
+            pending.extend(entry.children.iter());
+        }
+
+        let mut rules: Vec<RuleStats> = stats.into_values().collect();
+        rules.sort_by(|a, b| {
+            (b.attempts + b.cache_hits)
+                .cmp(&(a.attempts + a.cache_hits))
+                .then_with(|| format!("{:?}", a.rule).cmp(&format!("{:?}", b.rule)))
+        });
+
+        TraceSummary {
+            rules,
+            lookups,
+            cache_hits,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RuleStats {
+    pub rule: Rule,
+    /// Alternatives tried for this rule, excluding cache hits.
+    pub attempts: usize,
+    /// Alternatives that matched.
+    pub matches: usize,
+    pub cache_hits: usize,
+}
+
+/// Aggregated statistics over a `ParseTrace`, hottest rules first.
+#[derive(Debug, Clone)]
+pub struct TraceSummary {
+    pub rules: Vec<RuleStats>,
+    /// Number of `parse_rule` calls, cached or not.
+    pub lookups: usize,
+    pub cache_hits: usize,
+}
+
+impl TraceSummary {
+    pub fn cache_hit_rate(&self) -> f64 {
+        if self.lookups == 0 {
+            0.0
+        } else {
+            self.cache_hits as f64 / self.lookups as f64
+        }
+    }
+
+    fn write_json(&self, out: &mut String) {
+        out.push_str(&format!(
+            "{{\"lookups\":{},\"cache_hits\":{},\"cache_hit_rate\":{},\"rules\":[",
+            self.lookups,
+            self.cache_hits,
+            self.cache_hit_rate()
+        ));
+        for (i, stats) in self.rules.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str(&format!(
+                "{{\"rule\":\"{:?}\",\"attempts\":{},\"matches\":{},\"cache_hits\":{}}}",
+                stats.rule, stats.attempts, stats.matches, stats.cache_hits
+            ));
+        }
+        out.push_str("]}");
+    }
+}
+
+impl fmt::Display for TraceSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Rule lookups: {}, cache hits: {} ({:.1}%)",
+            self.lookups,
+            self.cache_hits,
+            self.cache_hit_rate() * 100.0
+        )?;
+        writeln!(f, "Hottest rules:")?;
+        for stats in self.rules.iter().take(10) {
+            writeln!(
+                f,
+                "  {:?}: {} attempts, {} matched, {} cache hits",
+                stats.rule, stats.attempts, stats.matches, stats.cache_hits
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds the trace tree while the parser recurses.
+#[derive(Default)]
+pub(crate) struct Tracer {
+    stack: Vec<TraceEntry>,
+    roots: Vec<TraceEntry>,
+}
+
+impl Tracer {
+    pub(crate) fn enter(&mut self, rule: Rule, pos: usize, location: (usize, usize), alt: usize) {
+        self.stack.push(TraceEntry {
+            rule,
+            pos,
+            line: location.0,
+            column: location.1,
+            alternative: Some(alt),
+            outcome: TraceOutcome::Failed,
+            children: Vec::new(),
+        });
+    }
+
+    pub(crate) fn exit(&mut self, outcome: TraceOutcome) {
+        if let Some(mut entry) = self.stack.pop() {
+            entry.outcome = outcome;
+            self.attach(entry);
+        }
+    }
+
+    pub(crate) fn leaf(
+        &mut self,
+        rule: Rule,
+        pos: usize,
+        location: (usize, usize),
+        outcome: TraceOutcome,
+    ) {
+        self.attach(TraceEntry {
+            rule,
+            pos,
+            line: location.0,
+            column: location.1,
+            alternative: None,
+            outcome,
+            children: Vec::new(),
+        });
+    }
+
+    fn attach(&mut self, entry: TraceEntry) {
+        match self.stack.last_mut() {
+            Some(parent) => parent.children.push(entry),
+            None => self.roots.push(entry),
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> ParseTrace {
+        ParseTrace {
+            roots: self.roots.clone(),
+        }
+    }
+}
@@ -154,7 +154,8 @@ fn test_negative_numbers() {
         Unary = UnaryOp Unary | Atom
         Atom = Float | Int | "(" Expr ")"
         
-        UnaryOp = [-]
+        UnaryOp = Neg
+        Neg = [-]
         Add = [\+]
         Sub = [-]
         Mul = [\*]
@@ -277,7 +278,7 @@ fn test_configurable_arithmetic() {
         String = ["[^"]*"]
         Identifier = [[a-zA-Z_][a-zA-Z0-9_]*]
     "#;
-    let grammar = Grammar::parse(&grammar_def);
+    let grammar = Grammar::parse(grammar_def);
 
     // Test "plus"
     let code = "print(4 plus 5)";
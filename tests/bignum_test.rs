@@ -0,0 +1,122 @@
+use multilang::error::{ErrorKind, InterpreterError};
+use multilang::grammar::Grammar;
+use multilang::interpreter::Interpreter;
+use multilang::node::Value;
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
+use std::fs;
+use std::str::FromStr;
+
+use crate::test_utils::run_code_and_check;
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&fs::read_to_string(GRAMMAR).unwrap())
+}
+
+#[test]
+fn test_factorial_and_fibonacci() {
+    let code = r#"
+        fn factorial(n) {
+            if n == 0 {
+                return 1
+            }
+            return n * factorial(n - 1)
+        }
+        print(factorial(25))
+        print(factorial(30) / factorial(28))
+
+        a = 0
+        b = 1
+        i = 0
+        while i < 100 {
+            t = a + b
+            a = b
+            b = t
+            i = i + 1
+        }
+        print(a)
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "15511210043330985984000000\n870\n354224848179261915075",
+    );
+}
+
+#[test]
+fn test_big_int_literals_compare_and_sort() {
+    let code = r#"
+        big = 100000000000000000000
+        print(big)
+        print(big > 5)
+        print(big == 100000000000000000000)
+        print(0 - big < 0 - 5)
+        xs = [big, 3, 0 - big, 2.5]
+        sort(xs)
+        print(xs)
+        print(to_int("123456789012345678901234567890"))
+        print(to_float(big))
+        print(to_int(to_float("1e20")))
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "100000000000000000000\ntrue\ntrue\ntrue\n[-100000000000000000000, 2.5, 3, 100000000000000000000]\n123456789012345678901234567890\n100000000000000000000\n100000000000000000000",
+    );
+}
+
+#[test]
+fn test_decimal_arithmetic_is_exact() {
+    let code = r#"
+        price = 19.99d
+        print(price * 3)
+        print(0.1d + 0.2d)
+        print(0.1d + 0.2d == 0.3d)
+        print(10.00d / 4)
+        print(price - 20)
+        print(0 - 1.50d)
+        print(sum([1.25d, 2, 0.75d]))
+        print(abs(0 - 2.5d))
+        print(to_int(7.99d))
+        print(to_float(0.5d))
+        print(1.5d > 1)
+        print(max_by([2.5d, 1, 3.25d], fn(x) { x }))
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "59.97\n0.3\ntrue\n2.50\n-0.01\n-1.50\n4.00\n2.5\n7\n0.5\ntrue\n3.25",
+    );
+}
+
+#[test]
+fn test_decimal_errors() {
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    match interp.eval("1.5d / 0") {
+        Err(InterpreterError::Runtime(e)) => assert_eq!(e.message, "Division by zero"),
+        other => panic!("Expected runtime error, got {:?}", other),
+    }
+    match interp.eval("m = 79228162514264337593543950335.0d\nm * 2") {
+        Err(InterpreterError::Runtime(e)) => assert_eq!(e.kind, ErrorKind::Overflow),
+        other => panic!("Expected runtime error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_conversions() {
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    let big: BigInt = interp.eval_as("2 * 9223372036854775807").unwrap();
+    assert_eq!(big, BigInt::from(i64::MAX) * 2);
+    let small: BigInt = interp.eval_as("7").unwrap();
+    assert_eq!(small, BigInt::from(7));
+
+    let price: Decimal = interp.eval_as("4.10d + 1").unwrap();
+    assert_eq!(price, Decimal::from_str("5.10").unwrap());
+
+    interp.set_global("huge", BigInt::from(i64::MAX) + 1);
+    assert_eq!(interp.eval("huge - 1").unwrap(), Value::Int(i64::MAX));
+}
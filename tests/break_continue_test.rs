@@ -0,0 +1,237 @@
+use multilang::grammar::{Grammar, Rule};
+use multilang::parser::Parser;
+use std::fs;
+
+use crate::test_utils::{run_code_and_check, standard_grammar};
+
+mod test_utils;
+
+fn get_grammar() -> Grammar {
+    let grammar_def = r##"
+        Program = Stmt*
+        Stmt = Break | Continue | FunctionDef | WhileLoop | ForLoop | IfThen | FunctionCall | Assignment | Expr
+
+        Break = "break" "@" label:Identifier | "break"
+        Continue = "continue" "@" label:Identifier | "continue"
+
+        WhileLoop = label:Identifier ":" "while" condition:Expr "{" body:Block "}"
+        WhileLoop = "while" condition:Expr "{" body:Block "}"
+        ForLoop = label:Identifier ":" "for" variable:Identifier "in" iterable:Expr "{" body:Block "}"
+        ForLoop = "for" variable:Identifier "in" iterable:Expr "{" body:Block "}"
+        IfThen = "if" condition:Expr "{" then:Block "}"
+
+        FunctionDef = "fn" name:Identifier "(" ")" "{" body:Block "}"
+        Block = Stmt*
+
+        FunctionCall = name:Identifier "(" args:ArgList ")"
+        FunctionCall = name:Identifier "(" ")"
+        ArgList = Expr "," args:ArgList
+        ArgList = Expr
+
+        Assignment = name:Identifier "=" value:Expr
+
+        Expr = Comparison
+        Comparison = Term Eq Term | Term Gt Term | Term
+        Term = Factor Add Term | Factor
+        Factor = Atom Mod Factor | Atom
+        Atom = Int | FunctionCall | Identifier | ListLiteral
+
+        ListLiteral = "[" Elements "]"
+        Elements = Expr "," Elements
+        Elements = Expr
+
+        Eq = [==]
+        Gt = [>]
+        Add = [\+]
+        Mod = [%]
+
+        Int = [[0-9]+]
+        Identifier = [[a-zA-Z_][a-zA-Z0-9_]*]
+    "##;
+    Grammar::parse(grammar_def)
+}
+
+#[test]
+fn test_break_while() {
+    let code = r#"
+        i = 0
+        while 1 {
+            if i == 3 {
+                break
+            }
+            print(i)
+            i = i + 1
+        }
+    "#;
+    run_code_and_check(&get_grammar(), code, "0\n1\n2");
+}
+
+#[test]
+fn test_continue_for() {
+    let code = r#"
+        for x in [1, 2, 3, 4, 5] {
+            if x % 2 == 0 {
+                continue
+            }
+            print(x)
+        }
+    "#;
+    run_code_and_check(&get_grammar(), code, "1\n3\n5");
+}
+
+#[test]
+fn test_break_only_exits_innermost_loop() {
+    let code = r#"
+        for x in [1, 2] {
+            for y in [10, 20, 30] {
+                if y > 10 {
+                    break
+                }
+                print(x + y)
+            }
+        }
+    "#;
+    run_code_and_check(&get_grammar(), code, "11\n12");
+}
+
+#[test]
+fn test_labelled_break() {
+    let code = r#"
+        outer: for x in [1, 2, 3] {
+            for y in [10, 20] {
+                if x == 2 {
+                    break @outer
+                }
+                print(x + y)
+            }
+        }
+        print(0)
+    "#;
+    run_code_and_check(&get_grammar(), code, "11\n21\n0");
+}
+
+#[test]
+fn test_labelled_continue() {
+    let code = r#"
+        outer: for i in [1, 2, 3] {
+            for y in [10, 20] {
+                if y == 20 {
+                    continue @outer
+                }
+                print(i + y)
+            }
+            print(0)
+        }
+    "#;
+    // The labelled continue skips the trailing print in every iteration.
+    run_code_and_check(&get_grammar(), code, "11\n12\n13");
+}
+
+#[test]
+fn test_break_outside_loop_is_parse_error() {
+    let grammar = get_grammar();
+    let code = "x = 1\nbreak\n";
+    let err = Parser::new(&grammar, code)
+        .parse(Rule::Program)
+        .err()
+        .expect("Expected parse error");
+    assert_eq!(err.message, "Break used outside of a loop");
+    assert_eq!(err.line, 2);
+}
+
+#[test]
+fn test_continue_in_function_inside_loop_is_parse_error() {
+    let grammar = get_grammar();
+    let code = r#"
+        while 1 {
+            fn helper() {
+                continue
+            }
+        }
+    "#;
+    let err = Parser::new(&grammar, code)
+        .parse(Rule::Program)
+        .err()
+        .expect("Expected parse error");
+    assert_eq!(err.message, "Continue used outside of a loop");
+}
+
+#[test]
+fn test_unknown_label_is_parse_error() {
+    let grammar = get_grammar();
+    let code = r#"
+        inner: while 1 {
+            break @outer
+        }
+    "#;
+    let err = Parser::new(&grammar, code)
+        .parse(Rule::Program)
+        .err()
+        .expect("Expected parse error");
+    assert_eq!(err.message, "Break refers to unknown loop label 'outer'");
+}
+
+#[test]
+fn test_dialect_keywords() {
+    let code = r#"
+        i = 0
+        while i less 10 do
+            i = i add 1
+            if i equals 2 then
+                skip
+            endif
+            if i equals 4 then
+                stop
+            endif
+            print(i)
+        end
+    "#;
+    let grammar_def = fs::read_to_string("tests/resources/wordy/grammar.mlg").unwrap();
+    run_code_and_check(&Grammar::parse(&grammar_def), code, "1\n3");
+
+    let code = r#"
+        i <- 0
+        while i < 10 =>
+            i <- i + 1
+            if i == 2 => >> ;
+            if i == 4 => !! ;
+            print#i$
+        ;
+    "#;
+    let grammar_def = fs::read_to_string("tests/resources/cryptic/grammar.mlg").unwrap();
+    run_code_and_check(&Grammar::parse(&grammar_def), code, "1\n3");
+}
+
+#[test]
+fn test_identifiers_starting_with_keywords() {
+    let code = r#"
+        breakfast = 0
+        continued = 0
+        i = 0
+        while 3 > i {
+            i = i + 1
+            breakfast = breakfast + i
+            continued = continued + 1
+        }
+        print(breakfast)
+        print(continued)
+    "#;
+    run_code_and_check(&get_grammar(), code, "6\n3");
+
+    let code = r#"
+        stopwatch = 1
+        skipped = stopwatch add 1
+        print(skipped)
+    "#;
+    let grammar_def = fs::read_to_string("tests/resources/wordy/grammar.mlg").unwrap();
+    run_code_and_check(&Grammar::parse(&grammar_def), code, "2");
+
+    let code = r#"
+        fn f() {
+            returned = 5
+            return returned
+        }
+        print(f())
+    "#;
+    run_code_and_check(&Grammar::parse(&standard_grammar()), code, "5");
+}
@@ -0,0 +1,140 @@
+use multilang::budget::Limits;
+use multilang::error::{ErrorKind, InterpreterError};
+use multilang::interpreter::Interpreter;
+use std::time::Duration;
+
+use crate::test_utils::standard_grammar_with_for;
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn run_limited(limits: Limits, code: &str) -> (Result<(), ErrorKind>, Interpreter) {
+    let mut interp = Interpreter::from_grammar_str(&standard_grammar_with_for());
+    interp.capture_output();
+    interp.set_limits(limits);
+    let result = match interp.eval(code) {
+        Ok(_) => Ok(()),
+        Err(InterpreterError::Runtime(e)) => Err(e.kind),
+        Err(e) => panic!("Unexpected error: {}", e),
+    };
+    (result, interp)
+}
+
+#[test]
+fn test_step_limit() {
+    let limits = Limits {
+        max_steps: Some(100),
+        ..Limits::default()
+    };
+    let (result, mut interp) = run_limited(limits, "while 1 == 1 {\n x = 1\n}");
+    assert_eq!(result, Err(ErrorKind::StepLimit));
+    assert_eq!(interp.context().budget.borrow().steps(), 101);
+
+    let (result, _) = run_limited(limits, "for x in range(99) {\n y = x\n}");
+    assert_eq!(result, Ok(()));
+    let (result, _) = run_limited(limits, "for x in range(101) {\n y = x\n}");
+    assert_eq!(result, Err(ErrorKind::StepLimit));
+}
+
+#[test]
+fn test_calls_count_as_steps() {
+    let limits = Limits {
+        max_steps: Some(50),
+        ..Limits::default()
+    };
+    let code = "fn f(n) {\n return n\n}\ni = 0\nwhile i < 30 {\n f(i)\n i = i + 1\n}";
+    let (result, _) = run_limited(limits, code);
+    assert_eq!(result, Err(ErrorKind::StepLimit));
+}
+
+#[test]
+fn test_time_limit() {
+    let limits = Limits {
+        timeout: Some(Duration::from_millis(50)),
+        ..Limits::default()
+    };
+    let (result, _) = run_limited(limits, "while 1 == 1 {\n x = 1\n}");
+    assert_eq!(result, Err(ErrorKind::TimeLimit));
+}
+
+#[test]
+fn test_memory_limit() {
+    let limits = Limits {
+        max_memory: Some(1000),
+        ..Limits::default()
+    };
+    let (result, _) = run_limited(limits, "xs = range(1000000)");
+    assert_eq!(result, Err(ErrorKind::MemoryLimit));
+
+    let (result, _) = run_limited(limits, "s = \"ab\"\nwhile 1 == 1 {\n s = s + s\n}");
+    assert_eq!(result, Err(ErrorKind::MemoryLimit));
+
+    let (result, mut interp) = run_limited(
+        limits,
+        "xs = [1, 2, 3]\nappend(xs, 4)\nys = map(xs, fn(x) { x })",
+    );
+    assert_eq!(result, Ok(()));
+    assert_eq!(interp.context().budget.borrow().memory(), 8);
+}
+
+#[test]
+fn test_memory_units() {
+    // One unit per list element or map entry, and per byte of string or
+    // big integer data.
+    let cases = [
+        ("xs = range(5)", 5),
+        ("xs = [1, 2, 3, 4]\nys = xs[1:3]", 6),
+        ("m = {\"a\": 1, \"b\": 2}", 2),
+        ("s = \"abc\" + \"de\"", 5),
+        ("s = \"hello\"[1:4]", 3),
+        ("n = 2 ** 64", 16),
+    ];
+    for (code, units) in cases {
+        let (result, mut interp) = run_limited(Limits::default(), code);
+        assert_eq!(result, Ok(()), "{}", code);
+        assert_eq!(interp.context().budget.borrow().memory(), units, "{}", code);
+    }
+}
+
+#[test]
+fn test_output_limit() {
+    let limits = Limits {
+        max_output: Some(10),
+        ..Limits::default()
+    };
+    let (result, mut interp) =
+        run_limited(limits, "i = 0\nwhile i < 100 {\n print(i)\n i = i + 1\n}");
+    assert_eq!(result, Err(ErrorKind::OutputLimit));
+    assert_eq!(interp.take_output(), "0\n1\n2\n3\n4\n");
+}
+
+#[test]
+fn test_limits_reset_and_problems_run_within_budget() {
+    let limits = Limits {
+        max_steps: Some(100_000),
+        timeout: Some(Duration::from_secs(10)),
+        max_memory: Some(100_000),
+        max_output: Some(10_000),
+    };
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    interp.capture_output();
+    interp.set_limits(limits);
+    interp
+        .run_file("tests/resources/standard/fizzbuzz.mlc")
+        .unwrap();
+    assert!(interp.context().budget.borrow().steps() > 0);
+
+    interp.set_limits(Limits::default());
+    assert_eq!(interp.context().budget.borrow().steps(), 0);
+}
+
+#[test]
+fn test_recursion_limit_kind() {
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    interp.context().max_depth = 5;
+    match interp.eval("fn f() {\n return f()\n}\nf()") {
+        Err(InterpreterError::Runtime(e)) => assert_eq!(e.kind, ErrorKind::RecursionLimit),
+        other => panic!("Expected runtime error, got {:?}", other),
+    }
+}
@@ -1,5 +1,4 @@
 use multilang::grammar::{Grammar, Rule};
-use multilang::node::Context;
 use multilang::parser::Parser;
 
 mod test_utils;
@@ -7,11 +6,14 @@ mod test_utils;
 fn get_grammar() -> Grammar {
     let grammar_def = r##"
         Program = Stmt*
-        Stmt = ClassDef | FunctionDef | FunctionCall | Return | Assignment | Expr
+        Stmt = ClassDef | FunctionDef | FunctionCall | Return | CompoundAssignment | Assignment | Expr
         
+        ClassDef = "class" name:Identifier "extends" parent:Identifier "{" ClassMember* "}"
         ClassDef = "class" name:Identifier "{" ClassMember* "}"
         ClassMember = FieldDef | MethodDef
+        FieldDef = name:Identifier "=" default:Expr ";"
         FieldDef = name:Identifier ";"
+        MethodDef = initializer:[constructor\b] "(" params:ParamList ")" "{" body:Block "}"
         MethodDef = "fn" name:Identifier "(" params:ParamList ")" "{" body:Block "}"
         MethodDef = "fn" name:Identifier "(" ")" "{" body:Block "}"
         
@@ -24,6 +26,8 @@ fn get_grammar() -> Grammar {
         FunctionCall = name:Identifier "(" ")"
         
         Assignment = name:Identifier "=" value:Expr
+        Assignment = target:Postfix "=" value:Expr
+        CompoundAssignment = target:Postfix AddAssign value:Expr
         
         ParamList = Identifier "," params:ParamList
         ParamList = Identifier
@@ -69,6 +73,7 @@ fn get_grammar() -> Grammar {
         Mul = [\*]
         Div = [/]
         Mod = [%]
+        AddAssign = [\+=]
         
         Float = [[0-9]+\.[0-9]+]
         Int = [[0-9]+]
@@ -90,14 +95,13 @@ fn test_class_definition_and_instantiation() {
         print(p)
     "#;
 
-    let (logs, _guard) = test_utils::capture_output();
+    let (mut ctx, output) = test_utils::capture_output();
     let grammar = get_grammar();
     let parser = Parser::new(&grammar, code);
     let node = parser.parse(Rule::Program).expect("Failed to parse");
-    let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    assert_eq!(*logs.borrow(), vec!["<Object Point>\n"]);
+    assert_eq!(output.stdout(), "Point(x: 10, y: 20)\n");
 }
 
 #[test]
@@ -116,14 +120,13 @@ fn test_method_call() {
         print(result)
     "#;
 
-    let (logs, _guard) = test_utils::capture_output();
+    let (mut ctx, output) = test_utils::capture_output();
     let grammar = get_grammar();
     let parser = Parser::new(&grammar, code);
     let node = parser.parse(Rule::Program).expect("Failed to parse");
-    let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    assert_eq!(*logs.borrow(), vec!["10\n"]);
+    assert_eq!(output.stdout(), "10\n");
 }
 
 #[test]
@@ -138,14 +141,13 @@ fn test_member_access() {
         print(val)
     "#;
 
-    let (logs, _guard) = test_utils::capture_output();
+    let (mut ctx, output) = test_utils::capture_output();
     let grammar = get_grammar();
     let parser = Parser::new(&grammar, code);
     let node = parser.parse(Rule::Program).expect("Failed to parse");
-    let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    assert_eq!(*logs.borrow(), vec!["123\n"]);
+    assert_eq!(output.stdout(), "123\n");
 }
 
 #[test]
@@ -164,12 +166,121 @@ fn test_method_call_with_multiple_args() {
         print(sum)
     "#;
 
-    let (logs, _guard) = test_utils::capture_output();
+    let (mut ctx, output) = test_utils::capture_output();
     let grammar = get_grammar();
     let parser = Parser::new(&grammar, code);
     let node = parser.parse(Rule::Program).expect("Failed to parse");
-    let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    assert_eq!(*logs.borrow(), vec!["22\n"]);
+    assert_eq!(output.stdout(), "22\n");
+}
+
+#[test]
+fn test_field_assignment() {
+    let code = r#"
+        class Counter {
+            count;
+
+            fn increment() {
+                this.count = this.count + 1
+                return this.count
+            }
+        }
+
+        c = new Counter(0)
+        c.increment()
+        c.increment()
+        print(c.count)
+        c.count = 10
+        print(c.increment())
+    "#;
+
+    let (mut ctx, output) = test_utils::capture_output();
+    let grammar = get_grammar();
+    let parser = Parser::new(&grammar, code);
+    let node = parser.parse(Rule::Program).expect("Failed to parse");
+    node.run(&mut ctx).expect("Failed to run");
+
+    assert_eq!(output.stdout(), "2\n11\n");
+}
+
+#[test]
+fn test_assigning_undeclared_field_fails() {
+    let code = r#"
+        class Point {
+            x;
+        }
+
+        p = new Point(1)
+        p.y = 2
+    "#;
+
+    let (mut ctx, _output) = test_utils::capture_output();
+    let grammar = get_grammar();
+    let parser = Parser::new(&grammar, code);
+    let node = parser.parse(Rule::Program).expect("Failed to parse");
+    let err = node.run(&mut ctx).unwrap_err();
+
+    assert_eq!(err.message, "Object of class 'Point' has no field 'y'");
+}
+
+#[test]
+fn test_compound_field_assignment() {
+    let code = r#"
+        class Account {
+            balance;
+
+            fn deposit(amount) {
+                this.balance += amount
+            }
+        }
+
+        acc = new Account(5)
+        acc.deposit(10)
+        acc.balance += 1
+        print(acc.balance)
+    "#;
+
+    let (mut ctx, output) = test_utils::capture_output();
+    let grammar = get_grammar();
+    let parser = Parser::new(&grammar, code);
+    let node = parser.parse(Rule::Program).expect("Failed to parse");
+    node.run(&mut ctx).expect("Failed to run");
+
+    assert_eq!(output.stdout(), "16\n");
+}
+
+#[test]
+fn test_grammar_chooses_initializer_name() {
+    let code = r#"
+        class Account {
+            owner;
+            balance = 0;
+
+            constructor(owner) {
+                this.owner = owner
+            }
+        }
+
+        class Savings extends Account {
+            rate = 2;
+
+            fn interest() {
+                return this.balance * this.rate
+            }
+        }
+
+        s = new Savings("ada")
+        s.balance = 50
+        print(s.owner)
+        print(s.interest())
+    "#;
+
+    let (mut ctx, output) = test_utils::capture_output();
+    let grammar = get_grammar();
+    let parser = Parser::new(&grammar, code);
+    let node = parser.parse(Rule::Program).expect("Failed to parse");
+    node.run(&mut ctx).expect("Failed to run");
+
+    assert_eq!(output.stdout(), "ada\n100\n");
 }
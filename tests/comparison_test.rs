@@ -148,7 +148,7 @@ fn test_configurable_comparison() {
         String = ["[^"]*"]
         Identifier = [[a-zA-Z_][a-zA-Z0-9_]*]
     "#;
-    let grammar = Grammar::parse(&grammar_def);
+    let grammar = Grammar::parse(grammar_def);
 
     // Test "4 plus 5 == 9"
     let code = "print(4 plus 5 == 9)";
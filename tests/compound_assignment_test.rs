@@ -0,0 +1,89 @@
+use multilang::error::InterpreterError;
+use multilang::grammar::Grammar;
+use multilang::interpreter::Interpreter;
+use std::fs;
+
+use crate::test_utils::run_code_and_check;
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&fs::read_to_string(GRAMMAR).unwrap())
+}
+
+#[test]
+fn test_compound_assignment_on_variables() {
+    let code = r#"
+        i = 10
+        i += 5
+        i -= 3
+        i *= 4
+        i /= 6
+        i %= 5
+        print(i)
+        s = "ab"
+        s += "c"
+        print(s)
+        f = 1
+        f += 0.5
+        print(f)
+    "#;
+    run_code_and_check(&get_grammar(), code, "3\nabc\n1.5");
+}
+
+#[test]
+fn test_compound_assignment_on_elements() {
+    let code = r#"
+        a = [1, 2, 3]
+        a[0] += 10
+        a[0 - 1] *= 2
+        counts = {"x": 1}
+        counts["x"] += 1
+        print(a)
+        print(counts["x"])
+    "#;
+    run_code_and_check(&get_grammar(), code, "[11, 2, 6]\n2");
+}
+
+#[test]
+fn test_target_is_evaluated_once() {
+    let code = r#"
+        calls = []
+        fn next_index() {
+            append(calls, 1)
+            return 1
+        }
+        a = [0, 0]
+        a[next_index()] += 5
+        print(a)
+        print(len(calls))
+    "#;
+    run_code_and_check(&get_grammar(), code, "[0, 5]\n1");
+}
+
+#[test]
+fn test_wordy_increase_and_decrease() {
+    let grammar = Grammar::parse(&fs::read_to_string("tests/resources/wordy/grammar.mlg").unwrap());
+    let code = r#"
+        i = 1
+        increase i by 4
+        decrease i by 2
+        print(i)
+    "#;
+    run_code_and_check(&grammar, code, "3");
+}
+
+#[test]
+fn test_compound_assignment_errors() {
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    match interp.eval("missing += 1") {
+        Err(InterpreterError::Runtime(e)) => assert_eq!(e.message, "Variable 'missing' not found"),
+        other => panic!("Expected runtime error, got {:?}", other),
+    }
+    match interp.eval("(1 + 2) += 3") {
+        Err(InterpreterError::Parse(e)) => assert_eq!(e.message, "Invalid assignment target"),
+        other => panic!("Expected check error, got {:?}", other),
+    }
+}
@@ -42,14 +42,13 @@ fn test_if_false() {
     // if false { print(10) } -> should do nothing, no output
     // We'll test this by checking that the logs are empty
     let code = "if false { print(10) }";
-    let (logs, _guard) = test_utils::capture_output();
+    let (mut ctx, output) = test_utils::capture_output();
     let parser = multilang::parser::Parser::new(&grammar, code);
     let node = parser
         .parse(multilang::grammar::Rule::Program)
         .expect("Parse error");
-    let mut ctx = multilang::node::Context::new();
     node.run(&mut ctx).expect("Runtime error");
-    assert_eq!(*logs.borrow(), Vec::<String>::new());
+    assert_eq!(output.stdout(), "");
 }
 
 #[test]
@@ -90,12 +89,11 @@ fn test_int_condition() {
     run_code_and_check(&grammar, "if 1 { print(10) }", "10");
     // 0 is false - no output
     let code = "if 0 { print(10) }";
-    let (logs, _guard) = test_utils::capture_output();
+    let (mut ctx, output) = test_utils::capture_output();
     let parser = multilang::parser::Parser::new(&grammar, code);
     let node = parser
         .parse(multilang::grammar::Rule::Program)
         .expect("Parse error");
-    let mut ctx = multilang::node::Context::new();
     node.run(&mut ctx).expect("Runtime error");
-    assert_eq!(*logs.borrow(), Vec::<String>::new());
+    assert_eq!(output.stdout(), "");
 }
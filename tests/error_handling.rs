@@ -104,7 +104,7 @@ fn test_runtime_function_not_found() {
             assert!(e.message.contains("Function 'unknown_func' not found"));
             // Check stack trace
             assert!(!e.stack_trace.is_empty());
-            assert!(e.stack_trace[0].contains("unknown_func"));
+            assert_eq!(e.stack_trace[0].function, "unknown_func");
         }
     }
 }
@@ -156,9 +156,9 @@ fn test_stack_trace() {
             // So stack trace: ["at c:...", "at b:...", "at a:..."]
 
             assert_eq!(e.stack_trace.len(), 3);
-            assert!(e.stack_trace[0].contains("c"));
-            assert!(e.stack_trace[1].contains("b"));
-            assert!(e.stack_trace[2].contains("a"));
+            assert_eq!(e.stack_trace[0].function, "c");
+            assert_eq!(e.stack_trace[1].function, "b");
+            assert_eq!(e.stack_trace[2].function, "a");
         }
     }
 }
@@ -0,0 +1,111 @@
+use multilang::error::{ErrorKind, Frame, Span};
+use multilang::grammar::Grammar;
+use std::fs;
+
+use crate::test_utils::{run_code_and_check, runtime_error, standard_grammar};
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&fs::read_to_string(GRAMMAR).unwrap())
+}
+
+#[test]
+fn test_errors_have_kinds_and_codes() {
+    let cases = [
+        ("1 + \"a\" * 2", ErrorKind::TypeError, "E0002"),
+        ("print(missing)", ErrorKind::NameError, "E0003"),
+        ("a = [1, 2]\na[2]", ErrorKind::IndexError, "E0004"),
+        ("1 % 0", ErrorKind::ZeroDivision, "E0006"),
+        (
+            "fn f(a) { return a }\nf(1, 2)",
+            ErrorKind::ArityError,
+            "E0007",
+        ),
+        ("len(1, 2)", ErrorKind::ArityError, "E0007"),
+        ("to_int(\"12x\")", ErrorKind::ValueError, "E0008"),
+        ("read_file(\"no/such/file\")", ErrorKind::IOError, "E0009"),
+        ("throw 1", ErrorKind::Thrown, "E0016"),
+    ];
+    for (code, kind, expected_code) in cases {
+        let err = runtime_error(&standard_grammar(), code);
+        assert_eq!(err.kind, kind, "{}", code);
+        assert_eq!(err.kind.code(), expected_code, "{}", code);
+    }
+}
+
+#[test]
+fn test_error_span_points_at_failing_expression() {
+    let err = runtime_error(&standard_grammar(), "x = 1\ny = x + (2 / 0)\nprint(y)");
+    assert_eq!(err.message, "Division by zero");
+    assert_eq!(
+        err.span,
+        Some(Span {
+            line: 2,
+            column: 10,
+            end_line: 2,
+            end_column: 15,
+        })
+    );
+    assert!(
+        err.to_string()
+            .starts_with("Runtime Error [E0006] at line 2, column 10: Division by zero")
+    );
+
+    let err = runtime_error(&standard_grammar(), "x = 1\n\n   print(undefined)");
+    assert_eq!(err.kind, ErrorKind::NameError);
+    assert_eq!(err.span.map(|span| (span.line, span.column)), Some((3, 4)));
+}
+
+#[test]
+fn test_span_of_error_inside_function() {
+    let err = runtime_error(&standard_grammar(), "fn f(a) {\n  return a[3]\n}\nf([1])");
+    assert_eq!(err.kind, ErrorKind::IndexError);
+    assert_eq!(err.span.map(|span| span.line), Some(2));
+    assert_eq!(
+        err.stack_trace,
+        vec![Frame {
+            function: "f".to_string(),
+            line: 4,
+        }]
+    );
+}
+
+#[test]
+fn test_frames_for_builtins_and_function_values() {
+    let err = runtime_error(
+        &standard_grammar(),
+        "fn g() {\n  return to_int(\"x\")\n}\ng()",
+    );
+    let frames: Vec<String> = err.stack_trace.iter().map(Frame::to_string).collect();
+    assert_eq!(frames, ["at to_int:2", "at g:4"]);
+
+    let err = runtime_error(
+        &standard_grammar(),
+        "m = {\"f\": fn(x) { return x / 0 }}\nh = m[\"f\"]\nh(1)",
+    );
+    assert_eq!(err.kind, ErrorKind::ZeroDivision);
+    assert_eq!(err.stack_trace[0].function, "h");
+}
+
+#[test]
+fn test_catch_sees_code_and_line() {
+    let code = r#"
+        try {
+            x = [1]
+            x[4]
+        } catch e {
+            print(e.kind)
+            print(e.code)
+            print(e.line)
+        }
+        try {
+            throw "boom"
+        } catch e {
+            print(e.code)
+        }
+    "#;
+    run_code_and_check(&get_grammar(), code, "IndexError\nE0004\n4\nE0016");
+}
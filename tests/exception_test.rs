@@ -0,0 +1,204 @@
+use multilang::error::{ErrorKind, InterpreterError};
+use multilang::grammar::Grammar;
+use multilang::interpreter::Interpreter;
+use multilang::node::Value;
+use std::fs;
+
+use crate::test_utils::run_code_and_check;
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&fs::read_to_string(GRAMMAR).unwrap())
+}
+
+#[test]
+fn test_catch_thrown_value() {
+    let code = r#"
+        try {
+            throw [1, 2]
+            print("unreachable")
+        } catch e {
+            print(e.value)
+            print(e.kind)
+            print(e.message)
+        }
+        print("after")
+    "#;
+    run_code_and_check(&get_grammar(), code, "[1, 2]\nThrown\n[1, 2]\nafter");
+}
+
+#[test]
+fn test_catch_interpreter_errors() {
+    let code = r#"
+        fn divide(a, b) {
+            return a / b
+        }
+        try {
+            divide(1, 0)
+        } catch e {
+            print(e.message)
+            print(e.kind)
+            print(e.stack_trace)
+            print(e.value)
+        }
+        a = [1]
+        try {
+            a[5] = 1
+        } catch e {
+            print(e.message)
+        }
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "Division by zero\nZeroDivision\n[at divide:6]\n(void)\nIndex 5 out of bounds (len 1)",
+    );
+}
+
+#[test]
+fn test_identifiers_starting_with_keywords() {
+    let code = r#"
+        throwaway = 1
+        trying = throwaway + 1
+        try {
+            throw trying
+        } catch e {
+            print(e.value)
+        }
+    "#;
+    run_code_and_check(&get_grammar(), code, "2");
+}
+
+#[test]
+fn test_errors_propagate_through_functions() {
+    let code = r#"
+        fn inner() {
+            throw "deep"
+        }
+        fn outer() {
+            inner()
+            print("unreachable")
+        }
+        try {
+            outer()
+        } catch e {
+            print(e.value)
+            print(len(e.stack_trace))
+        }
+    "#;
+    run_code_and_check(&get_grammar(), code, "deep\n2");
+}
+
+#[test]
+fn test_finally_runs_on_every_path() {
+    let code = r#"
+        fn early() {
+            try {
+                return 1
+            } finally {
+                print("finally after return")
+            }
+            return 2
+        }
+        print(early())
+
+        i = 0
+        while i < 3 {
+            i += 1
+            try {
+                if i == 2 {
+                    break
+                }
+            } finally {
+                print(i)
+            }
+        }
+
+        try {
+            try {
+                throw "inner"
+            } finally {
+                print("finally before catch")
+            }
+        } catch e {
+            print(e.value)
+        }
+
+        try {
+            print("no error")
+        } catch e {
+            print("unreachable")
+        } finally {
+            print("done")
+        }
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "finally after return\n1\n1\n2\nfinally before catch\ninner\nno error\ndone",
+    );
+}
+
+#[test]
+fn test_return_in_finally_replaces_error() {
+    let code = r#"
+        fn swallow() {
+            try {
+                throw "lost"
+            } finally {
+                return "finally"
+            }
+        }
+        print(swallow())
+    "#;
+    run_code_and_check(&get_grammar(), code, "finally");
+}
+
+#[test]
+fn test_error_in_handler_propagates() {
+    let code = r#"
+        try {
+            throw 1
+        } catch e {
+            throw e.value + 1
+        }
+    "#;
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    match interp.eval(code) {
+        Err(InterpreterError::Runtime(e)) => {
+            assert_eq!(e.kind, ErrorKind::Thrown);
+            assert_eq!(e.message, "2");
+            assert!(matches!(e.thrown, Some(Value::Int(2))));
+        }
+        other => panic!("Expected runtime error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_budget_errors_are_not_caught() {
+    let code = r#"
+        try {
+            while 1 == 1 {
+            }
+        } catch e {
+            print("caught")
+        } finally {
+            print("finally")
+        }
+    "#;
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    let output = multilang::output::CaptureOutput::new();
+    interp.set_output(output.clone());
+    interp.set_limits(multilang::budget::Limits {
+        max_steps: Some(100),
+        ..Default::default()
+    });
+    match interp.eval(code) {
+        Err(InterpreterError::Runtime(e)) => assert_eq!(e.kind, ErrorKind::StepLimit),
+        other => panic!("Expected step limit error, got {:?}", other),
+    }
+    assert_eq!(output.stdout(), "");
+}
@@ -50,3 +50,71 @@ fn test_nested_calls_with_args() {
 
     run_code_and_check(&grammar, input, "100");
 }
+
+#[test]
+fn test_return_exits_function_early() {
+    let grammar = load_standard_grammar();
+
+    let input = r#"
+        fn sign(x) {
+            if x < 0 {
+                return "negative"
+            }
+            if x == 0 {
+                return "zero"
+            }
+            return "positive"
+        }
+        print(sign(0 - 5))
+        print(sign(0))
+        print(sign(7))
+    "#;
+    run_code_and_check(&grammar, input, "negative\nzero\npositive");
+}
+
+#[test]
+fn test_return_exits_nested_loops() {
+    let grammar = load_standard_grammar();
+
+    // Every pair sums to the target; only the first one may be returned.
+    let input = r#"
+        fn first_pair(nums, target) {
+            result = []
+            i = 0
+            while i < len(nums) {
+                j = i + 1
+                while j < len(nums) {
+                    if get(nums, i) + get(nums, j) == target {
+                        append(result, i)
+                        append(result, j)
+                        return result
+                    }
+                    j = j + 1
+                }
+                i = i + 1
+            }
+            return result
+        }
+        print(first_pair([1, 1, 1], 2))
+    "#;
+    run_code_and_check(&grammar, input, "[0, 1]");
+}
+
+#[test]
+fn test_top_level_return_ends_program() {
+    let grammar = load_standard_grammar();
+
+    let input = r#"
+        print(1)
+        return 2
+        print(3)
+    "#;
+    let parser = multilang::parser::Parser::new(&grammar, input);
+    let node = parser
+        .parse(multilang::grammar::Rule::Program)
+        .expect("Parse error");
+    let (mut ctx, output) = test_utils::capture_output();
+    let result = node.run(&mut ctx).expect("Runtime error");
+    assert_eq!(result, multilang::node::Value::Int(2));
+    assert_eq!(output.stdout(), "1\n");
+}
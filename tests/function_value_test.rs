@@ -0,0 +1,221 @@
+use multilang::grammar::{Grammar, Rule};
+use multilang::node::Context;
+use multilang::parser::Parser;
+use std::fs;
+
+use crate::test_utils::run_code_and_check;
+
+mod test_utils;
+
+fn get_grammar() -> Grammar {
+    let grammar_def = r##"
+        Program = Stmt*
+        Stmt = ClassDef | FunctionDef | Return | Assignment | Expr
+
+        ClassDef = "class" name:Identifier "{" ClassMember* "}"
+        ClassMember = FieldDef | MethodDef
+        FieldDef = name:Identifier ";"
+        MethodDef = "fn" name:Identifier "(" params:ParamList ")" "{" body:Block "}"
+        MethodDef = "fn" name:Identifier "(" ")" "{" body:Block "}"
+
+        FunctionDef = "fn" name:Identifier "(" params:ParamList ")" "{" body:Block "}"
+        FunctionDef = "fn" name:Identifier "(" ")" "{" body:Block "}"
+        Lambda = "fn" "(" params:ParamList ")" "{" body:Block "}"
+        Lambda = "fn" "(" ")" "{" body:Block "}"
+        Lambda = "\" params:ParamList "->" body:Expr
+
+        Block = Stmt*
+        Return = "return" value:Expr
+
+        FunctionCall = name:Identifier "(" args:ArgList ")"
+        FunctionCall = name:Identifier "(" ")"
+        Assignment = name:Identifier "=" value:Expr
+
+        ParamList = Identifier "," params:ParamList
+        ParamList = Identifier
+        ArgList = Expr "," args:ArgList
+        ArgList = Expr
+
+        Expr = Comparison
+        Comparison = Term Eq Term | Term
+        Term = Factor Add Term | Factor
+        Factor = Unary Mul Factor | Unary
+        Unary = Postfix
+
+        Postfix = Atom PostfixSuffix*
+        PostfixSuffix = "." method:Identifier "(" args:ArgList ")"
+        PostfixSuffix = "." method:Identifier "(" ")"
+        PostfixSuffix = "." member:Identifier
+        PostfixSuffix = "(" args:ArgList ")"
+        PostfixSuffix = "(" ")"
+
+        Atom = Lambda | NewExpr | SelfReference | Int | String | FunctionCall | Identifier | ListLiteral | MapLiteral | "(" Expr ")"
+
+        NewExpr = "new" class_name:Identifier "(" args:ArgList ")"
+        SelfReference = "this"
+
+        ListLiteral = "[" Elements "]"
+        Elements = Expr "," Elements
+        Elements = Expr
+
+        MapLiteral = "{" MapEntries "}"
+        MapEntries = MapEntry "," MapEntries
+        MapEntries = MapEntry
+        MapEntry = Key ":" Expr
+        Key = String | Identifier
+
+        Eq = [==]
+        Add = [\+]
+        Mul = [\*]
+        Int = [[0-9]+]
+        String = ["[^"]*"]
+        Identifier = [[a-zA-Z_][a-zA-Z0-9_]*]
+    "##;
+    Grammar::parse(grammar_def)
+}
+
+#[test]
+fn test_lambda_assigned_and_called() {
+    let code = r#"
+        double = fn(x) { x * 2 }
+        triple = \x -> x * 3
+        print(double(4))
+        print(triple(2))
+        print(fn() { 7 }())
+    "#;
+    run_code_and_check(&get_grammar(), code, "8\n6\n7");
+}
+
+#[test]
+fn test_functions_as_arguments() {
+    let code = r#"
+        fn apply(f, x) {
+            return f(x)
+        }
+        fn inc(x) {
+            return x + 1
+        }
+        print(apply(inc, 1))
+        print(apply(\n -> n * 10, 2))
+        print(apply(len, [1, 2, 3]))
+    "#;
+    run_code_and_check(&get_grammar(), code, "2\n20\n3");
+}
+
+#[test]
+fn test_function_returned_from_function() {
+    let code = r#"
+        fn make_adder(n) {
+            return \x -> x + n
+        }
+        add5 = make_adder(5)
+        print(add5(1))
+        print(make_adder(10)(1))
+
+        fn compose(f, g) {
+            return fn(x) { f(g(x)) }
+        }
+        print(compose(add5, \x -> x * 2)(3))
+    "#;
+    run_code_and_check(&get_grammar(), code, "6\n11\n11");
+}
+
+#[test]
+fn test_function_stored_in_collections() {
+    let code = r#"
+        handlers = {double: \x -> x * 2, square: \x -> x * x}
+        print(get(handlers, "square")(4))
+        fns = [print, \x -> x + 1]
+        get(fns, 0)(get(fns, 1)(41))
+    "#;
+    run_code_and_check(&get_grammar(), code, "16\n42");
+}
+
+#[test]
+fn test_callable_fields_and_bound_methods() {
+    let code = r#"
+        class Button {
+            label;
+            callback;
+            fn click(n) {
+                return this.callback(n)
+            }
+            fn name() {
+                return this.label
+            }
+        }
+        b = new Button("ok", \n -> n + 100)
+        print(b.callback(2))
+        print(b.click(3))
+        name = b.name
+        print(name())
+    "#;
+    run_code_and_check(&get_grammar(), code, "102\n103\nok");
+}
+
+#[test]
+fn test_function_values_print_and_compare() {
+    let code = r#"
+        fn inc(x) {
+            return x + 1
+        }
+        f = inc
+        print(inc)
+        print(len)
+        print(\x -> x)
+        print(f == inc)
+        print(f == len)
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "<function inc>\n<built-in function len>\n<lambda>\ntrue\nfalse",
+    );
+}
+
+#[test]
+fn test_calling_non_function_is_error() {
+    let grammar = get_grammar();
+    let node = Parser::new(&grammar, "x = 1\nx(2)")
+        .parse(Rule::Program)
+        .expect("Failed to parse");
+    let err = node.run(&mut Context::new()).expect_err("Expected error");
+    assert_eq!(err.message, "'x' is not a function");
+
+    let node = Parser::new(&grammar, "f = \\x -> x\nf(1, 2)")
+        .parse(Rule::Program)
+        .expect("Failed to parse");
+    let err = node.run(&mut Context::new()).expect_err("Expected error");
+    assert_eq!(
+        err.message,
+        "Function '<lambda>' expects 1 arguments, got 2"
+    );
+}
+
+#[test]
+fn test_dialect_lambdas() {
+    let grammar_def = fs::read_to_string("tests/resources/standard/grammar.mlg").unwrap();
+    let code = r#"
+        fn twice(f, x) {
+            return f(f(x))
+        }
+        print(twice(fn(x) { x * 3 }, 2))
+    "#;
+    run_code_and_check(&Grammar::parse(&grammar_def), code, "18");
+
+    let grammar_def = fs::read_to_string("tests/resources/wordy/grammar.mlg").unwrap();
+    let code = r#"
+        function twice(f, x) do
+            return f(f(x))
+        end
+        print(twice(function(x) do x multiply 3 end, 2))
+    "#;
+    run_code_and_check(&Grammar::parse(&grammar_def), code, "18");
+
+    let grammar_def = fs::read_to_string("tests/resources/cryptic/grammar.mlg").unwrap();
+    let code = r#"
+        @twice: f & x => ^ f#f#x$$ ;
+        print#twice#\x -> x * 3 & 2$$
+    "#;
+    run_code_and_check(&Grammar::parse(&grammar_def), code, "18");
+}
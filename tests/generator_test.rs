@@ -0,0 +1,234 @@
+use multilang::budget::Limits;
+use multilang::error::{ErrorKind, InterpreterError};
+use multilang::grammar::{Grammar, Rule};
+use multilang::interpreter::Interpreter;
+use multilang::parser::Parser;
+
+use crate::test_utils::{run_code_and_check, runtime_error, standard_grammar_with_for};
+
+mod test_utils;
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&standard_grammar_with_for())
+}
+
+fn check_error(code: &str) -> (String, usize) {
+    let grammar = get_grammar();
+    let err = Parser::new(&grammar, code)
+        .parse(Rule::Program)
+        .err()
+        .expect("Expected parse error");
+    (err.message, err.line)
+}
+
+#[test]
+fn test_infinite_generator_with_take() {
+    let code = r#"
+        fn naturals() {
+            n = 0
+            while 1 {
+                yield n
+                n += 1
+            }
+        }
+        print(take(naturals(), 5))
+        nums = naturals()
+        print(take(nums, 2))
+        print(take(nums, 2))
+        print(take(nums, 0))
+    "#;
+    run_code_and_check(&get_grammar(), code, "[0, 1, 2, 3, 4]\n[0, 1]\n[2, 3]\n[]");
+}
+
+#[test]
+fn test_identifiers_starting_with_yield() {
+    let code = r#"
+        yieldx = 3
+        fn f() {
+            yielded = yieldx + 1
+            yield yielded
+        }
+        print(take(f(), 5))
+    "#;
+    run_code_and_check(&get_grammar(), code, "[4]");
+}
+
+#[test]
+fn test_for_over_generator() {
+    let code = r#"
+        fn countdown(n) {
+            while n > 0 {
+                yield n
+                n -= 1
+            }
+            yield "liftoff"
+        }
+        for x in countdown(3) {
+            print(x)
+        }
+        gen = countdown(1)
+        print(gen)
+        for x in gen {
+            print(x)
+        }
+        for x in gen {
+            print("never")
+        }
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "3\n2\n1\nliftoff\n<generator countdown>\n1\nliftoff",
+    );
+}
+
+#[test]
+fn test_yield_inside_if_and_for() {
+    let code = r#"
+        fn evens_then_odds(xs) {
+            for x in xs {
+                if x % 2 == 0 {
+                    yield x
+                }
+            }
+            for x in xs {
+                if x % 2 == 0 {
+                } else {
+                    label = "odd"
+                    yield x
+                    yield label
+                }
+            }
+        }
+        print(take(evens_then_odds([1, 2, 3, 4]), 10))
+    "#;
+    run_code_and_check(&get_grammar(), code, "[2, 4, 1, odd, 3, odd]");
+}
+
+#[test]
+fn test_lazy_pipeline() {
+    let code = r#"
+        calls = []
+        fn naturals() {
+            n = 1
+            while 1 {
+                append(calls, n)
+                yield n
+                n += 1
+            }
+        }
+        squares = map(naturals(), fn(x) { return x * x })
+        odd = filter(squares, fn(x) { return x % 2 == 1 })
+        print(calls)
+        print(take(odd, 3))
+        print(calls)
+        print(map([1, 2], fn(x) { return x + 1 }))
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "[]\n[1, 9, 25]\n[1, 2, 3, 4, 5]\n[2, 3]",
+    );
+}
+
+#[test]
+fn test_return_ends_generator() {
+    let code = r#"
+        fn first_words(text) {
+            for word in split(text, " ") {
+                if word == "stop" {
+                    return 0
+                }
+                yield word
+            }
+        }
+        print(take(first_words("a b stop c"), 10))
+        print(take("hello", 2))
+    "#;
+    run_code_and_check(&get_grammar(), code, "[a, b]\n[h, e]");
+}
+
+#[test]
+fn test_generator_methods_and_lambdas() {
+    let code = r#"
+        class Tree {
+            value
+            children = []
+            fn walk() {
+                yield this.value
+                for child in this.children {
+                    for value in child.walk() {
+                        yield value
+                    }
+                }
+            }
+        }
+        root = new Tree(1)
+        left = new Tree(2)
+        append(root.children, left)
+        append(left.children, new Tree(3))
+        append(root.children, new Tree(4))
+        print(take(root.walk(), 10))
+        pairs = fn(x) {
+            yield x
+            yield x
+        }
+        print(take(pairs(7), 10))
+    "#;
+    run_code_and_check(&get_grammar(), code, "[1, 2, 3, 4]\n[7, 7]");
+}
+
+#[test]
+fn test_generator_runs_within_limits() {
+    let mut interp = Interpreter::from_grammar_str(&standard_grammar_with_for());
+    interp.capture_output();
+    interp.set_limits(Limits {
+        max_steps: Some(10_000),
+        ..Limits::default()
+    });
+    let code = r#"
+        fn forever() {
+            while 1 {
+                yield 1
+            }
+        }
+        for x in forever() {
+        }
+    "#;
+    match interp.eval(code) {
+        Err(InterpreterError::Runtime(e)) => assert_eq!(e.kind, ErrorKind::StepLimit),
+        other => panic!("Expected step limit, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_generator_errors() {
+    assert_eq!(
+        check_error("x = 1\nyield x"),
+        ("Yield outside of a function".to_string(), 2)
+    );
+    assert_eq!(
+        check_error("fn f() {\n try {\n yield 1\n } catch e {\n }\n}"),
+        (
+            "Yield is only supported directly inside blocks, if, while and for".to_string(),
+            3
+        )
+    );
+    let err = runtime_error(
+        &standard_grammar_with_for(),
+        "fn f() {\n yield 1\n}\nx = f() + 1",
+    );
+    assert_eq!(err.kind, ErrorKind::TypeError);
+    let err = runtime_error(
+        &standard_grammar_with_for(),
+        "fn f() {\n yield 1\n}\ntake(f(), 0 - 1)",
+    );
+    assert_eq!(err.kind, ErrorKind::ValueError);
+    assert_eq!(err.message, "take expects a non-negative count");
+    let err = runtime_error(
+        &standard_grammar_with_for(),
+        "fn f() {\n for x in g {\n yield x\n }\n}\ng = f()\ntake(g, 1)",
+    );
+    assert_eq!(err.kind, ErrorKind::ValueError);
+    assert_eq!(err.message, "Generator is already running");
+}
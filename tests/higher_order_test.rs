@@ -0,0 +1,140 @@
+use multilang::grammar::{Grammar, Rule};
+use multilang::node::Context;
+use multilang::parser::Parser;
+use std::fs;
+
+use crate::test_utils::run_code_and_check;
+
+mod test_utils;
+
+fn get_grammar() -> Grammar {
+    let grammar_def = fs::read_to_string("tests/resources/standard/grammar.mlg").unwrap();
+    Grammar::parse(&grammar_def)
+}
+
+#[test]
+fn test_map_filter_reduce() {
+    let code = r#"
+        nums = [1, 2, 3, 4, 5]
+        print(map(nums, fn(x) { x * x }))
+        print(filter(nums, fn(x) { x % 2 == 1 }))
+        print(reduce(nums, fn(acc, x) { acc + x }))
+        print(reduce(nums, fn(acc, x) { acc * x }, 10))
+        print(reduce([], fn(acc, x) { acc + x }, 0))
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "[1, 4, 9, 16, 25]\n[1, 3, 5]\n15\n1200\n0",
+    );
+}
+
+#[test]
+fn test_named_functions_and_builtins_as_callbacks() {
+    let code = r#"
+        fn double(x) {
+            return x * 2
+        }
+        print(map([1, 2, 3], double))
+        print(map([[1], [1, 2], []], len))
+    "#;
+    run_code_and_check(&get_grammar(), code, "[2, 4, 6]\n[1, 2, 0]");
+}
+
+#[test]
+fn test_callbacks_close_over_locals() {
+    let code = r#"
+        fn scale_all(items, factor) {
+            return map(items, fn(x) { x * factor })
+        }
+        print(scale_all([1, 2], 10))
+
+        seen = []
+        map([1, 2, 3], fn(x) { append(seen, x * x) })
+        print(seen)
+    "#;
+    run_code_and_check(&get_grammar(), code, "[10, 20]\n[1, 4, 9]");
+}
+
+#[test]
+fn test_any_all_find() {
+    let code = r#"
+        nums = [1, 3, 4, 7]
+        print(any(nums, fn(x) { x % 2 == 0 }))
+        print(all(nums, fn(x) { x > 0 }))
+        print(all(nums, fn(x) { x < 5 }))
+        print(any([0, 0]))
+        print(all([]))
+        print(find(nums, fn(x) { x > 3 }))
+        print(find(nums, fn(x) { x > 10 }))
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "true\ntrue\nfalse\nfalse\ntrue\n4\n(void)",
+    );
+}
+
+#[test]
+fn test_sort_with_key() {
+    let code = r#"
+        words = ["pear", "fig", "banana", "kiwi"]
+        by_len = sort_by(words, len)
+        print(by_len)
+        print(words)
+        sort(words, fn(w) { 0 - len(w) })
+        print(words)
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "[fig, pear, kiwi, banana]\n[pear, fig, banana, kiwi]\n[banana, pear, kiwi, fig]",
+    );
+}
+
+#[test]
+fn test_group_by_min_by_max_by() {
+    let code = r#"
+        words = ["apple", "avocado", "banana", "cherry", "blueberry"]
+        groups = group_by(words, fn(w) { get(w, 0) })
+        print(groups)
+        print(group_by([1, 2, 3, 4], fn(x) { x % 2 }))
+        print(min_by(words, len))
+        print(max_by(words, len))
+        print(max_by([], len))
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "{a: [apple, avocado], b: [banana, blueberry], c: [cherry]}\n{0: [2, 4], 1: [1, 3]}\napple\nblueberry\n(void)",
+    );
+}
+
+#[test]
+fn test_callback_errors_propagate() {
+    let grammar = get_grammar();
+    let run = |code: &str| {
+        let node = Parser::new(&grammar, code)
+            .parse(Rule::Program)
+            .expect("Failed to parse");
+        node.run(&mut Context::new())
+            .expect_err("Expected error")
+            .message
+    };
+
+    assert_eq!(run("map([1, 0], fn(x) { 1 / x })"), "Division by zero");
+    assert_eq!(run("map([1], 2)"), "map expects a function");
+    assert_eq!(run("filter(1, len)"), "filter expects a list");
+    assert_eq!(
+        run("reduce([1], len, 0, 1)"),
+        "reduce expects 2 or 3 arguments, got 4"
+    );
+    assert_eq!(
+        run("reduce([], fn(a, b) { a + b })"),
+        "reduce of an empty list with no initial value"
+    );
+    assert_eq!(
+        run("map([1], fn(a, b) { a + b })"),
+        "Function '<lambda>' expects 2 arguments, got 1"
+    );
+}
@@ -0,0 +1,95 @@
+use multilang::error::InterpreterError;
+use multilang::grammar::Grammar;
+use multilang::interpreter::Interpreter;
+use std::fs;
+
+use crate::test_utils::run_code_and_check;
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&fs::read_to_string(GRAMMAR).unwrap())
+}
+
+fn error_message(code: &str) -> String {
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    match interp.eval(code) {
+        Err(InterpreterError::Runtime(e)) => e.message,
+        Err(InterpreterError::Parse(e)) => e.message,
+        other => panic!("Expected an error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_index() {
+    let code = r#"
+        a = [10, 20, 30]
+        m = {"k": 1}
+        s = "hello"
+        print(a[0])
+        print(a[0 - 1])
+        print(m["k"])
+        print(m["missing"])
+        print(get(m, "missing"))
+        print(s[1])
+        print([[1, 2], [3, 4]][1][0])
+    "#;
+    run_code_and_check(&get_grammar(), code, "10\n30\n1\n(void)\n(void)\ne\n3");
+}
+
+#[test]
+fn test_slice() {
+    let code = r#"
+        a = [1, 2, 3, 4, 5]
+        s = "hello"
+        print(s[1:3])
+        print(a[1:3])
+        print(a[:2])
+        print(a[3:])
+        print(a[:])
+        print(a[0 - 2:])
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "el\n[2, 3]\n[1, 2]\n[4, 5]\n[1, 2, 3, 4, 5]\n[4, 5]",
+    );
+}
+
+#[test]
+fn test_index_assignment() {
+    let code = r#"
+        a = [1, 2, 3]
+        a[1] = 20
+        a[0 - 1] = 30
+        m = {}
+        m["k"] = "v"
+        m["k"] = m["k"] + "w"
+        grid = [[0, 0], [0, 0]]
+        grid[1][0] = 5
+        print(a)
+        print(m["k"])
+        print(grid)
+    "#;
+    run_code_and_check(&get_grammar(), code, "[1, 20, 30]\nvw\n[[0, 0], [5, 0]]");
+}
+
+#[test]
+fn test_index_errors() {
+    assert_eq!(
+        error_message("a = [1]\nprint(a[1])"),
+        "Index 1 out of bounds (len 1)"
+    );
+    assert_eq!(
+        error_message("a = [1]\nprint(a[\"x\"])"),
+        "List index must be an integer, got String"
+    );
+    assert_eq!(error_message("x = 5\nprint(x[0])"), "Cannot index into Int");
+    assert_eq!(
+        error_message("s = \"abc\"\ns[0] = \"x\""),
+        "Cannot assign to an index of String"
+    );
+    assert_eq!(error_message("(1 + 2) = 3"), "Invalid assignment target");
+}
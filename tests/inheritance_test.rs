@@ -0,0 +1,183 @@
+use multilang::error::ErrorKind;
+use multilang::grammar::Grammar;
+use std::fs;
+
+use crate::test_utils::{run_code_and_check, runtime_error, standard_grammar};
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&fs::read_to_string(GRAMMAR).unwrap())
+}
+
+#[test]
+fn test_initializer_receives_arguments() {
+    let code = r#"
+        class Point {
+            x
+            y
+            fn init(x, y) {
+                this.x = x * 10
+                this.y = y * 10
+            }
+            fn sum() {
+                return this.x + this.y
+            }
+        }
+        p = new Point(1, 2)
+        print(p.x)
+        print(p.sum())
+    "#;
+    run_code_and_check(&get_grammar(), code, "10\n30");
+}
+
+#[test]
+fn test_field_defaults() {
+    let code = r#"
+        start = 5
+        class Counter {
+            count = start * 2
+            step = 1
+            items = []
+            fn tick() {
+                this.count += this.step
+                return this.count
+            }
+        }
+        a = new Counter()
+        b = new Counter()
+        append(a.items, 1)
+        print(a.tick())
+        print(len(b.items))
+        c = new Counter(100)
+        print(c.tick())
+        d = new Counter(0, 3)
+        print(d.tick())
+    "#;
+    run_code_and_check(&get_grammar(), code, "11\n0\n101\n3");
+}
+
+#[test]
+fn test_methods_are_inherited_and_overridden() {
+    let code = r#"
+        class Animal {
+            name
+            fn init(name) {
+                this.name = name
+            }
+            fn speak() {
+                return this.name + " makes a sound"
+            }
+            fn describe() {
+                return "I am " + this.name + " and " + this.speak()
+            }
+        }
+        class Dog extends Animal {
+            tricks = 0
+            fn speak() {
+                return this.name + " barks"
+            }
+        }
+        a = new Animal("cat")
+        d = new Dog("rex")
+        print(a.describe())
+        print(d.describe())
+        print(d.tricks)
+        f = d.describe
+        print(f())
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "I am cat and cat makes a sound\nI am rex and rex barks\n0\nI am rex and rex barks",
+    );
+}
+
+#[test]
+fn test_super_calls_parent_method() {
+    let code = r#"
+        class Shape {
+            name
+            fn init(name) {
+                this.name = name
+            }
+            fn describe() {
+                return "shape " + this.name
+            }
+        }
+        class Square extends Shape {
+            side
+            fn init(side) {
+                super.init("square")
+                this.side = side
+            }
+            fn describe() {
+                return super.describe() + " of side " + this.side
+            }
+        }
+        class Cube extends Square {
+            fn describe() {
+                return super.describe() + " in 3d"
+            }
+        }
+        print(new Square("2").describe())
+        print(new Cube("3").describe())
+        print(new Cube("3").name)
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "shape square of side 2\nshape square of side 3 in 3d\nsquare",
+    );
+}
+
+#[test]
+fn test_fields_of_ancestors_fill_positionally() {
+    let code = r#"
+        class Base {
+            a
+        }
+        class Derived extends Base {
+            b = "default"
+        }
+        d = new Derived(1)
+        print(d.a)
+        print(d.b)
+        d = new Derived(1, 2)
+        print(d.b)
+    "#;
+    run_code_and_check(&get_grammar(), code, "1\ndefault\n2");
+}
+
+#[test]
+fn test_class_errors() {
+    let err = runtime_error(&standard_grammar(), "class A extends Missing {\n}");
+    assert_eq!(err.kind, ErrorKind::NameError);
+    assert_eq!(err.message, "Undefined class 'Missing'");
+    let err = runtime_error(&standard_grammar(), "class A {\n x\n y = 1\n}\nnew A()");
+    assert_eq!(err.kind, ErrorKind::ArityError);
+    assert_eq!(err.message, "Class 'A' expects 1 to 2 arguments, got 0");
+    let err = runtime_error(
+        &standard_grammar(),
+        "class A {\n fn init(x) {\n }\n}\nnew A()",
+    );
+    assert_eq!(err.kind, ErrorKind::ArityError);
+    assert_eq!(err.message, "Method 'init' expects 1 arguments, got 0");
+    let err = runtime_error(
+        &standard_grammar(),
+        "class A {\n fn f() {\n return super.f()\n }\n}\nnew A().f()",
+    );
+    assert_eq!(err.kind, ErrorKind::NameError);
+    assert_eq!(
+        err.message,
+        "'super' used in class 'A', which extends no class"
+    );
+    let err = runtime_error(
+        &standard_grammar(),
+        "class A {\n}\nclass B extends A {\n}\nclass A extends B {\n}",
+    );
+    assert_eq!(err.kind, ErrorKind::TypeError);
+    assert_eq!(err.message, "Class 'A' cannot extend its own subclass 'B'");
+}
@@ -0,0 +1,96 @@
+use multilang::error::{ErrorKind, InterpreterError};
+use multilang::grammar::Grammar;
+use multilang::interpreter::Interpreter;
+use std::fs;
+
+use crate::test_utils::{run_code_and_check, runtime_error, standard_grammar};
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&fs::read_to_string(GRAMMAR).unwrap())
+}
+
+#[test]
+fn test_64_bit_integers() {
+    let code = r#"
+        big = 3000000000
+        print(big * 3)
+        print(9223372036854775807)
+        print(to_int("-9223372036854775808"))
+        print(len([1, 2]) * 4000000000)
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "9000000000\n9223372036854775807\n-9223372036854775808\n8000000000",
+    );
+}
+
+#[test]
+fn test_overflow_promotes_to_big_int() {
+    let code = r#"
+        m = 9223372036854775807
+        n = (0 - m) - 1
+        print(m + 1)
+        print(n - 1)
+        print(m * 2)
+        print(n / (0 - 1))
+        print(n % (0 - 1))
+        print(0 - n)
+        print(abs(n))
+        print(sum([m, 1]))
+        print((m + 1) - 1)
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "9223372036854775808\n-9223372036854775809\n18446744073709551614\n9223372036854775808\n0\n9223372036854775808\n9223372036854775808\n9223372036854775808\n9223372036854775807",
+    );
+}
+
+#[test]
+fn test_invalid_literal_is_a_parse_error() {
+    let mut interp = Interpreter::from_grammar_str(
+        r#"
+        Program = Stmt*
+        Stmt = Assignment
+        Assignment = name:Identifier "=" value:Int
+        Int = [[0-9][0-9a-z]*]
+        Identifier = [[a-z]+]
+    "#,
+    );
+    match interp.eval("x = 1\ny =  12ab") {
+        Err(InterpreterError::Parse(e)) => {
+            assert_eq!(e.message, "Invalid integer literal '12ab'");
+            assert_eq!((e.line, e.column), (2, 6));
+        }
+        other => panic!("Expected parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_conversions_check_range() {
+    let err = runtime_error(&standard_grammar(), "to_int(\"12x\")");
+    assert_eq!(err.kind, ErrorKind::ValueError);
+    assert_eq!(err.message, "to_int: invalid number format '12x'");
+
+    let err = runtime_error(&standard_grammar(), "x = to_float(\"1e400\")\nto_int(x)");
+    assert_eq!(err.kind, ErrorKind::Overflow);
+    assert_eq!(err.message, "to_int: inf has no integer value");
+
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    assert_eq!(interp.eval_as::<i64>("5000000000").unwrap(), 5_000_000_000);
+    let err = interp.eval_as::<i32>("5000000000").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Runtime Error [E0010]: Int 5000000000 does not fit in i32\nStack Trace:\n"
+    );
+    let err = interp.eval_as::<i64>("99999999999999999999").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Runtime Error [E0010]: Int 99999999999999999999 does not fit in i64\nStack Trace:\n"
+    );
+}
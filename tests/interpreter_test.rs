@@ -0,0 +1,127 @@
+use multilang::convert::{FromValue, IntoValue};
+use multilang::error::InterpreterError;
+use multilang::functions::registry::Arity;
+use multilang::interpreter::Interpreter;
+use multilang::node::Value;
+use std::collections::HashMap;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn interpreter() -> Interpreter {
+    Interpreter::from_grammar_file(GRAMMAR).expect("Failed to load grammar")
+}
+
+#[test]
+fn test_eval_keeps_state_between_calls() {
+    let mut interp = interpreter();
+    interp.eval("count = 1").unwrap();
+    interp.eval("fn bump(n) {\n return count + n\n}").unwrap();
+    assert_eq!(interp.eval("bump(2)").unwrap(), Value::Int(3));
+    assert_eq!(interp.eval("return count + 10").unwrap(), Value::Int(11));
+    assert_eq!(interp.eval_as::<i32>("count").unwrap(), 1);
+}
+
+#[test]
+fn test_call_functions_by_name() {
+    let mut interp = interpreter();
+    interp
+        .eval("fn add(a, b) {\n return a + b\n}\ntwice = fn(x) { x * 2 }")
+        .unwrap();
+
+    let sum: i32 = interp
+        .call_as("add", vec![1.into_value(), 2.into_value()])
+        .unwrap();
+    assert_eq!(sum, 3);
+    assert_eq!(
+        interp.call("twice", vec![21.into_value()]).unwrap(),
+        Value::Int(42)
+    );
+    assert_eq!(
+        interp.call("len", vec!["abc".into_value()]).unwrap(),
+        Value::Int(3)
+    );
+
+    match interp.call("missing", vec![]) {
+        Err(InterpreterError::Runtime(e)) => assert_eq!(e.message, "Function 'missing' not found"),
+        _ => panic!("Expected runtime error"),
+    }
+}
+
+#[test]
+fn test_globals_round_trip() {
+    let mut interp = interpreter();
+    interp.set_global("names", vec!["b", "a"]);
+    interp.set_global("limit", 2.5);
+    interp.eval("sort(names)\nbig = limit > 2.0").unwrap();
+
+    let names: Vec<String> = interp.get_global("names").unwrap();
+    assert_eq!(names, vec!["a", "b"]);
+    assert!(interp.get_global::<bool>("big").unwrap());
+    assert_eq!(interp.get_global::<Option<i32>>("unset").unwrap(), None);
+
+    match interp.get_global::<i32>("names") {
+        Err(InterpreterError::Runtime(e)) => assert_eq!(e.message, "Expected Int, got List"),
+        _ => panic!("Expected conversion error"),
+    }
+}
+
+#[test]
+fn test_value_conversions() {
+    let mut map = HashMap::new();
+    map.insert("a".to_string(), vec![1, 2]);
+    let value = map.clone().into_value();
+    assert_eq!(HashMap::<String, Vec<i32>>::from_value(value).unwrap(), map);
+
+    assert_eq!(f64::from_value(Value::Int(2)).unwrap(), 2.0);
+    assert_eq!(Option::<i32>::None.into_value(), Value::Void);
+    assert_eq!(().into_value(), Value::Void);
+    assert!(String::from_value(Value::Bool(true)).is_err());
+}
+
+#[test]
+fn test_output_capture() {
+    let mut interp = interpreter();
+    interp.capture_output();
+    interp.eval("print(\"hello\")\nprint(1, 2)").unwrap();
+    interp.eval("fn shout() {\n print(\"hi\")\n}").unwrap();
+    interp.call("shout", vec![]).unwrap();
+    assert_eq!(interp.take_output(), "hello\n1 2\nhi\n");
+    assert_eq!(interp.take_output(), "");
+}
+
+#[test]
+fn test_errors() {
+    let mut interp = interpreter();
+    assert!(matches!(
+        interp.eval("x = "),
+        Err(InterpreterError::Parse(_))
+    ));
+    assert!(matches!(
+        interp.eval("x = 1 / 0"),
+        Err(InterpreterError::Runtime(_))
+    ));
+    // The context stays usable after an error.
+    assert_eq!(interp.eval("return 5").unwrap(), Value::Int(5));
+    assert!(matches!(
+        interp.run_file("tests/resources/missing.mlc"),
+        Err(InterpreterError::Io { .. })
+    ));
+}
+
+#[test]
+fn test_run_file_with_host_builtins() {
+    let mut interp = interpreter();
+    interp.capture_output();
+    interp.context().builtins_mut().register(
+        "host",
+        "answer",
+        Arity::Exact(0),
+        "",
+        |_call, _args| Ok(Value::Int(42)),
+    );
+    interp
+        .run_file("tests/resources/standard/two_sum.mlc")
+        .unwrap();
+    assert_eq!(interp.take_output(), "[0, 1]\n");
+    assert_eq!(interp.eval("answer()").unwrap(), Value::Int(42));
+}
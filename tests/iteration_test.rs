@@ -0,0 +1,144 @@
+use multilang::budget::Limits;
+use multilang::error::ErrorKind;
+use multilang::grammar::Grammar;
+use multilang::interpreter::Interpreter;
+
+use crate::test_utils::{run_code_and_check, runtime_error, standard_grammar_with_for};
+
+mod test_utils;
+
+/// The standard grammar plus `for` loops with an optional index.
+fn grammar_source() -> String {
+    standard_grammar_with_for()
+        + "ForLoop = \"for\" index:Identifier \",\" variable:Identifier \"in\" iterable:Expr \"{\" body:Block \"}\"\n"
+}
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&grammar_source())
+}
+
+#[test]
+fn test_for_over_string_and_map() {
+    let code = r#"
+        for c in "abc" {
+            print(c)
+        }
+        m = {"b": 2, "a": 1}
+        for k in m {
+            print(k)
+        }
+        for k, v in m {
+            print(k + "=" + join([v], ""))
+        }
+    "#;
+    run_code_and_check(&get_grammar(), code, "a\nb\nc\na\nb\na=1\nb=2");
+}
+
+#[test]
+fn test_index_binding() {
+    let code = r#"
+        for i, x in ["a", "b"] {
+            print(i)
+            print(x)
+        }
+        for i, c in "hi" {
+            print(i)
+        }
+        for i, n in range(10, 12) {
+            print(i + n)
+        }
+    "#;
+    run_code_and_check(&get_grammar(), code, "0\na\n1\nb\n0\n1\n10\n12");
+}
+
+#[test]
+fn test_list_is_read_as_the_loop_goes() {
+    let code = r#"
+        xs = [1, 2]
+        for x in xs {
+            if x < 4 {
+                append(xs, x + 2)
+            }
+        }
+        print(xs)
+    "#;
+    run_code_and_check(&get_grammar(), code, "[1, 2, 3, 4, 5]");
+}
+
+#[test]
+fn test_range_in_for_loop_is_lazy() {
+    let mut interp = Interpreter::from_grammar_str(&grammar_source());
+    interp.capture_output();
+    interp.set_limits(Limits {
+        max_memory: Some(100),
+        ..Limits::default()
+    });
+    let code = r#"
+        total = 0
+        for i in range(0, 1000000000000, 2) {
+            if i > 10 {
+                break
+            }
+            total += i
+        }
+        print(total)
+        for x in range(0, 1, 0.25) {
+            total += x
+        }
+        print(total)
+    "#;
+    interp.eval(code).unwrap();
+    assert_eq!(interp.take_output(), "30\n31.5\n");
+}
+
+#[test]
+fn test_user_iterators() {
+    let code = r#"
+        class Countdown {
+            n
+            fn __next__() {
+                if this.n > 0 {
+                    this.n -= 1
+                    return this.n + 1
+                }
+            }
+        }
+        class Team {
+            members = ["ann", "bob"]
+            fn __iter__() {
+                return this.members
+            }
+        }
+        class Launch {
+            fn __iter__() {
+                return new Countdown(2)
+            }
+        }
+        for n in new Countdown(3) {
+            print(n)
+        }
+        for i, name in new Team() {
+            print(name)
+        }
+        for n in new Launch() {
+            print(n)
+        }
+    "#;
+    run_code_and_check(&get_grammar(), code, "3\n2\n1\nann\nbob\n2\n1");
+}
+
+#[test]
+fn test_iteration_errors() {
+    let err = runtime_error(&grammar_source(), "for x in 5 {\n}");
+    assert_eq!(err.kind, ErrorKind::TypeError);
+    assert_eq!(err.message, "Cannot iterate over Int");
+    let err = runtime_error(&grammar_source(), "class A {\n}\nfor x in new A() {\n}");
+    assert_eq!(err.kind, ErrorKind::TypeError);
+    assert_eq!(
+        err.message,
+        "Object of class 'A' is not iterable: it needs __iter__ or __next__"
+    );
+    let err = runtime_error(&grammar_source(), "for x in range(1, 2, 0) {\n}");
+    assert_eq!(err.kind, ErrorKind::ValueError);
+    assert_eq!(err.message, "range step must not be zero");
+}
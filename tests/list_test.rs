@@ -64,7 +64,7 @@ fn test_list_creation() {
     let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    let x = ctx.variables.get("x").expect("Variable x not found");
+    let x = ctx.get_variable("x").expect("Variable x not found");
     if let Value::List(list) = x {
         let list = list.borrow();
         assert_eq!(list.len(), 3);
@@ -85,7 +85,7 @@ fn test_list_append() {
     let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    let x = ctx.variables.get("x").expect("Variable x not found");
+    let x = ctx.get_variable("x").expect("Variable x not found");
     if let Value::List(list) = x {
         let list = list.borrow();
         assert_eq!(list.len(), 2);
@@ -105,8 +105,8 @@ fn test_list_get() {
     let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    let y = ctx.variables.get("y").expect("Variable y not found");
-    assert_eq!(*y, Value::Int(20));
+    let y = ctx.get_variable("y").expect("Variable y not found");
+    assert_eq!(y, Value::Int(20));
 }
 
 #[test]
@@ -118,7 +118,7 @@ fn test_nested_list() {
     let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    let x = ctx.variables.get("x").expect("Variable x not found");
+    let x = ctx.get_variable("x").expect("Variable x not found");
     if let Value::List(list) = x {
         let list = list.borrow();
         assert_eq!(list.len(), 2);
@@ -142,7 +142,7 @@ fn test_list_mutability() {
     let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    let x = ctx.variables.get("x").expect("Variable x not found");
+    let x = ctx.get_variable("x").expect("Variable x not found");
     if let Value::List(list) = x {
         let list = list.borrow();
         assert_eq!(list.len(), 2);
@@ -161,6 +161,6 @@ fn test_list_set() {
     let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    let x = ctx.variables.get("x").expect("Variable x not found");
-    assert_eq!(*x, Value::Int(10));
+    let x = ctx.get_variable("x").expect("Variable x not found");
+    assert_eq!(x, Value::Int(10));
 }
@@ -26,7 +26,8 @@ fn get_logical_grammar() -> Grammar {
         Unary = UnaryOp Unary | Atom
         Atom = Float | Int | String | Identifier | FunctionCall | "(" Expr ")"
         
-        UnaryOp = [!]
+        UnaryOp = Not
+        Not = [!]
         Eq = [==]
         Neq = [!=]
         Lt = [<]
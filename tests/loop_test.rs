@@ -74,8 +74,8 @@ fn test_while_loop() {
     let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    let sum = ctx.variables.get("sum").expect("Variable sum not found");
-    assert_eq!(*sum, Value::Int(10)); // 0+1+2+3+4 = 10
+    let sum = ctx.get_variable("sum").expect("Variable sum not found");
+    assert_eq!(sum, Value::Int(10)); // 0+1+2+3+4 = 10
 }
 
 #[test]
@@ -92,8 +92,8 @@ fn test_for_loop_literal() {
     let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    let sum = ctx.variables.get("sum").expect("Variable sum not found");
-    assert_eq!(*sum, Value::Int(6));
+    let sum = ctx.get_variable("sum").expect("Variable sum not found");
+    assert_eq!(sum, Value::Int(6));
 }
 
 #[test]
@@ -111,8 +111,8 @@ fn test_for_loop_variable() {
     let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    let sum = ctx.variables.get("sum").expect("Variable sum not found");
-    assert_eq!(*sum, Value::Int(60));
+    let sum = ctx.get_variable("sum").expect("Variable sum not found");
+    assert_eq!(sum, Value::Int(60));
 }
 
 #[test]
@@ -134,6 +134,29 @@ fn test_nested_loops() {
     let mut ctx = Context::new();
     node.run(&mut ctx).expect("Failed to run");
 
-    let sum = ctx.variables.get("sum").expect("Variable sum not found");
-    assert_eq!(*sum, Value::Int(66));
+    let sum = ctx.get_variable("sum").expect("Variable sum not found");
+    assert_eq!(sum, Value::Int(66));
+}
+
+#[test]
+fn test_return_from_for_loop() {
+    let code = "
+        fn first_sum(a, b) {
+            for x in a {
+                for y in b {
+                    return x + y
+                }
+            }
+            return 0
+        }
+        found = first_sum([5, 6, 7], [10, 20])
+    ";
+    let grammar = get_grammar();
+    let parser = Parser::new(&grammar, code);
+    let node = parser.parse(Rule::Program).expect("Failed to parse");
+    let mut ctx = Context::new();
+    node.run(&mut ctx).expect("Failed to run");
+
+    let found = ctx.get_variable("found").expect("Variable found not found");
+    assert_eq!(found, Value::Int(15));
 }
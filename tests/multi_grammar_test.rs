@@ -6,7 +6,8 @@ use crate::test_utils::run_code_and_check;
 mod test_utils;
 
 fn load_grammar(path: &str) -> Grammar {
-    let grammar_def = fs::read_to_string(path).expect(&format!("Failed to read grammar: {}", path));
+    let grammar_def =
+        fs::read_to_string(path).unwrap_or_else(|_| panic!("Failed to read grammar: {}", path));
     Grammar::parse(&grammar_def)
 }
 
@@ -36,6 +37,13 @@ fn test_standard_fizzbuzz() {
     );
 }
 
+#[test]
+fn test_standard_compound() {
+    let grammar = load_grammar("tests/resources/standard/grammar.mlg");
+    let mlc = fs::read_to_string("tests/resources/standard/compound.mlc").unwrap();
+    run_code_and_check(&grammar, &mlc, "55\n[7, 5, 3, 1]\n[1, 20, 3]");
+}
+
 // Wordy Grammar Tests
 #[test]
 fn test_wordy_two_sum() {
@@ -62,6 +70,13 @@ fn test_wordy_fizzbuzz() {
     );
 }
 
+#[test]
+fn test_wordy_compound() {
+    let grammar = load_grammar("tests/resources/wordy/grammar.mlg");
+    let mlc = fs::read_to_string("tests/resources/wordy/compound.mlc").unwrap();
+    run_code_and_check(&grammar, &mlc, "55\n[7, 5, 3, 1]");
+}
+
 // Cryptic Grammar Tests
 #[test]
 fn test_cryptic_two_sum() {
@@ -87,3 +102,10 @@ fn test_cryptic_fizzbuzz() {
         "[1, 2, Fizz, 4, Buzz, Fizz, 7, 8, Fizz, Buzz, 11, Fizz, 13, 14, FizzBuzz]",
     );
 }
+
+#[test]
+fn test_cryptic_compound() {
+    let grammar = load_grammar("tests/resources/cryptic/grammar.mlg");
+    let mlc = fs::read_to_string("tests/resources/cryptic/compound.mlc").unwrap();
+    run_code_and_check(&grammar, &mlc, "55\n[7, 5, 3, 1]");
+}
@@ -0,0 +1,80 @@
+use multilang::grammar::Grammar;
+use std::fs;
+
+use crate::test_utils::run_code_and_check;
+
+mod test_utils;
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&fs::read_to_string("tests/resources/standard/grammar.mlg").unwrap())
+}
+
+/// The standard grammar with `/` producing floats and `~/` truncating, since
+/// `//` starts a comment.
+fn float_division_grammar() -> Grammar {
+    let source = fs::read_to_string("tests/resources/standard/grammar.mlg")
+        .unwrap()
+        .replace(
+            "Unary Div Factor",
+            "Unary IntDiv Factor | Unary FloatDiv Factor",
+        )
+        .replace("Div = [/]", "IntDiv = [~/]\nFloatDiv = [/]");
+    Grammar::parse(&source)
+}
+
+#[test]
+fn test_mixed_arithmetic_promotes_to_float() {
+    let code = r#"
+        print(1 + 2.5)
+        print(2.5 - 1)
+        print(3 * 0.5)
+        print(7 / 2.0)
+        print(7.5 % 2)
+        print(0 - 1.5)
+    "#;
+    run_code_and_check(&get_grammar(), code, "3.5\n1.5\n1.5\n3.5\n1.5\n-1.5");
+}
+
+#[test]
+fn test_mixed_comparison() {
+    let code = r#"
+        print(3 < 4.0)
+        print(4.5 > 4)
+        print(2 == 2.0)
+        print(2 != 2.5)
+        print(1.5d < 2.0)
+    "#;
+    run_code_and_check(&get_grammar(), code, "true\ntrue\ntrue\ntrue\ntrue");
+}
+
+#[test]
+fn test_division_rules() {
+    run_code_and_check(&get_grammar(), "print(7 / 2)", "3");
+
+    let code = r#"
+        print(7 / 2)
+        print(7 ~/ 2)
+        print(7.5 ~/ 2)
+        print((0 - 7) ~/ 2)
+        print(1.0 / 0)
+    "#;
+    run_code_and_check(&float_division_grammar(), code, "3.5\n3\n3\n-3\ninf");
+}
+
+#[test]
+fn test_builtins_mix_numbers() {
+    let code = r#"
+        print(abs(0 - 2.5))
+        print(sum([1, 2.5, 3]))
+        print(sum([1, 2]))
+        print(range(3))
+        print(range(5, 0, 0 - 2))
+        print(range(0, 1, 0.25))
+        print(range(1, 2.0))
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "2.5\n6.5\n3\n[0, 1, 2]\n[5, 3, 1]\n[0, 0.25, 0.5, 0.75]\n[1]",
+    );
+}
@@ -0,0 +1,181 @@
+use multilang::error::InterpreterError;
+use multilang::grammar::{Grammar, Rule};
+use multilang::interpreter::Interpreter;
+use multilang::parser::Parser;
+use std::fs;
+
+use crate::test_utils::run_code_and_check;
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&fs::read_to_string(GRAMMAR).unwrap())
+}
+
+#[test]
+fn test_comparison_operators() {
+    let code = r#"
+        print(3 <= 3)
+        print(3 <= 2)
+        print(3 >= 2.5)
+        print(2.0 >= 2)
+        print("abc" <= "abd")
+        print((1 == 2) >= (1 == 1))
+        print(1 < 2)
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "true\nfalse\ntrue\ntrue\ntrue\nfalse\ntrue",
+    );
+}
+
+#[test]
+fn test_power() {
+    let code = r#"
+        print(2 ** 10)
+        print(2 ** 3 ** 2)
+        print(2 ** 100)
+        print(2 ** (0 - 1))
+        print(1.5d ** 2)
+        print(4 ** 0.5)
+        print(3 * 2 ** 2)
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "1024\n512\n1267650600228229401496703205376\n0.5\n2.25\n2\n12",
+    );
+}
+
+#[test]
+fn test_bitwise_operators() {
+    let code = r#"
+        print(12 & 10)
+        print(12 | 10)
+        print(12 ^ 10)
+        print(~5)
+        print(1 << 4)
+        print(1 + 1 << 2)
+        print(0 - 16 >> 2)
+        print(1 << 70)
+        print((1 << 70) >> 69)
+        print((1 == 1) & (1 == 2))
+        print((1 == 1) ^ (1 == 2))
+        print(1 | 2 == 3)
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "8\n14\n6\n-6\n16\n8\n-4\n1180591620717411303424\n2\nfalse\ntrue\ntrue",
+    );
+}
+
+#[test]
+fn test_logical_operators_still_parse() {
+    let code = r#"
+        print(1 == 1 || 1 == 2)
+        print(1 == 1 && 1 | 2 == 3)
+    "#;
+    run_code_and_check(&get_grammar(), code, "true\ntrue");
+}
+
+#[test]
+fn test_operators_resolve_by_rule() {
+    let grammar = Grammar::parse(
+        r#"
+        Program = Stmt*
+        Stmt = Print
+        Print = "say" Expr
+        Expr = Comparison
+        Comparison = Term Le Term | Term Ge Term | Term Eq Term | Term
+        Term = Factor Add Term | Factor
+        Factor = Unary IntDiv Factor | Unary Mul Factor | Unary
+        Unary = Neg Unary | Not Unary | Power
+        Power = Atom Pow Unary | Atom
+        Atom = Int | True | False
+        Le = [atmost]
+        Ge = [atleast]
+        Eq = [is]
+        Add = [plus]
+        Mul = [times]
+        IntDiv = [over]
+        Pow = [tothe]
+        Neg = [minus]
+        Not = [not]
+        True = [yes]
+        False = [no]
+        Int = [[0-9]+]
+    "#,
+    );
+    let code = r#"
+        say 3 atmost 4
+        say 5 atleast 6
+        say minus 2 tothe 2
+        say 7 over 2
+        say not yes is no
+    "#;
+    run_code_and_check(&grammar, code, "true\nfalse\n-4\n3\ntrue");
+}
+
+#[test]
+fn test_unary_op_resolves_to_operator_rule() {
+    let grammar_def = r#"
+        Program = Stmt*
+        Stmt = Print
+        Print = "say" Expr
+        Expr = Comparison
+        Comparison = Term Eq Term | Term
+        Term = Unary
+        Unary = UnaryOp Unary | Atom
+        Atom = Int | True | False
+        UnaryOp = Neg | Not | BitNot
+        Neg = [-]
+        Not = [!]
+        BitNot = [~]
+        Eq = [==]
+        True = [yes]
+        False = [no]
+        Int = [[0-9]+]
+    "#;
+    run_code_and_check(
+        &Grammar::parse(grammar_def),
+        "say -3\nsay !yes == no\nsay ~5",
+        "-3\ntrue\n-6",
+    );
+
+    let grammar =
+        Grammar::parse(&grammar_def.replace("UnaryOp = Neg | Not | BitNot", "UnaryOp = [-]"));
+    let err = Parser::new(&grammar, "say -3")
+        .parse(Rule::Program)
+        .err()
+        .expect("Expected a parse error");
+    assert_eq!(err.message, "UnaryOp must match one of Not, Neg or BitNot");
+}
+
+#[test]
+fn test_operator_errors() {
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    let message = |result| match result {
+        Err(InterpreterError::Runtime(e)) => e.message,
+        other => panic!("Expected runtime error, got {:?}", other),
+    };
+    assert_eq!(
+        message(interp.eval("1 << (0 - 1)")),
+        "Negative shift amount -1"
+    );
+    assert_eq!(
+        message(interp.eval("1.5 & 1")),
+        "Invalid operands for bitwise operation: Float(1.5) and Int(1)"
+    );
+    assert_eq!(
+        message(interp.eval("~(1 == 1)")),
+        "Expected integer for bitwise NOT, got Bool(true)"
+    );
+    assert_eq!(
+        message(interp.eval("\"a\" ** 2")),
+        "Invalid operands for exponentiation: String(RefCell { value: \"a\" }) and Int(2)"
+    );
+}
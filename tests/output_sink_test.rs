@@ -0,0 +1,100 @@
+use multilang::grammar::{Grammar, Rule};
+use multilang::interpreter::Interpreter;
+use multilang::node::Context;
+use multilang::output::{Output, Stream};
+use multilang::parser::Parser;
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::rc::Rc;
+use std::thread;
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn run(ctx: &mut Context, code: &str) {
+    let grammar = Grammar::parse(&fs::read_to_string(GRAMMAR).unwrap());
+    let node = Parser::new(&grammar, code)
+        .parse(Rule::Program)
+        .expect("Failed to parse");
+    node.run(ctx).expect("Failed to run");
+}
+
+/// Records every write and flush in order.
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Rc<RefCell<Vec<String>>>,
+}
+
+impl Output for Recorder {
+    fn write(&mut self, stream: Stream, text: &str) -> io::Result<()> {
+        self.events
+            .borrow_mut()
+            .push(format!("{:?}:{}", stream, text.trim_end()));
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.events.borrow_mut().push("flush".to_string());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_custom_output_sees_both_streams() {
+    let recorder = Recorder::default();
+    let mut ctx = Context::new();
+    ctx.set_output(recorder.clone());
+
+    run(
+        &mut ctx,
+        "print(1, \"a\")\neprint(\"oops\")\nflush()\nfn f() {\n print(2)\n}\nf()",
+    );
+    assert_eq!(
+        *recorder.events.borrow(),
+        vec!["Stdout:1 a", "Stderr:oops", "flush", "Stdout:2"]
+    );
+}
+
+#[test]
+fn test_capture_separates_streams() {
+    let (mut ctx, output) = test_utils::capture_output();
+    run(
+        &mut ctx,
+        "print(\"out\")\neprint(\"err\", 1)\nprint(map([1], fn(x) { print(x) }))",
+    );
+    assert_eq!(output.stdout(), "out\n1\n[(void)]\n");
+    assert_eq!(output.stderr(), "err 1\n");
+    assert_eq!(output.take_stdout(), "out\n1\n[(void)]\n");
+    assert_eq!(output.stdout(), "");
+}
+
+#[test]
+fn test_interpreter_error_output() {
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    interp.capture_output();
+    interp.eval("eprint(\"warning\")\nprint(\"done\")").unwrap();
+    assert_eq!(interp.take_error_output(), "warning\n");
+    assert_eq!(interp.take_output(), "done\n");
+}
+
+#[test]
+fn test_capture_is_independent_per_thread() {
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            thread::spawn(move || {
+                let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+                interp.capture_output();
+                for _ in 0..50 {
+                    interp.eval(&format!("print({})", i)).unwrap();
+                }
+                interp.take_output()
+            })
+        })
+        .collect();
+
+    for (i, handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.join().unwrap(), format!("{}\n", i).repeat(50));
+    }
+}
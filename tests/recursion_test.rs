@@ -0,0 +1,78 @@
+use multilang::convert::IntoValue;
+use multilang::error::{ErrorKind, MAX_STACK_TRACE};
+use multilang::interpreter::Interpreter;
+use multilang::node::DEFAULT_MAX_DEPTH;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+const SUM_TO: &str = r#"
+    fn sum_to(n) {
+        if n == 0 {
+            return 0
+        }
+        return n + sum_to(n - 1)
+    }
+"#;
+
+fn interpreter() -> Interpreter {
+    Interpreter::from_grammar_file(GRAMMAR).unwrap()
+}
+
+#[test]
+fn test_runaway_recursion_is_an_error() {
+    let mut interp = interpreter();
+    interp
+        .eval("fn forever(n) {\n return forever(n + 1)\n}")
+        .unwrap();
+    let err = match interp.call("forever", vec![0.into_value()]) {
+        Err(multilang::error::InterpreterError::Runtime(err)) => err,
+        other => panic!("Expected runtime error, got {:?}", other),
+    };
+
+    assert_eq!(err.message, "maximum recursion depth exceeded");
+    assert_eq!(err.kind, ErrorKind::RecursionLimit);
+    assert_eq!(err.stack_trace.len(), MAX_STACK_TRACE);
+    assert_eq!(err.stack_trace[0].to_string(), "at forever:2");
+    // One frame per nested call; the outermost call from the host has none.
+    assert_eq!(err.omitted_frames, DEFAULT_MAX_DEPTH - MAX_STACK_TRACE);
+    assert!(
+        err.to_string()
+            .ends_with(&format!("... {} more frames", err.omitted_frames))
+    );
+}
+
+#[test]
+fn test_depth_limit_is_configurable() {
+    let mut interp = interpreter();
+    interp.eval(SUM_TO).unwrap();
+    interp.context().max_depth = 10;
+
+    let sum: i32 = interp.eval_as("sum_to(8)").unwrap();
+    assert_eq!(sum, 36);
+    let err = interp.eval("sum_to(10)").unwrap_err();
+    assert!(err.to_string().contains("maximum recursion depth exceeded"));
+
+    // The interpreter is still usable after the error.
+    let sum: i32 = interp.eval_as("sum_to(3)").unwrap();
+    assert_eq!(sum, 6);
+}
+
+#[test]
+fn test_deep_non_tail_recursion() {
+    let mut interp = interpreter();
+    interp.eval(SUM_TO).unwrap();
+    interp.context().max_depth = 20000;
+
+    let sum: i32 = interp.eval_as("sum_to(10000)").unwrap();
+    assert_eq!(sum, 50005000);
+}
+
+#[test]
+fn test_recursion_through_callbacks_is_limited() {
+    let mut interp = interpreter();
+    interp.context().max_depth = 50;
+    let err = interp
+        .eval("fn nest(x) {\n return map([x], nest)\n}\nnest(1)")
+        .unwrap_err();
+    assert!(err.to_string().contains("maximum recursion depth exceeded"));
+}
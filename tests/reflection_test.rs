@@ -0,0 +1,147 @@
+use multilang::error::ErrorKind;
+use multilang::grammar::Grammar;
+use std::fs;
+
+use crate::test_utils::{run_code_and_check, runtime_error, standard_grammar};
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&fs::read_to_string(GRAMMAR).unwrap())
+}
+
+const SHAPES: &str = r#"
+    class Shape {
+        name
+        static count = 0
+        fn init(name) {
+            this.name = name
+            Shape.count += 1
+        }
+        fn describe() {
+            return this.name
+        }
+    }
+    class Circle extends Shape {
+        radius = 1
+        fn area() {
+            return 3 * this.radius * this.radius
+        }
+    }
+"#;
+
+#[test]
+fn test_static_methods_and_fields() {
+    let code = r#"
+        class Point {
+            x
+            y
+            static dimensions = 2
+            static origin = new Point(0, 0)
+            static fn make(x, y) {
+                return new Point(x, y)
+            }
+            static fn unit() {
+                return Point.make(1, 1)
+            }
+        }
+        p = Point.unit()
+        print(p.x + p.y)
+        print(Point.dimensions)
+        print(Point.origin.x)
+        Point.dimensions = 3
+        print(Point.dimensions)
+        make = Point.make
+        print(make(4, 5).y)
+    "#;
+    run_code_and_check(&get_grammar(), code, "2\n2\n0\n3\n5");
+}
+
+#[test]
+fn test_static_members_are_shared_and_inherited() {
+    let code = SHAPES.to_string()
+        + r#"
+        a = new Shape("square")
+        b = new Circle("circle")
+        print(Shape.count)
+        print(Circle.count)
+    "#;
+    run_code_and_check(&get_grammar(), &code, "2\n2");
+}
+
+#[test]
+fn test_variables_shadow_class_names() {
+    let code = r#"
+        class Box {
+            static size = 1
+        }
+        Box = {"size": 5}
+        print(Box["size"])
+    "#;
+    run_code_and_check(&get_grammar(), code, "5");
+}
+
+#[test]
+fn test_reflection_builtins() {
+    let code = SHAPES.to_string()
+        + r#"
+        c = new Circle("c")
+        print(class_of(c))
+        print(fields(c))
+        print(fields("Shape"))
+        print(methods(c))
+        print(has_field(c, "radius"))
+        print(has_field("Shape", "radius"))
+        print(has_method(c, "describe"))
+        print(has_method(c, "fly"))
+        print(isinstance(c, "Shape"))
+        print(isinstance(new Shape("s"), "Circle"))
+        print(isinstance(1, "Shape"))
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        &code,
+        "Circle\n[name, radius]\n[name]\n[area, describe, init]\ntrue\nfalse\ntrue\nfalse\ntrue\nfalse\nfalse",
+    );
+}
+
+#[test]
+fn test_print_shows_fields() {
+    let code = r#"
+        class Node {
+            value
+            next
+        }
+        a = new Node(1, [])
+        b = new Node("two", a)
+        print(b)
+        a.next = b
+        print(a)
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        code,
+        "Node(next: Node(next: [], value: 1), value: two)\nNode(next: Node(next: Node(...), value: two), value: 1)",
+    );
+}
+
+#[test]
+fn test_reflection_errors() {
+    let err = runtime_error(&standard_grammar(), "class A {\n}\nA.missing()");
+    assert_eq!(err.kind, ErrorKind::KeyError);
+    assert_eq!(
+        err.message,
+        "Static method 'missing' not found in class 'A'"
+    );
+    let err = runtime_error(&standard_grammar(), "class A {\n}\nA.x = 1");
+    assert_eq!(err.kind, ErrorKind::KeyError);
+    assert_eq!(err.message, "Class 'A' has no static field 'x'");
+    let err = runtime_error(&standard_grammar(), "class_of(1)");
+    assert_eq!(err.kind, ErrorKind::TypeError);
+    assert_eq!(err.message, "class_of expects an object, got Int");
+    let err = runtime_error(&standard_grammar(), "fields(\"Nope\")");
+    assert_eq!(err.kind, ErrorKind::NameError);
+    assert_eq!(err.message, "Undefined class 'Nope'");
+}
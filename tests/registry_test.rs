@@ -0,0 +1,145 @@
+use multilang::error::RuntimeError;
+use multilang::functions::registry::{Arity, BuiltInRegistry};
+use multilang::grammar::{Grammar, Rule};
+use multilang::node::{Context, Value};
+use multilang::parser::Parser;
+use std::cell::RefCell;
+use std::fs;
+use std::rc::Rc;
+
+fn get_grammar() -> Grammar {
+    let grammar_def = fs::read_to_string("tests/resources/standard/grammar.mlg").unwrap();
+    Grammar::parse(&grammar_def)
+}
+
+fn run(ctx: &mut Context, code: &str) -> Result<Value, RuntimeError> {
+    let grammar = get_grammar();
+    let node = Parser::new(&grammar, code)
+        .parse(Rule::Program)
+        .expect("Failed to parse");
+    node.run(ctx)
+}
+
+#[test]
+fn test_stateful_closure_builtin() {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let log = calls.clone();
+
+    let mut ctx = Context::new();
+    ctx.builtins_mut().register(
+        "host",
+        "record",
+        Arity::Exact(1),
+        "Records a value on the host side.",
+        move |_call, args| {
+            log.borrow_mut().push(args[0].clone());
+            Ok(Value::Int(log.borrow().len() as i64))
+        },
+    );
+
+    let result = run(&mut ctx, "record(10)\nrecord(\"a\")\nreturn record(3)").unwrap();
+    assert_eq!(result, Value::Int(3));
+    assert_eq!(calls.borrow().len(), 3);
+    assert_eq!(calls.borrow()[0], Value::Int(10));
+}
+
+#[test]
+fn test_builtins_visible_inside_functions() {
+    let counter = Rc::new(RefCell::new(0));
+    let shared = counter.clone();
+
+    let mut ctx = Context::new();
+    ctx.builtins_mut()
+        .register("host", "tick", Arity::Exact(0), "", move |_call, _args| {
+            *shared.borrow_mut() += 1;
+            Ok(Value::Void)
+        });
+
+    run(
+        &mut ctx,
+        "fn twice() {\n tick()\n tick()\n}\ntwice()\nmap([1, 2], fn(x) { tick() })",
+    )
+    .unwrap();
+    assert_eq!(*counter.borrow(), 4);
+}
+
+#[test]
+fn test_builtin_calls_back_into_script() {
+    let mut ctx = Context::new();
+    ctx.builtins_mut().register(
+        "host",
+        "call_twice",
+        Arity::Exact(2),
+        "Applies a function twice.",
+        |call, args| {
+            let once = call.call(&args[0], vec![args[1].clone()])?;
+            call.call(&args[0], vec![once])
+        },
+    );
+
+    let result = run(&mut ctx, "return call_twice(fn(x) { x * 3 }, 2)").unwrap();
+    assert_eq!(result, Value::Int(18));
+}
+
+#[test]
+fn test_arity_is_checked_before_call() {
+    let mut ctx = Context::new();
+    ctx.builtins_mut()
+        .register("host", "pair", Arity::Range(1, 2), "", |_call, _args| {
+            panic!("Should not be called with a bad argument count")
+        });
+
+    let err = run(&mut ctx, "pair(1, 2, 3)").unwrap_err();
+    assert_eq!(err.message, "pair expects 1 or 2 arguments, got 3");
+    let err = run(&mut ctx, "len()").unwrap_err();
+    assert_eq!(err.message, "len expects 1 argument, got 0");
+}
+
+#[test]
+fn test_disable_and_enable_modules() {
+    let mut ctx = Context::new();
+    ctx.builtins_mut().disable_module("io");
+
+    let err = run(&mut ctx, "read_file(\"x\")").unwrap_err();
+    assert_eq!(err.message, "Function 'read_file' not found");
+    assert!(!ctx.builtins().is_module_enabled("io"));
+    assert!(ctx.builtins().list().iter().all(|b| b.module != "io"));
+
+    ctx.builtins_mut().enable_module("io");
+    assert!(ctx.builtins().get("read_file").is_some());
+}
+
+#[test]
+fn test_custom_registry() {
+    let mut registry = BuiltInRegistry::new();
+    registry.register("host", "answer", Arity::Exact(0), "", |_call, _args| {
+        Ok(Value::Int(42))
+    });
+
+    let mut ctx = Context::with_builtins(registry);
+    assert_eq!(run(&mut ctx, "return answer()").unwrap(), Value::Int(42));
+    let err = run(&mut ctx, "len([])").unwrap_err();
+    assert_eq!(err.message, "Function 'len' not found");
+}
+
+#[test]
+fn test_standard_registry_metadata() {
+    let registry = BuiltInRegistry::standard();
+    assert_eq!(
+        registry.modules(),
+        vec![
+            "collections",
+            "conversions",
+            "functional",
+            "io",
+            "math",
+            "reflection",
+            "strings"
+        ]
+    );
+    let len = registry.get("len").unwrap();
+    assert_eq!(len.module, "collections");
+    assert_eq!(len.arity, Arity::Exact(1));
+    assert!(!len.doc.is_empty());
+    assert!(registry.list().iter().all(|b| !b.doc.is_empty()));
+}
@@ -0,0 +1,208 @@
+use multilang::grammar::{Grammar, Rule};
+use multilang::node::Context;
+use multilang::parser::Parser;
+
+use crate::test_utils::run_code_and_check;
+
+mod test_utils;
+
+fn get_grammar() -> Grammar {
+    let grammar_def = r##"
+        Program = Stmt*
+        Stmt = FunctionDef | Return | Declaration | Nonlocal | IfThen | ForLoop | FunctionCall | Assignment | Expr | "{" Block "}"
+
+        FunctionDef = "fn" name:Identifier "(" params:ParamList ")" "{" body:Block "}"
+        FunctionDef = "fn" name:Identifier "(" ")" "{" body:Block "}"
+        Return = "return" value:Expr
+        Declaration = "let" name:Identifier "=" value:Expr
+        Nonlocal = "nonlocal" names:ParamList
+        IfThen = "if" condition:Expr "{" then:Block "}"
+        ForLoop = "for" variable:Identifier "in" iterable:Expr "{" body:Block "}"
+        Block = Stmt*
+
+        FunctionCall = name:Identifier "(" args:ArgList ")"
+        FunctionCall = name:Identifier "(" ")"
+        Assignment = name:Identifier "=" value:Expr
+
+        ParamList = Identifier "," params:ParamList
+        ParamList = Identifier
+        ArgList = Expr "," args:ArgList
+        ArgList = Expr
+
+        Expr = Comparison
+        Comparison = Term Gt Term | Term
+        Term = Factor Add Term | Factor
+        Factor = Int | FunctionCall | Identifier | ListLiteral
+        ListLiteral = "[" Elements "]"
+        Elements = Expr "," Elements
+        Elements = Expr
+
+        Gt = [>]
+        Add = [\+]
+        Int = [[0-9]+]
+        Identifier = [[a-zA-Z_][a-zA-Z0-9_]*]
+    "##;
+    Grammar::parse(grammar_def)
+}
+
+fn run(code: &str) -> Context {
+    let grammar = get_grammar();
+    let node = Parser::new(&grammar, code)
+        .parse(Rule::Program)
+        .expect("Failed to parse");
+    let mut ctx = Context::new();
+    node.run(&mut ctx).expect("Failed to run");
+    ctx
+}
+
+#[test]
+fn test_function_reads_global() {
+    let code = r#"
+        base = 10
+        fn add_base(x) {
+            return x + base
+        }
+        print(add_base(5))
+    "#;
+    run_code_and_check(&get_grammar(), code, "15");
+}
+
+#[test]
+fn test_function_assignment_is_local() {
+    let code = r#"
+        count = 1
+        fn shadow() {
+            count = 100
+            return count
+        }
+        print(shadow())
+        print(count)
+    "#;
+    run_code_and_check(&get_grammar(), code, "100\n1");
+}
+
+#[test]
+fn test_nested_function_captures_locals() {
+    let code = r#"
+        fn outer(x) {
+            offset = 5
+            fn helper(y) {
+                return x + y + offset
+            }
+            return helper(1)
+        }
+        print(outer(10))
+    "#;
+    run_code_and_check(&get_grammar(), code, "16");
+}
+
+#[test]
+fn test_declaration_is_block_scoped() {
+    let code = r#"
+        x = 1
+        {
+            let x = 2
+            print(x)
+            {
+                let y = 3
+                print(x + y)
+            }
+        }
+        print(x)
+    "#;
+    run_code_and_check(&get_grammar(), code, "2\n5\n1");
+
+    let ctx = run("{ let hidden = 1 }");
+    assert!(ctx.get_variable("hidden").is_none());
+}
+
+#[test]
+fn test_assignment_in_block_updates_function_scope() {
+    let code = r#"
+        fn pick(flag) {
+            if flag > 0 {
+                result = 1
+            }
+            if 1 > flag {
+                result = 2
+            }
+            return result
+        }
+        print(pick(1))
+        print(pick(0))
+    "#;
+    run_code_and_check(&get_grammar(), code, "1\n2");
+}
+
+#[test]
+fn test_assignment_updates_shadowing_declaration() {
+    let code = r#"
+        x = 1
+        {
+            let x = 2
+            x = 3
+            print(x)
+        }
+        print(x)
+    "#;
+    run_code_and_check(&get_grammar(), code, "3\n1");
+}
+
+#[test]
+fn test_nonlocal_updates_outer_binding() {
+    let code = r#"
+        total = 0
+        fn add(n) {
+            nonlocal total
+            total = total + n
+        }
+        add(2)
+        add(3)
+        print(total)
+
+        fn counter() {
+            count = 0
+            fn bump(a, b) {
+                nonlocal count, unused
+                count = count + a + b
+            }
+            bump(1, 2)
+            bump(3, 4)
+            return count
+        }
+        print(counter())
+    "#;
+    run_code_and_check(&get_grammar(), code, "5\n10");
+}
+
+#[test]
+fn test_nonlocal_without_binding_is_error() {
+    let grammar = get_grammar();
+    let code = r#"
+        fn broken() {
+            nonlocal missing
+            missing = 1
+        }
+        broken()
+    "#;
+    let node = Parser::new(&grammar, code)
+        .parse(Rule::Program)
+        .expect("Failed to parse");
+    let err = node.run(&mut Context::new()).expect_err("Expected error");
+    assert_eq!(err.message, "No binding for nonlocal variable 'missing'");
+}
+
+#[test]
+fn test_for_variable_is_loop_scoped() {
+    let ctx = run(r#"
+        sum = 0
+        for item in [1, 2, 3] {
+            sum = sum + item
+        }
+    "#);
+    assert_eq!(
+        ctx.get_variable("sum"),
+        Some(multilang::node::Value::Int(6))
+    );
+    assert!(ctx.get_variable("item").is_none());
+}
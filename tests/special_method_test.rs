@@ -0,0 +1,158 @@
+use multilang::error::ErrorKind;
+use multilang::grammar::Grammar;
+
+use crate::test_utils::{run_code_and_check, runtime_error, standard_grammar_with_for};
+
+mod test_utils;
+
+/// The standard grammar plus a `for` loop and prefix negation.
+fn grammar_source() -> String {
+    standard_grammar_with_for().replace("Unary = ", "Unary = Neg Unary | ") + "Neg = [-]\n"
+}
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&grammar_source())
+}
+
+const VECTOR: &str = r#"
+    class Vector {
+        x
+        y
+        fn __add__(other) {
+            return new Vector(this.x + other.x, this.y + other.y)
+        }
+        fn __sub__(other) {
+            return new Vector(this.x - other.x, this.y - other.y)
+        }
+        fn __mul__(k) {
+            return new Vector(this.x * k, this.y * k)
+        }
+        fn __neg__() {
+            return new Vector(0 - this.x, 0 - this.y)
+        }
+        fn __eq__(other) {
+            return this.x == other.x && this.y == other.y
+        }
+        fn __lt__(other) {
+            return this.x * this.x + this.y * this.y < other.x * other.x + other.y * other.y
+        }
+        fn __str__() {
+            return "(" + join([this.x, this.y], ", ") + ")"
+        }
+    }
+"#;
+
+#[test]
+fn test_arithmetic_operators() {
+    let code = VECTOR.to_string()
+        + r#"
+        a = new Vector(1, 2)
+        b = new Vector(3, 4)
+        print(a + b)
+        print(b - a)
+        print(a * 3)
+        print(-a)
+        a += b
+        print(a)
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        &code,
+        "(4, 6)\n(2, 2)\n(3, 6)\n(-1, -2)\n(4, 6)",
+    );
+}
+
+#[test]
+fn test_comparison_operators() {
+    let code = VECTOR.to_string()
+        + r#"
+        a = new Vector(1, 2)
+        b = new Vector(3, 4)
+        print(a == new Vector(1, 2))
+        print(a != b)
+        print(a < b)
+        print(a > b)
+        print(a <= new Vector(2, 1))
+        print(b >= a)
+    "#;
+    run_code_and_check(&get_grammar(), &code, "true\ntrue\ntrue\nfalse\ntrue\ntrue");
+}
+
+#[test]
+fn test_str_is_used_by_print_inside_collections() {
+    let code = VECTOR.to_string()
+        + r#"
+        v = new Vector(1, 2)
+        print([v, new Vector(0, 0)])
+        print({"at": v})
+        print("v = " + v.__str__())
+    "#;
+    run_code_and_check(
+        &get_grammar(),
+        &code,
+        "[(1, 2), (0, 0)]\n{at: (1, 2)}\nv = (1, 2)",
+    );
+}
+
+#[test]
+fn test_container_methods() {
+    let code = r#"
+        class Bag {
+            items = []
+            fn __len__() {
+                return len(this.items)
+            }
+            fn __getitem__(i) {
+                return this.items[i]
+            }
+            fn __setitem__(i, value) {
+                this.items[i] = value
+            }
+            fn __iter__() {
+                return this.items
+            }
+        }
+        b = new Bag()
+        append(b.items, 1)
+        append(b.items, 2)
+        print(len(b))
+        b[1] = 20
+        print(b[1])
+        b[0] += 10
+        total = 0
+        for x in b {
+            total += x
+        }
+        print(total)
+    "#;
+    run_code_and_check(&get_grammar(), code, "2\n20\n31");
+}
+
+#[test]
+fn test_special_methods_are_inherited() {
+    let code = VECTOR.to_string()
+        + r#"
+        class Point extends Vector {
+        }
+        print(new Point(1, 1) + new Point(2, 2))
+    "#;
+    run_code_and_check(&get_grammar(), &code, "(3, 3)");
+}
+
+#[test]
+fn test_special_method_errors() {
+    let err = runtime_error(&grammar_source(), "class A {\n}\nnew A() + 1");
+    assert_eq!(err.kind, ErrorKind::TypeError);
+    let err = runtime_error(
+        &grammar_source(),
+        "class A {\n fn __len__() {\n return \"x\"\n }\n}\nlen(new A())",
+    );
+    assert_eq!(err.kind, ErrorKind::TypeError);
+    assert_eq!(err.message, "__len__ must return an Int, got String");
+    let err = runtime_error(
+        &grammar_source(),
+        "class A {\n fn __str__() {\n return 1\n }\n}\nprint(new A())",
+    );
+    assert_eq!(err.kind, ErrorKind::TypeError);
+    assert_eq!(err.message, "__str__ must return a String, got Int");
+}
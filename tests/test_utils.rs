@@ -1,27 +1,37 @@
-use std::cell::RefCell;
-use std::rc::Rc;
+use multilang::error::{InterpreterError, RuntimeError};
+use multilang::grammar::{Grammar, Rule};
+use multilang::interpreter::Interpreter;
+use multilang::node::Context;
+use multilang::output::CaptureOutput;
+use multilang::parser::Parser;
+use std::fs;
 
-pub struct TestLogGuard;
+#[allow(dead_code)]
+pub const STANDARD_GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
 
-impl Drop for TestLogGuard {
-    fn drop(&mut self) {
-        multilang::functions::print::TEST_LOGS.with(|logs| {
-            *logs.borrow_mut() = None;
-        });
-    }
+/// The source of the standard grammar.
+#[allow(dead_code)]
+pub fn standard_grammar() -> String {
+    fs::read_to_string(STANDARD_GRAMMAR).unwrap()
 }
 
-pub fn capture_output() -> (Rc<RefCell<Vec<String>>>, TestLogGuard) {
-    let logs = Rc::new(RefCell::new(Vec::new()));
-    multilang::functions::print::TEST_LOGS.with(|l| {
-        *l.borrow_mut() = Some(logs.clone());
-    });
-    (logs, TestLogGuard)
+/// The standard grammar plus `for` loops.
+#[allow(dead_code)]
+pub fn standard_grammar_with_for() -> String {
+    standard_grammar().replace("Stmt = ", "Stmt = ForLoop | ")
+        + "\nForLoop = \"for\" variable:Identifier \"in\" iterable:Expr \"{\" body:Block \"}\"\n"
 }
-use multilang::grammar::{Grammar, Rule};
-use multilang::node::Context;
-use multilang::parser::Parser;
 
+/// A context whose output is collected in the returned `CaptureOutput`.
+#[allow(dead_code)]
+pub fn capture_output() -> (Context, CaptureOutput) {
+    let output = CaptureOutput::new();
+    let mut ctx = Context::new();
+    ctx.set_output(output.clone());
+    (ctx, output)
+}
+
+#[allow(dead_code)]
 pub fn run_code_and_check(grammar: &Grammar, code: &str, expected: &str) {
     let parser = Parser::new(grammar, code);
     let node = parser
@@ -29,14 +39,23 @@ pub fn run_code_and_check(grammar: &Grammar, code: &str, expected: &str) {
         .map_err(|e| format!("Failed to parse: {}", e))
         .expect("Parse error");
 
-    let (logs, _guard) = capture_output();
-    let mut ctx = Context::new();
+    let (mut ctx, output) = capture_output();
     node.run(&mut ctx)
         .map_err(|e| format!("Runtime error: {}", e))
         .expect("Runtime error");
 
-    // Join all log entries into a single string and compare
-    let actual = logs.borrow().join("");
     let expected_with_newline = format!("{}\n", expected);
-    assert_eq!(actual, expected_with_newline);
+    assert_eq!(output.stdout(), expected_with_newline);
+}
+
+/// Runs `code` with the grammar in `grammar_source` and returns the runtime
+/// error it fails with.
+#[allow(dead_code)]
+pub fn runtime_error(grammar_source: &str, code: &str) -> RuntimeError {
+    let mut interp = Interpreter::from_grammar_str(grammar_source);
+    interp.capture_output();
+    match interp.eval(code) {
+        Err(InterpreterError::Runtime(e)) => e,
+        other => panic!("Expected runtime error, got {:?}", other),
+    }
 }
@@ -0,0 +1,113 @@
+use multilang::grammar::{Grammar, Rule};
+use multilang::parser::Parser;
+use multilang::trace::TraceOutcome;
+
+fn get_grammar() -> Grammar {
+    let grammar_def = r#"
+        Program = Stmt*
+        Stmt = Return | Print
+        Return = "return" value:Expr
+        Print = "print" "(" Expr ")"
+        Expr = Int
+        Int = [[0-9]+]
+    "#;
+    Grammar::parse(grammar_def)
+}
+
+#[test]
+fn test_trace_disabled_by_default() {
+    let grammar = get_grammar();
+    let parser = Parser::new(&grammar, "return 1");
+    parser.parse(Rule::Program).expect("Failed to parse");
+    assert!(parser.trace().is_none());
+}
+
+#[test]
+fn test_trace_records_alternatives() {
+    let grammar = get_grammar();
+    let parser = Parser::new(&grammar, "print(1)").with_trace();
+    parser.parse(Rule::Program).expect("Failed to parse");
+
+    let trace = parser.trace().expect("Trace should be recorded");
+    assert_eq!(trace.roots.len(), 1);
+    let program = &trace.roots[0];
+    assert_eq!(program.rule, Rule::Program);
+    assert_eq!(program.outcome, TraceOutcome::Matched { end: 8 });
+
+    // Stmt tries Return first (fails), then Print (matches).
+    let stmts: Vec<_> = program
+        .children
+        .iter()
+        .filter(|c| c.rule == Rule::Stmt && c.pos == 0)
+        .collect();
+    assert_eq!(stmts.len(), 2);
+    assert_eq!(stmts[0].alternative, Some(0));
+    assert_eq!(stmts[0].outcome, TraceOutcome::Failed);
+    assert_eq!(stmts[0].children[0].rule, Rule::Return);
+    assert_eq!(stmts[1].alternative, Some(1));
+    assert_eq!(stmts[1].outcome, TraceOutcome::Matched { end: 8 });
+}
+
+#[test]
+fn test_trace_records_cache_hits() {
+    // Both alternatives start with Expr, so the second one reuses the cached result.
+    let grammar_def = r#"
+        Program = Stmt*
+        Stmt = Expr "!" | Expr "?"
+        Expr = Int
+        Int = [[0-9]+]
+    "#;
+    let grammar = Grammar::parse(grammar_def);
+    let parser = Parser::new(&grammar, "1?").with_trace();
+    parser.parse(Rule::Program).expect("Failed to parse");
+
+    let trace = parser.trace().unwrap();
+    assert!(trace.render_tree().contains("Expr @ 1:1 cache hit (ok)"));
+
+    let summary = trace.summary();
+    assert!(summary.cache_hits > 0);
+    assert!(summary.cache_hit_rate() > 0.0 && summary.cache_hit_rate() < 1.0);
+    // One hit at "1" and one for the failed Expr attempt at end of input.
+    let expr = summary.rules.iter().find(|r| r.rule == Rule::Expr).unwrap();
+    assert_eq!(expr.cache_hits, 2);
+}
+
+#[test]
+fn test_trace_summary_orders_hottest_first() {
+    let grammar = get_grammar();
+    let parser = Parser::new(&grammar, "print(1) print(2) return 3").with_trace();
+    parser.parse(Rule::Program).expect("Failed to parse");
+
+    let summary = parser.trace().unwrap().summary();
+    let totals: Vec<usize> = summary
+        .rules
+        .iter()
+        .map(|r| r.attempts + r.cache_hits)
+        .collect();
+    let mut sorted = totals.clone();
+    sorted.sort_by(|a, b| b.cmp(a));
+    assert_eq!(totals, sorted);
+}
+
+#[test]
+fn test_trace_failed_parse() {
+    let grammar = get_grammar();
+    let parser = Parser::new(&grammar, "print(").with_trace();
+    assert!(parser.parse(Rule::Program).is_err());
+
+    let trace = parser.trace().unwrap();
+    let rendered = trace.render_tree();
+    assert!(rendered.contains("Print #0 @ 1:1 fail"));
+}
+
+#[test]
+fn test_trace_json_export() {
+    let grammar = get_grammar();
+    let parser = Parser::new(&grammar, "return 1").with_trace();
+    parser.parse(Rule::Program).expect("Failed to parse");
+
+    let json = parser.trace().unwrap().to_json();
+    assert!(json.starts_with("{\"trace\":[{\"rule\":\"Program\""));
+    assert!(json.contains("\"outcome\":\"matched\""));
+    assert!(json.contains("\"summary\":{\"lookups\":"));
+}
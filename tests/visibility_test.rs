@@ -0,0 +1,125 @@
+use multilang::grammar::Grammar;
+use multilang::interpreter::Interpreter;
+use multilang::node::Value;
+use std::fs;
+
+use crate::test_utils::run_code_and_check;
+
+mod test_utils;
+
+const GRAMMAR: &str = "tests/resources/standard/grammar.mlg";
+
+fn get_grammar() -> Grammar {
+    Grammar::parse(&fs::read_to_string(GRAMMAR).unwrap())
+}
+
+#[test]
+fn test_classes_are_visible_inside_functions() {
+    let code = r#"
+        class Point {
+            x
+            y
+        }
+        fn make(x) {
+            return new Point(x, x)
+        }
+        fn outer() {
+            return make(2)
+        }
+        print(outer().y)
+        f = fn() { return new Point(3, 4) }
+        print(f().x)
+    "#;
+    run_code_and_check(&get_grammar(), code, "2\n3");
+}
+
+#[test]
+fn test_classes_inside_functions_are_global() {
+    let code = r#"
+        fn setup(n) {
+            class Counter {
+                count = n
+            }
+        }
+        setup(4)
+        print(new Counter().count)
+    "#;
+    run_code_and_check(&get_grammar(), code, "4");
+}
+
+#[test]
+fn test_nested_functions_are_local() {
+    let code = r#"
+        fn helper() {
+            return "top"
+        }
+        fn f() {
+            fn helper() {
+                return "inner"
+            }
+            return helper()
+        }
+        print(f())
+        print(helper())
+    "#;
+    run_code_and_check(&get_grammar(), code, "inner\ntop");
+
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    interp.capture_output();
+    let err = interp
+        .eval("fn outer() {\n fn inner() {\n return 1\n }\n}\nouter()\ninner()")
+        .unwrap_err();
+    assert!(err.to_string().contains("Function 'inner' not found"));
+}
+
+#[test]
+fn test_nested_functions_recurse_and_close_over_locals() {
+    let code = r#"
+        fn outer(n) {
+            fn countdown(i) {
+                if i == 0 {
+                    return n
+                }
+                return countdown(i - 1)
+            }
+            return countdown(3)
+        }
+        print(outer(9))
+    "#;
+    run_code_and_check(&get_grammar(), code, "9");
+}
+
+#[test]
+fn test_functions_see_later_definitions() {
+    let code = r#"
+        fn describe() {
+            return new Late().name + " " + later()
+        }
+        class Late {
+            name = "late"
+        }
+        fn later() {
+            return "too"
+        }
+        print(describe())
+    "#;
+    run_code_and_check(&get_grammar(), code, "late too");
+}
+
+#[test]
+fn test_calls_share_globals_with_the_interpreter() {
+    let mut interp = Interpreter::from_grammar_file(GRAMMAR).unwrap();
+    interp.capture_output();
+    interp
+        .eval("fn define() {\n class Inner {\n x = 7\n }\n}\ndefine()")
+        .unwrap();
+    assert!(
+        interp
+            .context()
+            .globals
+            .borrow()
+            .classes
+            .contains_key("Inner")
+    );
+    assert_eq!(interp.eval("new Inner().x").unwrap(), Value::Int(7));
+}